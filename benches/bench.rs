@@ -17,7 +17,9 @@ mod merge {
         merge_benches,
         vec_merge_bench,
         array_merge_bench,
-        tuple_merge_bench
+        tuple_merge_bench,
+        pair_merge_bench,
+        vec_merge_burst_bench
     );
 
     fn vec_merge_bench(c: &mut Criterion) {
@@ -53,6 +55,26 @@ mod merge {
         });
     }
 
+    fn pair_merge_bench(c: &mut Criterion) {
+        c.bench_function("vec::merge 2", |b| {
+            b.to_async(FuturesExecutor).iter(|| vec_merge(black_box(2)))
+        });
+        c.bench_function("stream::Merge2", |b| {
+            b.to_async(FuturesExecutor).iter(pair_merge)
+        });
+    }
+
+    fn vec_merge_burst_bench(c: &mut Criterion) {
+        c.bench_function("vec::merge burst 100x10", |b| {
+            b.to_async(FuturesExecutor)
+                .iter(|| vec_merge_burst(black_box(100), false))
+        });
+        c.bench_function("vec::merge burst 100x10 batched_drain", |b| {
+            b.to_async(FuturesExecutor)
+                .iter(|| vec_merge_burst(black_box(100), true))
+        });
+    }
+
     async fn vec_merge(max: usize) {
         let mut counter = 0;
         let streams = streams_vec(max);
@@ -63,6 +85,22 @@ mod merge {
         assert_eq!(counter, max);
     }
 
+    /// Every stream is always ready, so each poll finds all of them ready.
+    async fn vec_merge_burst(max: usize, batched: bool) {
+        let mut counter = 0;
+        let streams: Vec<_> = (0..max)
+            .map(|_| futures_lite::stream::iter(0..10))
+            .collect();
+        let mut s = streams.merge();
+        if batched {
+            s = s.batched_drain();
+        }
+        while s.next().await.is_some() {
+            counter += 1;
+        }
+        assert_eq!(counter, max * 10);
+    }
+
     async fn array_merge<const N: usize>() {
         block_on(async move {
             let mut counter = 0;
@@ -75,6 +113,16 @@ mod merge {
         })
     }
 
+    async fn pair_merge() {
+        let mut counter = 0;
+        let [a, b] = streams_array::<2>();
+        let mut s = a.merge(b);
+        while s.next().await.is_some() {
+            counter += 1;
+        }
+        assert_eq!(counter, 2);
+    }
+
     async fn tuple_merge() {
         block_on(async move {
             let mut counter = 0;
@@ -114,6 +162,14 @@ mod join {
             b.to_async(FuturesExecutor)
                 .iter(|| vec_join(black_box(1000)))
         });
+        c.bench_function("vec::join 10000", |b| {
+            b.to_async(FuturesExecutor)
+                .iter(|| vec_join(black_box(10000)))
+        });
+        c.bench_function("vec::join 10000 sparse", |b| {
+            b.to_async(FuturesExecutor)
+                .iter(|| vec_join_sparse(black_box(10000)))
+        });
     }
 
     fn array_join_bench(c: &mut Criterion) {
@@ -126,6 +182,54 @@ mod join {
         c.bench_function("array::join 1000", |b| {
             b.to_async(FuturesExecutor).iter(array_join::<1000>)
         });
+        c.bench_function("array::join 2 sparse", |b| {
+            b.to_async(FuturesExecutor)
+                .iter(|| array_join_sparse::<2>(black_box(true)))
+        });
+        c.bench_function("array::join 2 sparse unselective", |b| {
+            b.to_async(FuturesExecutor)
+                .iter(|| array_join_sparse::<2>(black_box(false)))
+        });
+        c.bench_function("array::join 4 sparse", |b| {
+            b.to_async(FuturesExecutor)
+                .iter(|| array_join_sparse::<4>(black_box(true)))
+        });
+        c.bench_function("array::join 4 sparse unselective", |b| {
+            b.to_async(FuturesExecutor)
+                .iter(|| array_join_sparse::<4>(black_box(false)))
+        });
+        c.bench_function("array::join 8 sparse", |b| {
+            b.to_async(FuturesExecutor)
+                .iter(|| array_join_sparse::<8>(black_box(true)))
+        });
+        c.bench_function("array::join 8 sparse unselective", |b| {
+            b.to_async(FuturesExecutor)
+                .iter(|| array_join_sparse::<8>(black_box(false)))
+        });
+        c.bench_function("array::join 16 sparse", |b| {
+            b.to_async(FuturesExecutor)
+                .iter(|| array_join_sparse::<16>(black_box(true)))
+        });
+        c.bench_function("array::join 16 sparse unselective", |b| {
+            b.to_async(FuturesExecutor)
+                .iter(|| array_join_sparse::<16>(black_box(false)))
+        });
+        c.bench_function("array::join 32 sparse", |b| {
+            b.to_async(FuturesExecutor)
+                .iter(|| array_join_sparse::<32>(black_box(true)))
+        });
+        c.bench_function("array::join 32 sparse unselective", |b| {
+            b.to_async(FuturesExecutor)
+                .iter(|| array_join_sparse::<32>(black_box(false)))
+        });
+        c.bench_function("array::join 64 sparse", |b| {
+            b.to_async(FuturesExecutor)
+                .iter(|| array_join_sparse::<64>(black_box(true)))
+        });
+        c.bench_function("array::join 64 sparse unselective", |b| {
+            b.to_async(FuturesExecutor)
+                .iter(|| array_join_sparse::<64>(black_box(false)))
+        });
     }
 
     fn tuple_join_bench(c: &mut Criterion) {
@@ -140,12 +244,49 @@ mod join {
         assert_eq!(output.len(), max);
     }
 
+    /// Wake the futures one at a time, so most polls only find a single
+    /// future ready.
+    async fn vec_join_sparse(max: usize) {
+        let (senders, receivers): (Vec<_>, Vec<_>) = (0..max)
+            .map(|_| futures::channel::oneshot::channel())
+            .unzip();
+        let send = async move {
+            for sender in senders {
+                sender.send(()).unwrap();
+                futures_lite::future::yield_now().await;
+            }
+        };
+        let (output, ()) = (receivers.join(), send).join().await;
+        assert_eq!(output.len(), max);
+    }
+
     async fn array_join<const N: usize>() {
         let futures = futures_array::<N>();
         let output = futures.join().await;
         assert_eq!(output.len(), N);
     }
 
+    /// Wake the futures one at a time, in reverse order, comparing the
+    /// default join against its unselective mode.
+    async fn array_join_sparse<const N: usize>(selective: bool) {
+        use std::convert::TryInto;
+
+        let (senders, receivers): (Vec<_>, Vec<_>) = (0..N)
+            .map(|_| futures::channel::oneshot::channel::<()>())
+            .unzip();
+        let receivers: [_; N] = receivers.try_into().unwrap();
+        let send = async move {
+            for sender in senders.into_iter().rev() {
+                sender.send(()).unwrap();
+                futures_lite::future::yield_now().await;
+            }
+        };
+        let join = receivers.join();
+        let join = if selective { join } else { join.unselective() };
+        let (output, ()) = (join, send).join().await;
+        assert_eq!(output.len(), N);
+    }
+
     async fn tuple_join() {
         let futures = futures_tuple();
         let output = futures.join().await;
@@ -165,6 +306,7 @@ mod race {
     criterion_group!(
         race_benches,
         vec_race_bench,
+        vec_racer_bench,
         array_race_bench,
         tuple_race_bench
     );
@@ -183,6 +325,17 @@ mod race {
         });
     }
 
+    fn vec_racer_bench(c: &mut Criterion) {
+        c.bench_function("vec::race 10 x100", |b| {
+            b.to_async(FuturesExecutor)
+                .iter(|| vec_race_loop(black_box(10)))
+        });
+        c.bench_function("vec::race 10 x100 racer", |b| {
+            b.to_async(FuturesExecutor)
+                .iter(|| vec_racer_loop(black_box(10)))
+        });
+    }
+
     fn array_race_bench(c: &mut Criterion) {
         c.bench_function("array::race 10", |b| {
             b.to_async(FuturesExecutor).iter(array_race::<10>)
@@ -207,6 +360,25 @@ mod race {
         assert_eq!(output, ());
     }
 
+    /// Race a fresh set of futures on every iteration of a loop.
+    async fn vec_race_loop(max: usize) {
+        for _ in 0..100 {
+            let futures: Vec<_> = (0..max).map(|_| std::future::ready(())).collect();
+            futures.race().await;
+        }
+    }
+
+    /// Race a fresh set of futures on every iteration of a loop, reusing the
+    /// storage between iterations.
+    async fn vec_racer_loop(max: usize) {
+        let mut racer = futures_concurrency::vec::Racer::new();
+        for _ in 0..100 {
+            racer
+                .race_next((0..max).map(|_| std::future::ready(())))
+                .await;
+        }
+    }
+
     async fn array_race<const N: usize>() {
         let futures = futures_array::<N>();
         let output = futures.race().await;
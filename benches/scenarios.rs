@@ -0,0 +1,217 @@
+//! Benchmarks of the combinators under controlled readiness patterns.
+//!
+//! Each child future or stream is driven by a [`Control`], so every scenario
+//! decides exactly how many children are ready each time the combinator is
+//! polled. Where `futures` has an equivalent operation it's measured in the
+//! same group, so the results can be compared side by side.
+
+use criterion::async_executor::FuturesExecutor;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use futures_concurrency::prelude::*;
+use futures_lite::prelude::*;
+
+mod utils;
+
+use utils::Control;
+
+/// Visit every index in `0..len` in a scattered order, so neighbouring
+/// children aren't released back to back.
+fn scattered(len: usize) -> impl Iterator<Item = usize> {
+    // 7919 is a prime larger than any size benchmarked here, so it's coprime
+    // with `len` and this is a permutation of `0..len`.
+    (0..len).map(move |n| (n * 7919) % len)
+}
+
+fn join_scenarios(c: &mut Criterion) {
+    let mut group = c.benchmark_group("scenario join");
+    for len in [2, 8, 64, 1024] {
+        // Every future is ready on the first poll.
+        group.bench_with_input(BenchmarkId::new("ready", len), &len, |b, &len| {
+            b.to_async(FuturesExecutor).iter(|| async move {
+                let output = Control::new(len, 1).futures().join().await;
+                assert_eq!(output.len(), len);
+            })
+        });
+        group.bench_with_input(
+            BenchmarkId::new("ready futures-rs", len),
+            &len,
+            |b, &len| {
+                b.to_async(FuturesExecutor).iter(|| async move {
+                    let futures = Control::new(len, 1).futures();
+                    let output = futures::future::join_all(futures).await;
+                    assert_eq!(output.len(), len);
+                })
+            },
+        );
+
+        // Futures are woken one at a time, so almost every poll finds all
+        // but one future pending. This is the common case in practice.
+        group.bench_with_input(BenchmarkId::new("pending-heavy", len), &len, |b, &len| {
+            b.to_async(FuturesExecutor).iter(|| async move {
+                let control = Control::new(len, 0);
+                let futures = control.futures();
+                let driver = control.drive(scattered(len), 1);
+                let (output, ()) = (futures.join(), driver).join().await;
+                assert_eq!(output.len(), len);
+            })
+        });
+        group.bench_with_input(
+            BenchmarkId::new("pending-heavy futures-rs", len),
+            &len,
+            |b, &len| {
+                b.to_async(FuturesExecutor).iter(|| async move {
+                    let control = Control::new(len, 0);
+                    let futures = futures::future::join_all(control.futures());
+                    let driver = control.drive(scattered(len), 1);
+                    let (output, ()) = (futures, driver).join().await;
+                    assert_eq!(output.len(), len);
+                })
+            },
+        );
+
+        // All futures are woken at once after having been polled.
+        group.bench_with_input(BenchmarkId::new("burst", len), &len, |b, &len| {
+            b.to_async(FuturesExecutor).iter(|| async move {
+                let control = Control::new(len, 0);
+                let futures = control.futures();
+                let driver = control.drive(0..len, len);
+                let (output, ()) = (futures.join(), driver).join().await;
+                assert_eq!(output.len(), len);
+            })
+        });
+    }
+    group.finish();
+}
+
+fn merge_scenarios(c: &mut Criterion) {
+    const ITEMS: usize = 4;
+
+    let mut group = c.benchmark_group("scenario merge");
+    for len in [4, 64, 512] {
+        // Every stream is ready for all of its items straight away.
+        group.bench_with_input(BenchmarkId::new("ready", len), &len, |b, &len| {
+            b.to_async(FuturesExecutor).iter(|| async move {
+                let streams = Control::new(len, ITEMS).streams(ITEMS);
+                let count = streams.merge().count().await;
+                assert_eq!(count, len * ITEMS);
+            })
+        });
+        group.bench_with_input(
+            BenchmarkId::new("ready futures-rs", len),
+            &len,
+            |b, &len| {
+                b.to_async(FuturesExecutor).iter(|| async move {
+                    let streams = Control::new(len, ITEMS).streams(ITEMS);
+                    let count = futures::stream::select_all(streams).count().await;
+                    assert_eq!(count, len * ITEMS);
+                })
+            },
+        );
+
+        // Every stream gets an item each round, and rounds are separated
+        // by a yield.
+        group.bench_with_input(BenchmarkId::new("round-robin", len), &len, |b, &len| {
+            b.to_async(FuturesExecutor).iter(|| async move {
+                let control = Control::new(len, 0);
+                let streams = control.streams(ITEMS);
+                let order = (0..ITEMS).flat_map(|_| 0..len);
+                let driver = control.drive(order, len);
+                let (count, ()) = (streams.merge().count(), driver).join().await;
+                assert_eq!(count, len * ITEMS);
+            })
+        });
+
+        // A single stream is ready at a time, in a scattered order.
+        group.bench_with_input(BenchmarkId::new("pending-heavy", len), &len, |b, &len| {
+            b.to_async(FuturesExecutor).iter(|| async move {
+                let control = Control::new(len, 0);
+                let streams = control.streams(ITEMS);
+                let order = (0..ITEMS).flat_map(|_| scattered(len));
+                let driver = control.drive(order, 1);
+                let (count, ()) = (streams.merge().count(), driver).join().await;
+                assert_eq!(count, len * ITEMS);
+            })
+        });
+        group.bench_with_input(
+            BenchmarkId::new("pending-heavy futures-rs", len),
+            &len,
+            |b, &len| {
+                b.to_async(FuturesExecutor).iter(|| async move {
+                    let control = Control::new(len, 0);
+                    let streams = futures::stream::select_all(control.streams(ITEMS));
+                    let order = (0..ITEMS).flat_map(|_| scattered(len));
+                    let driver = control.drive(order, 1);
+                    let (count, ()) = (streams.count(), driver).join().await;
+                    assert_eq!(count, len * ITEMS);
+                })
+            },
+        );
+
+        // Most streams finish after their first item, leaving a handful of
+        // long-lived streams which are woken one at a time.
+        group.bench_with_input(BenchmarkId::new("dwindling", len), &len, |b, &len| {
+            b.to_async(FuturesExecutor).iter(|| async move {
+                const LIVE: usize = 4;
+                const LONG: usize = 64;
+                let control = Control::new(len, 1);
+                let streams: Vec<_> = control
+                    .streams(LONG)
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, s)| s.take(if index < LIVE { LONG } else { 1 }))
+                    .collect();
+                let order = (1..LONG).flat_map(|_| 0..LIVE);
+                let driver = control.drive(order, 1);
+                let (count, ()) = (streams.merge().count(), driver).join().await;
+                assert_eq!(count, len - LIVE + LIVE * LONG);
+            })
+        });
+    }
+    group.finish();
+}
+
+fn race_scenarios(c: &mut Criterion) {
+    let mut group = c.benchmark_group("scenario race");
+    for len in [2, 8, 64, 1024] {
+        // Only the last future is ready, so the whole set is scanned once.
+        group.bench_with_input(BenchmarkId::new("single-ready", len), &len, |b, &len| {
+            b.to_async(FuturesExecutor).iter(|| async move {
+                let control = Control::new(len, 0);
+                control.release(len - 1);
+                control.futures().race().await;
+            })
+        });
+        group.bench_with_input(
+            BenchmarkId::new("single-ready futures-rs", len),
+            &len,
+            |b, &len| {
+                b.to_async(FuturesExecutor).iter(|| async move {
+                    let control = Control::new(len, 0);
+                    control.release(len - 1);
+                    let (_, index, _) = futures::future::select_all(control.futures()).await;
+                    assert_eq!(index, len - 1);
+                })
+            },
+        );
+
+        // Nothing is ready on the first poll, and a single future is woken
+        // afterwards.
+        group.bench_with_input(BenchmarkId::new("pending-heavy", len), &len, |b, &len| {
+            b.to_async(FuturesExecutor).iter(|| async move {
+                let control = Control::new(len, 0);
+                let race = control.futures().race();
+                let driver = control.drive([len / 2], 1);
+                (race, driver).join().await;
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(
+    scenario_benches,
+    join_scenarios,
+    merge_scenarios,
+    race_scenarios
+);
+criterion_main!(scenario_benches);
@@ -230,3 +230,124 @@ impl Future for CountdownFuture {
         }
     }
 }
+
+/// Shared readiness for a set of [`ControlledFuture`]s and [`ControlledStream`]s.
+///
+/// Every call to [`Control::release`] hands out one credit to the future or
+/// stream at that index and wakes it. A future completes once it holds a
+/// credit, and a stream spends one credit for every item it yields. This lets
+/// benchmarks decide exactly which children are ready on each poll.
+#[derive(Clone)]
+pub struct Control {
+    state: Rc<RefCell<ControlState>>,
+}
+
+struct ControlState {
+    credits: Vec<usize>,
+    wakers: Vec<Option<Waker>>,
+}
+
+impl Control {
+    /// Create a control for `len` children, each starting with `credits`
+    /// credits.
+    pub fn new(len: usize, credits: usize) -> Self {
+        let state = ControlState {
+            credits: vec![credits; len],
+            wakers: vec![None; len],
+        };
+        Self {
+            state: Rc::new(RefCell::new(state)),
+        }
+    }
+
+    /// Give the child at `index` one more credit, and wake it.
+    pub fn release(&self, index: usize) {
+        let mut state = self.state.borrow_mut();
+        state.credits[index] += 1;
+        if let Some(waker) = state.wakers[index].take() {
+            waker.wake();
+        }
+    }
+
+    /// Release the children in `order`, yielding to the executor after every
+    /// `batch` releases.
+    pub async fn drive(self, order: impl IntoIterator<Item = usize>, batch: usize) {
+        for (n, index) in order.into_iter().enumerate() {
+            self.release(index);
+            if (n + 1) % batch == 0 {
+                futures_lite::future::yield_now().await;
+            }
+        }
+    }
+
+    /// Create one future per child.
+    pub fn futures(&self) -> Vec<ControlledFuture> {
+        let len = self.state.borrow().credits.len();
+        (0..len)
+            .map(|index| ControlledFuture {
+                index,
+                state: self.state.clone(),
+            })
+            .collect()
+    }
+
+    /// Create one stream per child, each yielding `items` items.
+    pub fn streams(&self, items: usize) -> Vec<ControlledStream> {
+        let len = self.state.borrow().credits.len();
+        (0..len)
+            .map(|index| ControlledStream {
+                index,
+                remaining: items,
+                state: self.state.clone(),
+            })
+            .collect()
+    }
+}
+
+/// A future which completes once its [`Control`] has released it.
+pub struct ControlledFuture {
+    index: usize,
+    state: Rc<RefCell<ControlState>>,
+}
+
+impl Future for ControlledFuture {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.state.borrow_mut();
+        if state.credits[self.index] > 0 {
+            Poll::Ready(())
+        } else {
+            state.wakers[self.index] = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+/// A stream which yields an item each time its [`Control`] releases it.
+pub struct ControlledStream {
+    index: usize,
+    remaining: usize,
+    state: Rc<RefCell<ControlState>>,
+}
+
+impl Stream for ControlledStream {
+    type Item = ();
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.remaining == 0 {
+            return Poll::Ready(None);
+        }
+        let index = self.index;
+        let state = self.state.clone();
+        let mut state = state.borrow_mut();
+        if state.credits[index] > 0 {
+            state.credits[index] -= 1;
+            self.remaining -= 1;
+            Poll::Ready(Some(()))
+        } else {
+            state.wakers[index] = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
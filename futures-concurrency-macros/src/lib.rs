@@ -0,0 +1,161 @@
+//! Derive macros for [`futures-concurrency`].
+//!
+//! This crate is an implementation detail. Enable the `derive` feature of
+//! `futures-concurrency` and use the macros from there instead.
+//!
+//! [`futures-concurrency`]: https://docs.rs/futures-concurrency
+
+#![deny(missing_debug_implementations, nonstandard_style)]
+#![warn(missing_docs, unreachable_pub)]
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields};
+
+/// The largest tuple the tuple `Join` is implemented for.
+const MAX_FIELDS: usize = 16;
+
+/// Derive a `join_fields` method which awaits every field of a struct
+/// concurrently.
+///
+/// Every field of the struct must implement `IntoFuture`. The derive
+/// generates a `{Name}Output` struct with the same shape, where each field
+/// holds the output of the corresponding future, and a `join_fields` method
+/// which joins all fields like a tuple `Join` and resolves to that struct.
+/// Both have the same visibility as the deriving struct.
+///
+/// Named, tuple and unit structs are supported, with at most 16 fields.
+///
+/// # Examples
+///
+/// ```
+/// use futures_concurrency::future::Join;
+/// use futures_lite::future::block_on;
+/// use std::future::{ready, Ready};
+///
+/// #[derive(Join)]
+/// struct Config<F> {
+///     name: Ready<&'static str>,
+///     retries: F,
+/// }
+///
+/// block_on(async {
+///     let config = Config {
+///         name: ready("server"),
+///         retries: async { 3 },
+///     };
+///     let ConfigOutput { name, retries } = config.join_fields().await;
+///     assert_eq!((name, retries), ("server", 3));
+/// })
+/// ```
+#[proc_macro_derive(Join)]
+pub fn derive_join(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        Data::Enum(data) => {
+            return Err(Error::new_spanned(
+                data.enum_token,
+                "`Join` can only be derived for structs",
+            ))
+        }
+        Data::Union(data) => {
+            return Err(Error::new_spanned(
+                data.union_token,
+                "`Join` can only be derived for structs",
+            ))
+        }
+    };
+    if fields.len() > MAX_FIELDS {
+        return Err(Error::new_spanned(
+            &input.ident,
+            format!("`Join` can only be derived for structs with at most {MAX_FIELDS} fields"),
+        ));
+    }
+
+    let vis = &input.vis;
+    let name = &input.ident;
+    let output = format_ident!("{}Output", name);
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    // Every field has to be a future, both for the method and for the output
+    // struct to name the resolved types.
+    let types: Vec<_> = fields.iter().map(|field| &field.ty).collect();
+    let predicates = where_clause
+        .into_iter()
+        .flat_map(|clause| &clause.predicates);
+    let where_clause = quote! {
+        where
+            #(#predicates,)*
+            #(#types: ::core::future::IntoFuture,)*
+    };
+
+    let bindings: Vec<_> = fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| match &field.ident {
+            Some(ident) => ident.clone(),
+            None => format_ident!("__field{}", index),
+        })
+        .collect();
+    let output_fields = fields.iter().map(|field| {
+        let attrs = field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("doc"));
+        let vis = &field.vis;
+        let ty = &field.ty;
+        let ty = quote!(<#ty as ::core::future::IntoFuture>::Output);
+        match &field.ident {
+            Some(ident) => quote!(#(#attrs)* #vis #ident: #ty),
+            None => quote!(#(#attrs)* #vis #ty),
+        }
+    });
+
+    let (pattern, output_def, construct) = match fields {
+        Fields::Named(_) => (
+            quote!(Self { #(#bindings),* }),
+            quote!(#vis struct #output #impl_generics #where_clause { #(#output_fields,)* }),
+            quote!(#output { #(#bindings),* }),
+        ),
+        Fields::Unnamed(_) => (
+            quote!(Self(#(#bindings),*)),
+            quote!(#vis struct #output #impl_generics (#(#output_fields,)*) #where_clause;),
+            quote!(#output(#(#bindings),*)),
+        ),
+        Fields::Unit => (
+            quote!(Self),
+            quote!(#vis struct #output #impl_generics #where_clause;),
+            quote!(#output),
+        ),
+    };
+
+    let output_doc = format!("The resolved fields of [`{name}`].");
+    let method_doc =
+        format!("Await all fields of this `{name}` concurrently, resolving to a [`{output}`].");
+
+    Ok(quote! {
+        #[doc = #output_doc]
+        #output_def
+
+        impl #impl_generics #name #ty_generics #where_clause {
+            #[doc = #method_doc]
+            #[must_use = "futures do nothing unless you `.await` or poll them"]
+            #vis fn join_fields(self) -> impl ::core::future::Future<Output = #output #ty_generics> {
+                let #pattern = self;
+                async move {
+                    let (#(#bindings,)*) =
+                        ::futures_concurrency::future::Join::join((#(#bindings,)*)).await;
+                    #construct
+                }
+            }
+        }
+    })
+}
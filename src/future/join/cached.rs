@@ -0,0 +1,103 @@
+use super::vec::Join;
+
+use core::fmt;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{ready, Context, Poll};
+
+use pin_project::pin_project;
+
+/// Wait for all futures to complete, and keep their outputs around to be
+/// awaited again.
+///
+/// This `struct` is created by the [`join_cached`] method on [`Join`]. See
+/// its documentation for more.
+///
+/// [`join_cached`]: Join::join_cached
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+#[pin_project]
+pub struct CachedJoin<Fut>
+where
+    Fut: Future,
+{
+    #[pin]
+    join: Join<Fut>,
+    outputs: Option<Vec<Fut::Output>>,
+}
+
+impl<Fut> CachedJoin<Fut>
+where
+    Fut: Future,
+{
+    pub(crate) fn new(join: Join<Fut>) -> Self {
+        Self {
+            join,
+            outputs: None,
+        }
+    }
+
+    /// The outputs of the futures, if they have all completed.
+    pub fn outputs(&self) -> Option<&[Fut::Output]> {
+        self.outputs.as_deref()
+    }
+}
+
+impl<Fut> fmt::Debug for CachedJoin<Fut>
+where
+    Fut: Future + fmt::Debug,
+    Fut::Output: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CachedJoin")
+            .field("join", &self.join)
+            .field("outputs", &self.outputs)
+            .finish()
+    }
+}
+
+impl<Fut> Future for CachedJoin<Fut>
+where
+    Fut: Future,
+    Fut::Output: Clone,
+{
+    type Output = Vec<Fut::Output>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        if let Some(outputs) = this.outputs {
+            return Poll::Ready(outputs.clone());
+        }
+        let outputs = ready!(this.join.poll(cx));
+        Poll::Ready(this.outputs.insert(outputs).clone())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::future::Join as _;
+    use futures_lite::future::{block_on, yield_now};
+    use std::cell::Cell;
+    use std::pin::pin;
+
+    #[test]
+    fn runs_once_and_returns_clones() {
+        block_on(async {
+            let runs = Cell::new(0);
+            let task = |n| {
+                let runs = &runs;
+                async move {
+                    yield_now().await;
+                    runs.set(runs.get() + 1);
+                    n
+                }
+            };
+            let mut join = pin!(vec![task(1), task(2)].join().join_cached());
+            assert_eq!(join.outputs(), None);
+
+            assert_eq!(join.as_mut().await, vec![1, 2]);
+            assert_eq!(join.as_mut().await, vec![1, 2]);
+            assert_eq!(join.outputs(), Some(&[1, 2][..]));
+            assert_eq!(runs.get(), 2);
+        })
+    }
+}
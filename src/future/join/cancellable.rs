@@ -0,0 +1,186 @@
+use core::fmt;
+use core::future::Future;
+use core::pin::Pin;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use core::task::{Context, Poll, Waker};
+use std::error::Error;
+use std::sync::{Arc, Mutex};
+
+use pin_project::pin_project;
+
+/// Wait for all futures to complete, with a handle to cancel each of them.
+///
+/// This is implemented for tuples of futures.
+pub trait JoinCancellable {
+    /// The resulting output type.
+    type Output;
+
+    /// The cancellation handles, one for each future.
+    type Handles;
+
+    /// Which kind of future are we turning this into?
+    type Future: Future<Output = Result<Self::Output, Cancelled>>;
+
+    /// Waits for multiple futures to complete, unless one of them is cancelled.
+    ///
+    /// Returns the joined future along with one [`CancelHandle`] per future,
+    /// in the same order as the futures. Calling [`cancel`][CancelHandle::cancel]
+    /// on any handle drops all futures and their outputs, and makes the join
+    /// resolve to `Err(Cancelled { index })`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_concurrency::future::{Cancelled, JoinCancellable};
+    /// use futures_lite::future::{block_on, pending};
+    /// use std::future;
+    ///
+    /// block_on(async {
+    ///     let a = future::ready(1);
+    ///     let b = pending::<u8>();
+    ///     let (fut, handles) = (a, b).join_cancellable();
+    ///     handles.1.cancel();
+    ///     assert_eq!(fut.await, Err(Cancelled { index: 1 }));
+    /// })
+    /// ```
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    fn join_cancellable(self) -> (Self::Future, Self::Handles);
+}
+
+/// An error returned when a future in a cancellable join was cancelled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cancelled {
+    /// The index of the future whose handle was cancelled.
+    pub index: usize,
+}
+
+impl fmt::Display for Cancelled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "future at index {} was cancelled", self.index)
+    }
+}
+
+impl Error for Cancelled {}
+
+/// State shared between a [`Cancellable`] future and its handles.
+#[derive(Debug)]
+pub(crate) struct Shared {
+    // `0` means nothing was cancelled, otherwise the cancelled index plus one.
+    cancelled: AtomicUsize,
+    waker: Mutex<Option<Waker>>,
+}
+
+impl Shared {
+    pub(crate) fn new() -> Arc<Self> {
+        Arc::new(Self {
+            cancelled: AtomicUsize::new(0),
+            waker: Mutex::new(None),
+        })
+    }
+
+    fn cancelled(&self) -> Option<usize> {
+        match self.cancelled.load(Ordering::Acquire) {
+            0 => None,
+            n => Some(n - 1),
+        }
+    }
+}
+
+/// A handle to cancel one of the futures in a cancellable join.
+///
+/// This `struct` is created by the [`join_cancellable`] method on the
+/// [`JoinCancellable`] trait. See its documentation for more.
+///
+/// [`join_cancellable`]: JoinCancellable::join_cancellable
+#[derive(Debug, Clone)]
+pub struct CancelHandle {
+    shared: Arc<Shared>,
+    index: usize,
+}
+
+impl CancelHandle {
+    pub(crate) fn new(shared: &Arc<Shared>, index: usize) -> Self {
+        Self {
+            shared: shared.clone(),
+            index,
+        }
+    }
+
+    /// Cancel the join this handle belongs to.
+    ///
+    /// If another handle of the same join was cancelled first, its index is
+    /// the one reported. Cancelling after the join has completed does nothing.
+    pub fn cancel(&self) {
+        let res = self.shared.cancelled.compare_exchange(
+            0,
+            self.index + 1,
+            Ordering::AcqRel,
+            Ordering::Acquire,
+        );
+        if res.is_ok() {
+            if let Some(waker) = self.shared.waker.lock().unwrap().take() {
+                waker.wake();
+            }
+        }
+    }
+}
+
+/// Wait for all futures to complete, unless one of them is cancelled.
+///
+/// This `struct` is created by the [`join_cancellable`] method on the
+/// [`JoinCancellable`] trait. See its documentation for more.
+///
+/// [`join_cancellable`]: JoinCancellable::join_cancellable
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+#[pin_project]
+pub struct Cancellable<Fut> {
+    #[pin]
+    future: Option<Fut>,
+    shared: Arc<Shared>,
+}
+
+impl<Fut> Cancellable<Fut> {
+    pub(crate) fn new(future: Fut, shared: Arc<Shared>) -> Self {
+        Self {
+            future: Some(future),
+            shared,
+        }
+    }
+}
+
+impl<Fut: fmt::Debug> fmt::Debug for Cancellable<Fut> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Cancellable").field(&self.future).finish()
+    }
+}
+
+impl<Fut: Future> Future for Cancellable<Fut> {
+    type Output = Result<Fut::Output, Cancelled>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+        assert!(
+            this.future.is_some(),
+            "Futures must not be polled after completing"
+        );
+
+        // Register the waker before checking the flag, so a cancellation which
+        // races with this poll is never missed.
+        *this.shared.waker.lock().unwrap() = Some(cx.waker().clone());
+
+        if let Some(index) = this.shared.cancelled() {
+            // Drop the futures and any outputs they produced before returning.
+            this.future.set(None);
+            return Poll::Ready(Err(Cancelled { index }));
+        }
+
+        let future = this.future.as_mut().as_pin_mut().unwrap();
+        match future.poll(cx) {
+            Poll::Ready(output) => {
+                this.future.set(None);
+                Poll::Ready(Ok(output))
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
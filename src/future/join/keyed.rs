@@ -0,0 +1,214 @@
+use super::vec::Join;
+
+use core::fmt;
+use core::future::{Future, IntoFuture};
+use core::hash::Hash;
+use core::iter::FromIterator;
+use core::marker::PhantomData;
+use core::pin::Pin;
+use core::task::{ready, Context, Poll};
+use std::collections::{BTreeMap, HashMap};
+
+use pin_project::pin_project;
+
+/// Wait for all futures to complete, collecting their outputs by key.
+///
+/// This is implemented for vectors of `(key, future)` pairs. If the same key
+/// appears more than once, the output of the last future with that key wins.
+pub trait JoinKeyed {
+    /// The key each future is labeled with.
+    type Key;
+
+    /// The output of each future.
+    type Value;
+
+    /// The future type being joined.
+    type Future: Future<Output = Self::Value>;
+
+    /// Waits for all futures to complete, returning their outputs in a
+    /// [`HashMap`] keyed by the label of each future.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_concurrency::prelude::*;
+    /// use futures_lite::future::block_on;
+    /// use std::future;
+    ///
+    /// block_on(async {
+    ///     let map = vec![("a", future::ready(1)), ("b", future::ready(2))]
+    ///         .join_keyed()
+    ///         .await;
+    ///     assert_eq!(map["b"], 2);
+    /// })
+    /// ```
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    fn join_keyed(self) -> KeyedHashJoin<Self::Key, Self::Future>
+    where
+        Self::Key: Hash + Eq;
+
+    /// Waits for all futures to complete, returning their outputs in a
+    /// [`BTreeMap`] keyed by the label of each future.
+    ///
+    /// Unlike [`join_keyed`][JoinKeyed::join_keyed], iterating over the map
+    /// visits the outputs in key order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_concurrency::prelude::*;
+    /// use futures_lite::future::block_on;
+    /// use std::future;
+    ///
+    /// block_on(async {
+    ///     let map = vec![("b", future::ready(2)), ("a", future::ready(1))]
+    ///         .join_keyed_ordered()
+    ///         .await;
+    ///     assert_eq!(map.into_iter().collect::<Vec<_>>(), vec![("a", 1), ("b", 2)]);
+    /// })
+    /// ```
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    fn join_keyed_ordered(self) -> KeyedBTreeJoin<Self::Key, Self::Future>
+    where
+        Self::Key: Ord;
+}
+
+/// Waits for all futures to complete, collecting their outputs by key.
+///
+/// This `struct` is created by the [`join_keyed`] and [`join_keyed_ordered`]
+/// methods on the [`JoinKeyed`] trait. See its documentation for more.
+///
+/// [`join_keyed`]: JoinKeyed::join_keyed
+/// [`join_keyed_ordered`]: JoinKeyed::join_keyed_ordered
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+#[pin_project]
+pub struct KeyedJoin<K, Fut, M>
+where
+    Fut: Future,
+{
+    keys: Option<Vec<K>>,
+    #[pin]
+    join: Join<Fut>,
+    _map: PhantomData<fn() -> M>,
+}
+
+/// A [`KeyedJoin`] which collects into a [`HashMap`].
+pub type KeyedHashJoin<K, Fut> = KeyedJoin<K, Fut, HashMap<K, <Fut as Future>::Output>>;
+
+/// A [`KeyedJoin`] which collects into a [`BTreeMap`].
+pub type KeyedBTreeJoin<K, Fut> = KeyedJoin<K, Fut, BTreeMap<K, <Fut as Future>::Output>>;
+
+impl<K, Fut, M> KeyedJoin<K, Fut, M>
+where
+    Fut: Future,
+{
+    #[track_caller]
+    fn new(pairs: Vec<(K, Fut)>) -> Self {
+        let (keys, futures) = pairs.into_iter().unzip();
+        Self {
+            keys: Some(keys),
+            join: Join::new(futures),
+            _map: PhantomData,
+        }
+    }
+}
+
+impl<K, Fut, M> fmt::Debug for KeyedJoin<K, Fut, M>
+where
+    K: fmt::Debug,
+    Fut: Future + fmt::Debug,
+    Fut::Output: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeyedJoin")
+            .field("keys", &self.keys)
+            .field("join", &self.join)
+            .finish()
+    }
+}
+
+impl<K, Fut, M> Future for KeyedJoin<K, Fut, M>
+where
+    Fut: Future,
+    M: FromIterator<(K, Fut::Output)>,
+{
+    type Output = M;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let outputs = ready!(this.join.poll(cx));
+        let keys = this
+            .keys
+            .take()
+            .expect("Futures must not be polled after completing");
+        Poll::Ready(keys.into_iter().zip(outputs).collect())
+    }
+}
+
+impl<K, Fut> JoinKeyed for Vec<(K, Fut)>
+where
+    Fut: IntoFuture,
+{
+    type Key = K;
+    type Value = Fut::Output;
+    type Future = Fut::IntoFuture;
+
+    #[track_caller]
+    fn join_keyed(self) -> KeyedHashJoin<K, Self::Future>
+    where
+        K: Hash + Eq,
+    {
+        KeyedJoin::new(into_futures(self))
+    }
+
+    #[track_caller]
+    fn join_keyed_ordered(self) -> KeyedBTreeJoin<K, Self::Future>
+    where
+        K: Ord,
+    {
+        KeyedJoin::new(into_futures(self))
+    }
+}
+
+fn into_futures<K, Fut: IntoFuture>(pairs: Vec<(K, Fut)>) -> Vec<(K, Fut::IntoFuture)> {
+    pairs
+        .into_iter()
+        .map(|(key, fut)| (key, fut.into_future()))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::future;
+
+    #[test]
+    fn hash_map() {
+        futures_lite::future::block_on(async {
+            let futures = vec![("a", future::ready(1)), ("b", future::ready(2))];
+            let map = futures.join_keyed().await;
+            assert_eq!(map, HashMap::from([("a", 1), ("b", 2)]));
+        });
+    }
+
+    #[test]
+    fn duplicate_keys_last_wins() {
+        futures_lite::future::block_on(async {
+            let futures = vec![
+                (1, future::ready("first")),
+                (2, future::ready("other")),
+                (1, future::ready("last")),
+            ];
+            let map = futures.join_keyed_ordered().await;
+            assert_eq!(map, BTreeMap::from([(1, "last"), (2, "other")]));
+        });
+    }
+
+    #[test]
+    fn empty() {
+        futures_lite::future::block_on(async {
+            let futures: Vec<(u8, future::Ready<u8>)> = vec![];
+            assert!(futures.join_keyed().await.is_empty());
+        });
+    }
+}
@@ -0,0 +1,262 @@
+use crate::utils::{self, polled_after_completion, PollVec, WakerVec};
+
+use core::fmt;
+use core::future::{Future, IntoFuture};
+use core::panic::Location;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use pin_project::pin_project;
+
+/// Wait for all futures to complete, with only a limited number of them in
+/// flight at once.
+///
+/// This is implemented for vectors of futures.
+pub trait JoinWithLimit {
+    /// The future type being joined.
+    type Future: Future;
+
+    /// Waits for all futures to complete, polling at most `max` of them at
+    /// a time.
+    ///
+    /// The futures are started in order: the first `max` of them straight
+    /// away, and every other one as soon as an earlier one completes and
+    /// frees up its slot. A future which hasn't been started isn't polled at
+    /// all, so a large batch of requests doesn't all hit a server at once.
+    /// The outputs are returned in the same order as the futures.
+    ///
+    /// # Panics
+    ///
+    /// This panics if `max` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_concurrency::prelude::*;
+    /// use futures_lite::future::block_on;
+    /// use std::future::ready;
+    ///
+    /// block_on(async {
+    ///     let futures: Vec<_> = (0..100).map(ready).collect();
+    ///     let outputs = futures.join_with_limit(8).await;
+    ///     assert_eq!(outputs, (0..100).collect::<Vec<_>>());
+    /// })
+    /// ```
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    fn join_with_limit(self, max: usize) -> JoinLimit<Self::Future>;
+}
+
+/// Waits for all futures to complete, with only a limited number of them in
+/// flight at once.
+///
+/// This `struct` is created by the [`join_with_limit`] method on the
+/// [`JoinWithLimit`] trait. See its documentation for more.
+///
+/// [`join_with_limit`]: JoinWithLimit::join_with_limit
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+#[pin_project]
+pub struct JoinLimit<Fut>
+where
+    Fut: Future,
+{
+    #[pin]
+    futures: Vec<Fut>,
+    /// The outputs of the futures which have completed. They're moved out
+    /// once the join completes, so dropping the join early only drops the
+    /// outputs it's still holding.
+    outputs: Vec<Option<Fut::Output>>,
+    wakers: WakerVec,
+    state: PollVec,
+    /// The futures before this index have been started.
+    started: usize,
+    /// The number of futures which have been started but haven't completed.
+    in_flight: usize,
+    pending: usize,
+    max: usize,
+    done: bool,
+    /// Where the join was created, for the panic when it's polled after
+    /// completing.
+    location: &'static Location<'static>,
+}
+
+impl<Fut> fmt::Debug for JoinLimit<Fut>
+where
+    Fut: Future,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JoinLimit")
+            .field("state", &self.state)
+            .field("started", &self.started)
+            .field("max", &self.max)
+            .finish()
+    }
+}
+
+impl<Fut> Future for JoinLimit<Fut>
+where
+    Fut: Future,
+{
+    type Output = Vec<Fut::Output>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+        if *this.done {
+            polled_after_completion!(this.futures.len(), this.location);
+        }
+
+        let mut readiness = this.wakers.readiness().lock().unwrap();
+        readiness.set_waker(cx.waker());
+
+        // Futures start out ready, so the ones started in a pass are polled
+        // by the next one, without waiting for a wakeup.
+        let mut from = 0;
+        loop {
+            for index in from..*this.started {
+                if !this.state[index].is_pending() || !readiness.clear_ready(index) {
+                    continue;
+                }
+
+                // unlock readiness so we don't deadlock when polling
+                drop(readiness);
+
+                let mut cx = Context::from_waker(this.wakers.get(index).unwrap());
+                let fut = utils::get_pin_mut_from_vec(this.futures.as_mut(), index).unwrap();
+                if let Poll::Ready(output) = fut.poll(&mut cx) {
+                    this.outputs[index] = Some(output);
+                    this.state[index].set_ready();
+                    *this.pending -= 1;
+                    *this.in_flight -= 1;
+                }
+
+                readiness = this.wakers.readiness().lock().unwrap();
+            }
+
+            // Fill the slots freed up by the futures which just completed.
+            let len = this.futures.len();
+            if *this.in_flight == *this.max || *this.started == len {
+                break;
+            }
+            from = *this.started;
+            let admitted = (*this.max - *this.in_flight).min(len - *this.started);
+            *this.started += admitted;
+            *this.in_flight += admitted;
+        }
+
+        if *this.pending == 0 {
+            *this.done = true;
+            this.state.iter_mut().for_each(|state| state.set_consumed());
+            let outputs = this.outputs.drain(..).map(Option::unwrap).collect();
+            Poll::Ready(outputs)
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+impl<Fut> JoinWithLimit for Vec<Fut>
+where
+    Fut: IntoFuture,
+{
+    type Future = Fut::IntoFuture;
+
+    #[track_caller]
+    fn join_with_limit(self, max: usize) -> JoinLimit<Self::Future> {
+        assert!(max > 0, "join_with_limit requires a non-zero limit");
+        let futures: Vec<_> = self.into_iter().map(IntoFuture::into_future).collect();
+        let len = futures.len();
+        JoinLimit {
+            futures,
+            outputs: (0..len).map(|_| None).collect(),
+            wakers: WakerVec::new(len),
+            state: PollVec::new(len),
+            started: 0,
+            in_flight: 0,
+            pending: len,
+            max,
+            done: false,
+            location: Location::caller(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use futures_lite::future::{block_on, pending, poll_once, yield_now};
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    #[test]
+    fn caps_futures_in_flight() {
+        block_on(async {
+            let in_flight = Cell::new(0);
+            let peak = Cell::new(0);
+            let futures: Vec<_> = (0..20)
+                .map(|n| {
+                    let (in_flight, peak) = (&in_flight, &peak);
+                    async move {
+                        in_flight.set(in_flight.get() + 1);
+                        peak.set(peak.get().max(in_flight.get()));
+                        for _ in 0..n % 4 {
+                            yield_now().await;
+                        }
+                        in_flight.set(in_flight.get() - 1);
+                        n
+                    }
+                })
+                .collect();
+            let outputs = futures.join_with_limit(3).await;
+            assert_eq!(outputs, (0..20).collect::<Vec<_>>());
+            assert_eq!(peak.get(), 3);
+        })
+    }
+
+    #[test]
+    fn drop_only_drops_held_outputs() {
+        block_on(async {
+            let guard = Rc::new(());
+            let ready = {
+                let guard = guard.clone();
+                Box::pin(async move { Some(guard) })
+                    as Pin<Box<dyn Future<Output = Option<Rc<()>>>>>
+            };
+            let stuck = Box::pin(async {
+                pending::<()>().await;
+                None
+            });
+            let mut join = vec![ready, stuck].join_with_limit(2);
+            assert!(poll_once(&mut join).await.is_none());
+
+            // The first output is held by the join until it's dropped.
+            assert_eq!(Rc::strong_count(&guard), 2);
+            assert!(format!("{:?}", join).contains("Ready"));
+            drop(join);
+            assert_eq!(Rc::strong_count(&guard), 1);
+        })
+    }
+
+    #[test]
+    fn poll_after_completion() {
+        let mut join = vec![std::future::ready(1)].join_with_limit(1);
+        block_on(poll_once(&mut join)).unwrap();
+
+        let res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            block_on(poll_once(&mut join))
+        }));
+        if cfg!(feature = "permissive-polling") {
+            assert!(res.unwrap().is_none());
+        } else {
+            let err = res.unwrap_err();
+            let msg = err.downcast_ref::<String>().unwrap();
+            assert!(msg.contains(file!()), "{}", msg);
+        }
+    }
+
+    #[test]
+    fn empty() {
+        block_on(async {
+            let futures: Vec<std::future::Ready<u8>> = vec![];
+            assert!(futures.join_with_limit(1).await.is_empty());
+        })
+    }
+}
@@ -1,6 +1,21 @@
 use core::future::Future;
 
 pub(crate) mod array;
+pub(crate) mod cached;
+pub(crate) mod cancellable;
+pub(crate) mod keyed;
+pub(crate) mod limit;
+pub(crate) mod ordered_drop;
+pub(crate) mod progressive;
+pub(crate) mod reduce;
+pub(crate) mod results;
+pub(crate) mod settled;
+pub(crate) mod slice;
+#[cfg(feature = "spawn-parallel")]
+pub(crate) mod spawn;
+pub(crate) mod staggered;
+#[cfg(feature = "timed-join")]
+pub(crate) mod timed;
 pub(crate) mod tuple;
 pub(crate) mod vec;
 
@@ -8,6 +23,10 @@ pub(crate) mod vec;
 ///
 /// Awaits multiple futures simultaneously, returning the output of the futures
 /// once all complete.
+///
+/// When all futures being joined are `Unpin`, the joined future is `Unpin` as
+/// well, so it can be polled through a `&mut` reference or stored in a
+/// `Box<dyn Future + Unpin>`.
 pub trait Join {
     /// The resulting output type.
     type Output;
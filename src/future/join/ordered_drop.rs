@@ -0,0 +1,219 @@
+use super::vec::Join;
+
+use core::fmt;
+use core::future::Future;
+use core::mem;
+use core::ops::{Deref, DerefMut};
+use core::pin::Pin;
+use core::task::{ready, Context, Poll};
+
+use pin_project::pin_project;
+
+/// The order in which the items of an [`OrderedDrop`] are dropped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DropOrder {
+    /// Drop the first item first, and the last item last.
+    Forward,
+    /// Drop the last item first, and the first item last.
+    Reverse,
+}
+
+/// The outputs of a join, dropped in a guaranteed order.
+///
+/// When an `OrderedDrop` is dropped, each item is dropped one at a time in
+/// the [`DropOrder`] it was created with, where the index of an item is the
+/// index of the future which produced it. If dropping an item panics, the
+/// items which haven't been dropped yet are still dropped in the same order
+/// while unwinding.
+///
+/// Items which are moved out, for example through
+/// [`into_inner`][OrderedDrop::into_inner], are no longer covered by the
+/// guarantee.
+pub struct OrderedDrop<T> {
+    items: Vec<T>,
+    order: DropOrder,
+}
+
+impl<T> OrderedDrop<T> {
+    /// The order in which the items will be dropped.
+    pub fn order(&self) -> DropOrder {
+        self.order
+    }
+
+    /// Take the items out, leaving their drop order up to the caller.
+    pub fn into_inner(mut self) -> Vec<T> {
+        mem::take(&mut self.items)
+    }
+}
+
+impl<T> Drop for OrderedDrop<T> {
+    fn drop(&mut self) {
+        match self.order {
+            // `Drain` drops the remaining items in order if one of them
+            // panics, so the order holds while unwinding too.
+            DropOrder::Forward => self.items.drain(..).for_each(drop),
+            DropOrder::Reverse => {
+                // Reversing up front means that draining in order drops the
+                // items back to front, even while unwinding.
+                self.items.reverse();
+                self.items.drain(..).for_each(drop);
+            }
+        }
+    }
+}
+
+impl<T> Deref for OrderedDrop<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        &self.items
+    }
+}
+
+impl<T> DerefMut for OrderedDrop<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.items
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for OrderedDrop<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OrderedDrop")
+            .field("items", &self.items)
+            .field("order", &self.order)
+            .finish()
+    }
+}
+
+/// Wait for all futures to complete, dropping their outputs in a guaranteed
+/// order.
+///
+/// This `struct` is created by the [`ordered_drop`] method on [`Join`]. See
+/// its documentation for more.
+///
+/// [`ordered_drop`]: Join::ordered_drop
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+#[pin_project]
+pub struct OrderedDropJoin<Fut>
+where
+    Fut: Future,
+{
+    #[pin]
+    join: Join<Fut>,
+    order: DropOrder,
+}
+
+impl<Fut> OrderedDropJoin<Fut>
+where
+    Fut: Future,
+{
+    pub(crate) fn new(join: Join<Fut>, order: DropOrder) -> Self {
+        Self { join, order }
+    }
+}
+
+impl<Fut> fmt::Debug for OrderedDropJoin<Fut>
+where
+    Fut: Future + fmt::Debug,
+    Fut::Output: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OrderedDropJoin")
+            .field("join", &self.join)
+            .field("order", &self.order)
+            .finish()
+    }
+}
+
+impl<Fut> Future for OrderedDropJoin<Fut>
+where
+    Fut: Future,
+{
+    type Output = OrderedDrop<Fut::Output>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let items = ready!(this.join.poll(cx));
+        Poll::Ready(OrderedDrop {
+            items,
+            order: *this.order,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::future::Join as _;
+    use std::cell::RefCell;
+    use std::future;
+    use std::panic::{self, AssertUnwindSafe};
+    use std::rc::Rc;
+
+    /// Records its index in a shared log when dropped.
+    struct Trace {
+        index: usize,
+        log: Rc<RefCell<Vec<usize>>>,
+        panic: bool,
+    }
+
+    impl Drop for Trace {
+        fn drop(&mut self) {
+            self.log.borrow_mut().push(self.index);
+            if self.panic {
+                panic!("dropping {}", self.index);
+            }
+        }
+    }
+
+    fn traced(log: &Rc<RefCell<Vec<usize>>>, panic_at: Option<usize>) -> Vec<future::Ready<Trace>> {
+        (0..4)
+            .map(|index| {
+                future::ready(Trace {
+                    index,
+                    log: log.clone(),
+                    panic: panic_at == Some(index),
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn forward() {
+        let log = Rc::new(RefCell::new(vec![]));
+        futures_lite::future::block_on(async {
+            let outputs = traced(&log, None)
+                .join()
+                .ordered_drop(DropOrder::Forward)
+                .await;
+            assert_eq!(outputs.len(), 4);
+        });
+        assert_eq!(*log.borrow(), vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn reverse() {
+        let log = Rc::new(RefCell::new(vec![]));
+        futures_lite::future::block_on(async {
+            let outputs = traced(&log, None)
+                .join()
+                .ordered_drop(DropOrder::Reverse)
+                .await;
+            assert_eq!(outputs.order(), DropOrder::Reverse);
+        });
+        assert_eq!(*log.borrow(), vec![3, 2, 1, 0]);
+    }
+
+    #[test]
+    fn reverse_while_unwinding() {
+        let log = Rc::new(RefCell::new(vec![]));
+        let outputs = futures_lite::future::block_on(
+            traced(&log, Some(2))
+                .join()
+                .ordered_drop(DropOrder::Reverse),
+        );
+        let res = panic::catch_unwind(AssertUnwindSafe(move || drop(outputs)));
+        assert!(res.is_err());
+        assert_eq!(*log.borrow(), vec![3, 2, 1, 0]);
+    }
+}
@@ -0,0 +1,109 @@
+use core::fmt;
+use core::future::Future;
+
+use futures_core::Stream;
+use pin_project::pin_project;
+
+/// Wait for all futures to complete, reporting each one as it resolves.
+///
+/// This is implemented for tuples of futures.
+pub trait JoinProgressive {
+    /// The resulting output type.
+    type Output;
+
+    /// Which kind of stream are we turning this into?
+    type Stream: Stream<Item = Progress<Self::Output>>;
+
+    /// Waits for multiple futures to complete, yielding an event each time
+    /// one of them does.
+    ///
+    /// The stream yields [`Progress::Resolved`] with the index of each future
+    /// as it completes, followed by [`Progress::Done`] with the outputs of
+    /// all futures once the last one has completed. Every index is reported
+    /// exactly once, before `Done`. The outputs of a tuple can have
+    /// different types, so the markers only carry the index; this is enough
+    /// to know that a piece of data has arrived, for example to update a
+    /// progress indicator while the rest is still loading.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_concurrency::future::{JoinProgressive, Progress};
+    /// use futures_lite::future::{block_on, yield_now};
+    /// use futures_lite::stream::StreamExt;
+    ///
+    /// block_on(async {
+    ///     let name = async { "ferris" };
+    ///     let age = async {
+    ///         yield_now().await;
+    ///         12
+    ///     };
+    ///     let events: Vec<_> = (name, age).join_progressive().collect().await;
+    ///     assert_eq!(
+    ///         events,
+    ///         vec![
+    ///             Progress::Resolved(0),
+    ///             Progress::Resolved(1),
+    ///             Progress::Done(("ferris", 12)),
+    ///         ]
+    ///     );
+    /// })
+    /// ```
+    fn join_progressive(self) -> Self::Stream;
+}
+
+/// An event yielded by [`JoinProgressive`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Progress<T> {
+    /// The future at this index has completed.
+    Resolved(usize),
+    /// All futures have completed, with these outputs.
+    Done(T),
+}
+
+/// A stream which reports the futures of a join as they complete.
+///
+/// This `struct` is created by the [`join_progressive`] method on the
+/// [`JoinProgressive`] trait. See its documentation for more.
+///
+/// [`join_progressive`]: JoinProgressive::join_progressive
+#[must_use = "streams do nothing unless you `.await` or poll them"]
+#[pin_project]
+pub struct ProgressiveJoin<J>
+where
+    J: Future,
+{
+    #[pin]
+    pub(crate) join: J,
+    /// The indexes which have been reported, as a bitmask.
+    pub(crate) reported: u16,
+    /// The join's output, held until every index has been reported.
+    pub(crate) output: Option<J::Output>,
+    pub(crate) done: bool,
+}
+
+impl<J> ProgressiveJoin<J>
+where
+    J: Future,
+{
+    pub(crate) fn new(join: J) -> Self {
+        Self {
+            join,
+            reported: 0,
+            output: None,
+            done: false,
+        }
+    }
+}
+
+impl<J> fmt::Debug for ProgressiveJoin<J>
+where
+    J: Future + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProgressiveJoin")
+            .field("join", &self.join)
+            .field("reported", &self.reported)
+            .finish()
+    }
+}
@@ -0,0 +1,208 @@
+use crate::utils::{self, polled_after_completion, Indexer, PollVec, WakerVec};
+
+use core::fmt;
+use core::future::{Future, IntoFuture};
+use core::panic::Location;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use pin_project::pin_project;
+
+/// Wait for all futures to complete, folding their outputs into a single
+/// value.
+///
+/// This is implemented for vectors of futures.
+pub trait JoinReduce {
+    /// The future type being joined.
+    type Future: Future;
+
+    /// Waits for all futures to complete, folding each output into `init`
+    /// with `f` as soon as it's available.
+    ///
+    /// Unlike joining and then folding the resulting `Vec`, the outputs are
+    /// never buffered: each one is handed to `f` from within the poll loop
+    /// as its future completes, and only the accumulator is kept around.
+    ///
+    /// Outputs are folded in the order their futures complete, which isn't
+    /// deterministic. For the result to be deterministic as well, `f` should
+    /// be commutative and associative, like a sum or a maximum.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_concurrency::prelude::*;
+    /// use futures_lite::future::block_on;
+    /// use std::future::ready;
+    ///
+    /// block_on(async {
+    ///     let futures = vec![ready(1), ready(2), ready(3)];
+    ///     let sum = futures.join_reduce(0, |acc, n| acc + n).await;
+    ///     assert_eq!(sum, 6);
+    /// })
+    /// ```
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    fn join_reduce<Acc, F>(self, init: Acc, f: F) -> ReduceJoin<Self::Future, Acc, F>
+    where
+        F: FnMut(Acc, <Self::Future as Future>::Output) -> Acc;
+}
+
+/// Waits for all futures to complete, folding their outputs into a single
+/// value.
+///
+/// This `struct` is created by the [`join_reduce`] method on the
+/// [`JoinReduce`] trait. See its documentation for more.
+///
+/// [`join_reduce`]: JoinReduce::join_reduce
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+#[pin_project]
+pub struct ReduceJoin<Fut, Acc, F> {
+    #[pin]
+    futures: Vec<Fut>,
+    wakers: WakerVec,
+    state: PollVec,
+    indexer: Indexer,
+    pending: usize,
+    /// Taken out while folding, and once the join has completed.
+    acc: Option<Acc>,
+    f: F,
+    /// Where the join was created, for the panic when it's polled after
+    /// completing.
+    location: &'static Location<'static>,
+}
+
+impl<Fut, Acc, F> fmt::Debug for ReduceJoin<Fut, Acc, F>
+where
+    Fut: fmt::Debug,
+    Acc: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReduceJoin")
+            .field("futures", &self.futures)
+            .field("acc", &self.acc)
+            .finish()
+    }
+}
+
+impl<Fut, Acc, F> Future for ReduceJoin<Fut, Acc, F>
+where
+    Fut: Future,
+    F: FnMut(Acc, Fut::Output) -> Acc,
+{
+    type Output = Acc;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+        if this.acc.is_none() {
+            polled_after_completion!(this.futures.len(), this.location);
+        }
+
+        let mut readiness = this.wakers.readiness().lock().unwrap();
+        readiness.set_waker(cx.waker());
+
+        for index in this.indexer.iter() {
+            if !readiness.any_ready() {
+                // Nothing else is ready yet
+                break;
+            } else if !this.state[index].is_pending() || !readiness.clear_ready(index) {
+                continue;
+            }
+
+            // unlock readiness so we don't deadlock when polling
+            drop(readiness);
+
+            let mut cx = Context::from_waker(this.wakers.get(index).unwrap());
+            let fut = utils::get_pin_mut_from_vec(this.futures.as_mut(), index).unwrap();
+            if let Poll::Ready(output) = fut.poll(&mut cx) {
+                this.state[index].set_consumed();
+                *this.pending -= 1;
+                let acc = this.acc.take().unwrap();
+                *this.acc = Some((this.f)(acc, output));
+            }
+
+            readiness = this.wakers.readiness().lock().unwrap();
+        }
+
+        if *this.pending == 0 {
+            Poll::Ready(this.acc.take().unwrap())
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+impl<Fut> JoinReduce for Vec<Fut>
+where
+    Fut: IntoFuture,
+{
+    type Future = Fut::IntoFuture;
+
+    #[track_caller]
+    fn join_reduce<Acc, F>(self, init: Acc, f: F) -> ReduceJoin<Self::Future, Acc, F>
+    where
+        F: FnMut(Acc, <Self::Future as Future>::Output) -> Acc,
+    {
+        let futures: Vec<_> = self.into_iter().map(IntoFuture::into_future).collect();
+        let len = futures.len();
+        ReduceJoin {
+            futures,
+            wakers: WakerVec::new(len),
+            state: PollVec::new(len),
+            indexer: Indexer::new(len),
+            pending: len,
+            acc: Some(init),
+            f,
+            location: Location::caller(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use futures_lite::future::{block_on, yield_now};
+
+    #[test]
+    fn sums_without_collecting() {
+        block_on(async {
+            let futures: Vec<_> = (1..=1000u64)
+                .map(|n| async move {
+                    if n % 2 == 0 {
+                        yield_now().await;
+                    }
+                    n
+                })
+                .collect();
+            let sum = futures.join_reduce(0, |acc, n| acc + n).await;
+            assert_eq!(sum, 500_500);
+        })
+    }
+
+    #[test]
+    fn folds_in_completion_order() {
+        block_on(async {
+            let futures: Vec<_> = (0..3)
+                .map(|n| async move {
+                    for _ in n..3 {
+                        yield_now().await;
+                    }
+                    n
+                })
+                .collect();
+            let order = futures
+                .join_reduce(vec![], |mut acc, n| {
+                    acc.push(n);
+                    acc
+                })
+                .await;
+            assert_eq!(order, vec![2, 1, 0]);
+        })
+    }
+
+    #[test]
+    fn empty() {
+        block_on(async {
+            let futures: Vec<std::future::Ready<u8>> = vec![];
+            assert_eq!(futures.join_reduce(7, |acc, _| acc).await, 7);
+        })
+    }
+}
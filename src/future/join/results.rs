@@ -0,0 +1,92 @@
+use super::vec::Join;
+
+use core::fmt;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{ready, Context, Poll};
+
+use pin_project::pin_project;
+
+/// Wait for all fallible futures to complete, splitting their outputs into
+/// successes and failures.
+///
+/// This `struct` is created by the [`join_results`] method on [`Join`]. See
+/// its documentation for more.
+///
+/// [`join_results`]: Join::join_results
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+#[pin_project]
+pub struct JoinResults<Fut>
+where
+    Fut: Future,
+{
+    #[pin]
+    join: Join<Fut>,
+}
+
+impl<Fut> JoinResults<Fut>
+where
+    Fut: Future,
+{
+    pub(crate) fn new(join: Join<Fut>) -> Self {
+        Self { join }
+    }
+}
+
+impl<Fut> fmt::Debug for JoinResults<Fut>
+where
+    Fut: Future + fmt::Debug,
+    Fut::Output: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JoinResults")
+            .field("join", &self.join)
+            .finish()
+    }
+}
+
+impl<Fut, T, E> Future for JoinResults<Fut>
+where
+    Fut: Future<Output = Result<T, E>>,
+{
+    type Output = (Vec<T>, Vec<(usize, E)>);
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let outputs = ready!(this.join.poll(cx));
+        let mut oks = Vec::with_capacity(outputs.len());
+        let mut errs = Vec::new();
+        for (index, output) in outputs.into_iter().enumerate() {
+            match output {
+                Ok(value) => oks.push(value),
+                Err(err) => errs.push((index, err)),
+            }
+        }
+        Poll::Ready((oks, errs))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::future::Join as _;
+    use futures_lite::future::{block_on, yield_now};
+
+    #[test]
+    fn runs_everything_and_partitions() {
+        block_on(async {
+            let futures: Vec<_> = (0..5)
+                .map(|n| async move {
+                    // Failures complete first, and don't stop the others.
+                    if n % 2 == 1 {
+                        return Err(n * 10);
+                    }
+                    yield_now().await;
+                    Ok(n)
+                })
+                .collect();
+            let (oks, errs) = futures.join().join_results().await;
+            assert_eq!(oks, vec![0, 2, 4]);
+            assert_eq!(errs, vec![(1, 10), (3, 30)]);
+        })
+    }
+}
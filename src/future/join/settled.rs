@@ -0,0 +1,225 @@
+use super::vec::Join;
+
+use core::fmt;
+use core::future::{Future, IntoFuture};
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use std::any::Any;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use pin_project::pin_project;
+
+/// How a future in a [`join_settled`][JoinSettled::join_settled] settled.
+#[derive(Debug)]
+pub enum SettledResult<T, E> {
+    /// The future completed with `Ok`.
+    Ok(T),
+    /// The future completed with `Err`.
+    Err(E),
+    /// The future panicked, with this payload.
+    Panicked(Box<dyn Any + Send>),
+}
+
+impl<T, E> SettledResult<T, E> {
+    /// Returns `true` if the future completed with `Ok`.
+    pub fn is_ok(&self) -> bool {
+        matches!(self, SettledResult::Ok(_))
+    }
+
+    /// Returns `true` if the future completed with `Err`.
+    pub fn is_err(&self) -> bool {
+        matches!(self, SettledResult::Err(_))
+    }
+
+    /// Returns `true` if the future panicked.
+    pub fn is_panicked(&self) -> bool {
+        matches!(self, SettledResult::Panicked(_))
+    }
+}
+
+/// Wait for all fallible futures to settle, whether they succeed, fail or
+/// panic.
+///
+/// This is implemented for vectors of futures returning a `Result`.
+pub trait JoinSettled {
+    /// The value each future returns on success.
+    type Ok;
+
+    /// The error each future returns on failure.
+    type Error;
+
+    /// The future type being joined.
+    type Future: Future<Output = Result<Self::Ok, Self::Error>>;
+
+    /// Waits for all futures to settle, returning how each of them did in
+    /// the same order as the futures.
+    ///
+    /// This is like `Promise.allSettled` in JavaScript. Unlike
+    /// [`try_join`][crate::future::TryJoin::try_join], an error doesn't
+    /// short-circuit the join, and unlike [`join`][crate::future::Join::join]
+    /// a panic doesn't unwind through it: each future is polled inside
+    /// [`catch_unwind`][std::panic::catch_unwind], and a future which panics
+    /// settles as [`SettledResult::Panicked`] and is never polled again. The
+    /// other futures keep running either way.
+    ///
+    /// Futures are asserted to be unwind safe. Any state a panicking future
+    /// shares with the other futures may have been left inconsistent.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_concurrency::future::SettledResult;
+    /// use futures_concurrency::prelude::*;
+    /// use futures_lite::future::block_on;
+    /// use std::future;
+    ///
+    /// block_on(async {
+    ///     let futures = vec![future::ready(Ok(1)), future::ready(Err("failed"))];
+    ///     let settled = futures.join_settled().await;
+    ///     assert!(matches!(settled[0], SettledResult::Ok(1)));
+    ///     assert!(matches!(settled[1], SettledResult::Err("failed")));
+    /// })
+    /// ```
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    fn join_settled(self) -> SettledJoin<Self::Future, Self::Ok, Self::Error>;
+}
+
+/// Waits for all fallible futures to settle, whether they succeed, fail or
+/// panic.
+///
+/// This `struct` is created by the [`join_settled`] method on the
+/// [`JoinSettled`] trait. See its documentation for more.
+///
+/// [`join_settled`]: JoinSettled::join_settled
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+#[pin_project]
+pub struct SettledJoin<Fut, T, E>
+where
+    Fut: Future<Output = Result<T, E>>,
+{
+    #[pin]
+    join: Join<Settle<Fut>>,
+}
+
+impl<Fut, T, E> fmt::Debug for SettledJoin<Fut, T, E>
+where
+    Fut: Future<Output = Result<T, E>> + fmt::Debug,
+    T: fmt::Debug,
+    E: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SettledJoin")
+            .field("join", &self.join)
+            .finish()
+    }
+}
+
+impl<Fut, T, E> Future for SettledJoin<Fut, T, E>
+where
+    Fut: Future<Output = Result<T, E>>,
+{
+    type Output = Vec<SettledResult<T, E>>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().join.poll(cx)
+    }
+}
+
+/// A future which settles instead of panicking.
+#[pin_project]
+struct Settle<Fut> {
+    #[pin]
+    future: Fut,
+}
+
+impl<Fut> fmt::Debug for Settle<Fut>
+where
+    Fut: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.future.fmt(f)
+    }
+}
+
+impl<Fut, T, E> Future for Settle<Fut>
+where
+    Fut: Future<Output = Result<T, E>>,
+{
+    type Output = SettledResult<T, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut future = self.project().future;
+        // The join doesn't poll a future again once it's ready, so a future
+        // which panicked is only dropped afterwards.
+        match catch_unwind(AssertUnwindSafe(|| future.as_mut().poll(cx))) {
+            Ok(Poll::Ready(Ok(value))) => Poll::Ready(SettledResult::Ok(value)),
+            Ok(Poll::Ready(Err(err))) => Poll::Ready(SettledResult::Err(err)),
+            Ok(Poll::Pending) => Poll::Pending,
+            Err(payload) => Poll::Ready(SettledResult::Panicked(payload)),
+        }
+    }
+}
+
+impl<Fut, T, E> JoinSettled for Vec<Fut>
+where
+    Fut: IntoFuture<Output = Result<T, E>>,
+{
+    type Ok = T;
+    type Error = E;
+    type Future = Fut::IntoFuture;
+
+    #[track_caller]
+    fn join_settled(self) -> SettledJoin<Self::Future, T, E> {
+        let futures = self
+            .into_iter()
+            .map(|future| Settle {
+                future: future.into_future(),
+            })
+            .collect();
+        SettledJoin {
+            join: Join::new(futures),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use futures_lite::future::{block_on, yield_now, Boxed, FutureExt};
+
+    #[test]
+    fn settles_every_outcome() {
+        block_on(async {
+            let futures: Vec<Boxed<Result<u8, &str>>> = vec![
+                async {
+                    yield_now().await;
+                    Ok(1)
+                }
+                .boxed(),
+                async { Err("failed") }.boxed(),
+                async {
+                    yield_now().await;
+                    panic!("boom")
+                }
+                .boxed(),
+                async {
+                    yield_now().await;
+                    yield_now().await;
+                    Ok(4)
+                }
+                .boxed(),
+            ];
+            let mut settled = futures.join_settled().await;
+
+            assert!(matches!(settled[0], SettledResult::Ok(1)));
+            assert!(matches!(settled[1], SettledResult::Err("failed")));
+            assert!(matches!(settled[3], SettledResult::Ok(4)));
+            match settled.remove(2) {
+                SettledResult::Panicked(payload) => {
+                    assert_eq!(*payload.downcast::<&str>().unwrap(), "boom");
+                }
+                other => panic!("expected a panic, got {:?}", other),
+            }
+        })
+    }
+}
@@ -0,0 +1,33 @@
+use super::vec::Join;
+use super::Join as JoinTrait;
+
+use core::future::Future;
+
+impl<'a, Fut> JoinTrait for &'a mut [Fut]
+where
+    Fut: Future + Unpin,
+{
+    type Output = Vec<Fut::Output>;
+    type Future = Join<&'a mut Fut>;
+
+    #[track_caller]
+    fn join(self) -> Self::Future {
+        Join::new(self.iter_mut().collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use futures_lite::future::{block_on, ready, Boxed, FutureExt};
+
+    #[test]
+    fn join_borrowed_slice() {
+        block_on(async {
+            let mut futures: Vec<Boxed<u8>> = vec![ready(1).boxed(), ready(2).boxed()];
+            let outputs = futures.as_mut_slice().join().await;
+            assert_eq!(outputs, vec![1, 2]);
+            assert_eq!(futures.len(), 2);
+        });
+    }
+}
@@ -0,0 +1,232 @@
+use super::vec::Join;
+
+use core::fmt;
+use core::future::{Future, IntoFuture};
+use core::pin::Pin;
+use core::task::{Context, Poll, Waker};
+use std::sync::{Arc, Mutex};
+
+/// A future which has been boxed so it can be spawned.
+pub type BoxedTask = Pin<Box<dyn Future<Output = ()> + Send + 'static>>;
+
+/// A way to spawn futures onto an executor.
+///
+/// [`join_spawned`][JoinSpawned::join_spawned] takes a `Spawn` rather than
+/// depending on a specific runtime, so any executor able to run `Send`
+/// futures in the background can be plugged in. This trait is implemented for
+/// all closures which take a [`BoxedTask`].
+///
+/// # Examples
+///
+/// ```
+/// use futures_concurrency::future::{BoxedTask, Spawn};
+///
+/// fn assert_spawn(_: impl Spawn) {}
+/// assert_spawn(|task: BoxedTask| {
+///     async_std::task::spawn(task);
+/// });
+/// ```
+pub trait Spawn {
+    /// Spawn `task` so that it runs to completion in the background.
+    fn spawn(&self, task: BoxedTask);
+}
+
+impl<F> Spawn for F
+where
+    F: Fn(BoxedTask),
+{
+    fn spawn(&self, task: BoxedTask) {
+        self(task)
+    }
+}
+
+/// Wait for all futures to complete, running each of them as a separate
+/// task.
+///
+/// This is implemented for vectors of futures.
+pub trait JoinSpawned {
+    /// The output of each future.
+    type Output;
+
+    /// Spawns every future onto `spawner`, and waits for all of them to
+    /// complete.
+    ///
+    /// Unlike [`Join::join`][crate::future::Join::join], which polls every
+    /// future from the task awaiting the join, each future becomes its own
+    /// task. On a multi-threaded executor this means the futures can run in
+    /// parallel. The outputs are returned in the same order as the futures.
+    ///
+    /// The futures and their outputs must be `Send + 'static`, since they're
+    /// moved to the executor. Dropping the join doesn't cancel the spawned
+    /// tasks. If a task is dropped before completing, for example because it
+    /// panicked, awaiting the join panics.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_concurrency::future::{BoxedTask, JoinSpawned};
+    /// use std::future;
+    ///
+    /// async_std::task::block_on(async {
+    ///     let spawner = |task: BoxedTask| {
+    ///         async_std::task::spawn(task);
+    ///     };
+    ///     let futures = vec![future::ready(1), future::ready(2)];
+    ///     assert_eq!(futures.join_spawned(&spawner).await, vec![1, 2]);
+    /// })
+    /// ```
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    fn join_spawned<S>(self, spawner: &S) -> Join<SpawnHandle<Self::Output>>
+    where
+        S: Spawn + ?Sized;
+}
+
+impl<Fut> JoinSpawned for Vec<Fut>
+where
+    Fut: IntoFuture,
+    Fut::IntoFuture: Send + 'static,
+    Fut::Output: Send + 'static,
+{
+    type Output = Fut::Output;
+
+    #[track_caller]
+    fn join_spawned<S>(self, spawner: &S) -> Join<SpawnHandle<Self::Output>>
+    where
+        S: Spawn + ?Sized,
+    {
+        let handles = self
+            .into_iter()
+            .map(|fut| {
+                let shared = Arc::new(Mutex::new(Shared {
+                    output: None,
+                    waker: None,
+                    abandoned: false,
+                }));
+                let guard = Guard {
+                    shared: Some(shared.clone()),
+                };
+                let fut = fut.into_future();
+                spawner.spawn(Box::pin(async move {
+                    let output = fut.await;
+                    guard.complete(output);
+                }));
+                SpawnHandle { shared }
+            })
+            .collect();
+        Join::new(handles)
+    }
+}
+
+/// The state shared between a spawned task and its handle.
+struct Shared<T> {
+    output: Option<T>,
+    waker: Option<Waker>,
+    /// Set if the task was dropped without producing an output.
+    abandoned: bool,
+}
+
+/// Hands the output of a spawned task to its handle, or marks the task as
+/// abandoned if it's dropped first.
+struct Guard<T> {
+    shared: Option<Arc<Mutex<Shared<T>>>>,
+}
+
+impl<T> Guard<T> {
+    fn complete(mut self, output: T) {
+        let shared = self.shared.take().unwrap();
+        let mut shared = shared.lock().unwrap();
+        shared.output = Some(output);
+        if let Some(waker) = shared.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+impl<T> Drop for Guard<T> {
+    fn drop(&mut self) {
+        if let Some(shared) = self.shared.take() {
+            // Don't panic while panicking if the handle's side poisoned it.
+            let mut shared = match shared.lock() {
+                Ok(shared) => shared,
+                Err(poisoned) => poisoned.into_inner(),
+            };
+            shared.abandoned = true;
+            if let Some(waker) = shared.waker.take() {
+                waker.wake();
+            }
+        }
+    }
+}
+
+/// The output of a future spawned by [`join_spawned`].
+///
+/// This `struct` is created by the [`join_spawned`] method on the
+/// [`JoinSpawned`] trait. See its documentation for more.
+///
+/// [`join_spawned`]: JoinSpawned::join_spawned
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct SpawnHandle<T> {
+    shared: Arc<Mutex<Shared<T>>>,
+}
+
+impl<T> fmt::Debug for SpawnHandle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SpawnHandle").finish_non_exhaustive()
+    }
+}
+
+impl<T> Future for SpawnHandle<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut shared = self.shared.lock().unwrap();
+        if let Some(output) = shared.output.take() {
+            return Poll::Ready(output);
+        }
+        assert!(
+            !shared.abandoned,
+            "spawned future was dropped before completing"
+        );
+        shared.waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::Barrier;
+    use std::thread;
+
+    fn threads(task: BoxedTask) {
+        thread::spawn(move || futures_lite::future::block_on(task));
+    }
+
+    #[test]
+    fn runs_in_parallel() {
+        futures_lite::future::block_on(async {
+            // Every future blocks its thread until all of them are running,
+            // which can only happen if they run in parallel.
+            let barrier = Arc::new(Barrier::new(3));
+            let futures: Vec<_> = (0..3)
+                .map(|n| {
+                    let barrier = barrier.clone();
+                    async move {
+                        barrier.wait();
+                        n
+                    }
+                })
+                .collect();
+            assert_eq!(futures.join_spawned(&threads).await, vec![0, 1, 2]);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "spawned future was dropped before completing")]
+    fn dropped_task_panics() {
+        futures_lite::future::block_on(async {
+            let futures = vec![std::future::ready(1)];
+            futures.join_spawned(&drop::<BoxedTask>).await;
+        });
+    }
+}
@@ -0,0 +1,184 @@
+use super::vec::Join;
+use crate::future::Timer;
+
+use core::convert::TryFrom;
+use core::fmt;
+use core::future::{Future, IntoFuture};
+use core::pin::Pin;
+use core::task::{ready, Context, Poll};
+use core::time::Duration;
+
+use pin_project::pin_project;
+
+/// Wait for all futures to complete, starting them one after the other.
+///
+/// This is implemented for vectors of futures.
+pub trait JoinStaggered {
+    /// The future type being joined.
+    type Future: Future;
+
+    /// Waits for all futures to complete, starting the future at index `i`
+    /// only once `i * delay` has elapsed.
+    ///
+    /// This spreads out the start of many similar futures, such as a batch
+    /// of network requests, to avoid a spike of work all at once. Each
+    /// future except the first is gated by a sleep created by `timer`, and
+    /// isn't polled until its sleep has completed. From then on it's polled
+    /// like in a regular [`Join`][crate::future::Join]. The outputs are
+    /// returned in the same order as the futures.
+    ///
+    /// All sleeps are created up front, so the delays are measured from
+    /// when this method is called, or from the first poll of the join for
+    /// timers whose sleeps only start counting once polled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_concurrency::prelude::*;
+    /// use futures_lite::future::block_on;
+    /// use std::future::ready;
+    /// use std::time::Duration;
+    ///
+    /// block_on(async {
+    ///     let timer = |dur| async_std::task::sleep(dur);
+    ///     let futures = vec![ready(1), ready(2), ready(3)];
+    ///     let outputs = futures
+    ///         .join_staggered(Duration::from_millis(1), timer)
+    ///         .await;
+    ///     assert_eq!(outputs, vec![1, 2, 3]);
+    /// })
+    /// ```
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    fn join_staggered<T>(self, delay: Duration, timer: T) -> StaggeredJoin<Self::Future, T::Sleep>
+    where
+        T: Timer;
+}
+
+/// Waits for all futures to complete, starting them one after the other.
+///
+/// This is created by the [`join_staggered`] method on the [`JoinStaggered`]
+/// trait. See its documentation for more.
+///
+/// [`join_staggered`]: JoinStaggered::join_staggered
+pub type StaggeredJoin<Fut, S> = Join<Staggered<Fut, S>>;
+
+impl<Fut> JoinStaggered for Vec<Fut>
+where
+    Fut: IntoFuture,
+{
+    type Future = Fut::IntoFuture;
+
+    #[track_caller]
+    fn join_staggered<T>(
+        self,
+        delay: Duration,
+        mut timer: T,
+    ) -> StaggeredJoin<Self::Future, T::Sleep>
+    where
+        T: Timer,
+    {
+        let futures = self
+            .into_iter()
+            .enumerate()
+            .map(|(index, fut)| {
+                // The first future starts straight away.
+                let gate = (index > 0).then(|| {
+                    let index = u32::try_from(index).unwrap_or(u32::MAX);
+                    timer.sleep(delay.saturating_mul(index))
+                });
+                Staggered {
+                    gate,
+                    future: fut.into_future(),
+                }
+            })
+            .collect();
+        Join::new(futures)
+    }
+}
+
+/// A future which isn't polled until its start gate has opened.
+///
+/// This is one of the futures joined by [`join_staggered`]. See its
+/// documentation for more.
+///
+/// [`join_staggered`]: JoinStaggered::join_staggered
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+#[pin_project]
+pub struct Staggered<Fut, S> {
+    #[pin]
+    gate: Option<S>,
+    #[pin]
+    future: Fut,
+}
+
+impl<Fut, S> fmt::Debug for Staggered<Fut, S>
+where
+    Fut: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Staggered")
+            .field("started", &self.gate.is_none())
+            .field("future", &self.future)
+            .finish()
+    }
+}
+
+impl<Fut, S> Future for Staggered<Fut, S>
+where
+    Fut: Future,
+    S: Future,
+{
+    type Output = Fut::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+        if let Some(gate) = this.gate.as_mut().as_pin_mut() {
+            ready!(gate.poll(cx));
+            this.gate.set(None);
+        }
+        this.future.poll(cx)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[test]
+    fn starts_in_order() {
+        futures_lite::future::block_on(async {
+            let started = Rc::new(RefCell::new(vec![]));
+            let delays = RefCell::new(vec![]);
+            // Each gate yields once per unit of delay.
+            let timer = |dur: Duration| {
+                delays.borrow_mut().push(dur);
+                async move {
+                    for _ in 0..dur.as_millis() {
+                        futures_lite::future::yield_now().await;
+                    }
+                }
+            };
+            let futures: Vec<_> = (0..3)
+                .map(|n| {
+                    let started = started.clone();
+                    async move {
+                        started.borrow_mut().push(n);
+                        n
+                    }
+                })
+                .collect();
+            let outputs = futures
+                .join_staggered(Duration::from_millis(2), timer)
+                .await;
+
+            assert_eq!(outputs, vec![0, 1, 2]);
+            assert_eq!(*started.borrow(), vec![0, 1, 2]);
+            assert_eq!(
+                *delays.borrow(),
+                vec![Duration::from_millis(2), Duration::from_millis(4)]
+            );
+        });
+    }
+}
@@ -0,0 +1,133 @@
+use super::vec::Join;
+
+use core::fmt;
+use core::future::{Future, IntoFuture};
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use core::time::Duration;
+use std::time::Instant;
+
+use pin_project::pin_project;
+
+/// Wait for all futures to complete, measuring how long each of them took.
+///
+/// This is implemented for vectors of futures.
+pub trait JoinTimed {
+    /// The future type being joined.
+    type Future: Future;
+
+    /// Waits for all futures to complete, pairing each output with the time
+    /// its future took.
+    ///
+    /// The duration is the wall-clock time between a future's first poll and
+    /// the poll in which it completed. It includes the time spent waiting on
+    /// other futures and on the executor, so it's not a measure of CPU time.
+    /// The outputs are returned in the same order as the futures.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_concurrency::prelude::*;
+    /// use futures_lite::future::block_on;
+    /// use std::future::ready;
+    ///
+    /// block_on(async {
+    ///     let outputs = vec![ready(1), ready(2)].join_timed().await;
+    ///     let (values, _): (Vec<_>, Vec<_>) = outputs.into_iter().unzip();
+    ///     assert_eq!(values, vec![1, 2]);
+    /// })
+    /// ```
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    fn join_timed(self) -> TimedJoin<Self::Future>;
+}
+
+/// Waits for all futures to complete, measuring how long each of them took.
+///
+/// This is created by the [`join_timed`] method on the [`JoinTimed`] trait.
+/// See its documentation for more.
+///
+/// [`join_timed`]: JoinTimed::join_timed
+pub type TimedJoin<Fut> = Join<Timed<Fut>>;
+
+impl<Fut> JoinTimed for Vec<Fut>
+where
+    Fut: IntoFuture,
+{
+    type Future = Fut::IntoFuture;
+
+    #[track_caller]
+    fn join_timed(self) -> TimedJoin<Self::Future> {
+        let futures = self
+            .into_iter()
+            .map(|fut| Timed {
+                start: None,
+                future: fut.into_future(),
+            })
+            .collect();
+        Join::new(futures)
+    }
+}
+
+/// A future which measures the time from its first poll to its completion.
+///
+/// This is one of the futures joined by [`join_timed`]. See its
+/// documentation for more.
+///
+/// [`join_timed`]: JoinTimed::join_timed
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+#[pin_project]
+pub struct Timed<Fut> {
+    start: Option<Instant>,
+    #[pin]
+    future: Fut,
+}
+
+impl<Fut> fmt::Debug for Timed<Fut>
+where
+    Fut: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Timed")
+            .field("start", &self.start)
+            .field("future", &self.future)
+            .finish()
+    }
+}
+
+impl<Fut> Future for Timed<Fut>
+where
+    Fut: Future,
+{
+    type Output = (Fut::Output, Duration);
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let start = *this.start.get_or_insert_with(Instant::now);
+        this.future.poll(cx).map(|output| (output, start.elapsed()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use futures_lite::future::{self, Boxed, FutureExt};
+
+    #[test]
+    fn measures_from_first_poll() {
+        future::block_on(async {
+            let futures: Vec<Boxed<u8>> = vec![
+                future::ready(1).boxed(),
+                async {
+                    std::thread::sleep(Duration::from_millis(20));
+                    2
+                }
+                .boxed(),
+            ];
+            let outputs = futures.join_timed().await;
+            assert_eq!(outputs[0].0, 1);
+            assert_eq!(outputs[1].0, 2);
+            assert!(outputs[0].1 < outputs[1].1);
+            assert!(outputs[1].1 >= Duration::from_millis(20));
+        })
+    }
+}
@@ -1,41 +1,69 @@
+use super::cancellable::{CancelHandle, Cancellable, JoinCancellable, Shared};
+use super::progressive::{JoinProgressive, Progress, ProgressiveJoin};
 use super::Join as JoinTrait;
-use crate::utils::{PollArray, WakerArray};
+use crate::utils::{polled_after_completion, PollArray, WakerArray};
 
 use core::fmt::{self, Debug};
 use core::future::{Future, IntoFuture};
 use core::mem::MaybeUninit;
+use core::panic::Location;
 use core::pin::Pin;
 use core::task::{Context, Poll};
 
+use futures_core::Stream;
 use pin_project::{pin_project, pinned_drop};
 
-/// Generates the `poll` call for every `Future` inside `$futures`.
+/// Polls the future at `$iteration`, evaluating to `true` once it completes.
+///
+/// This expands to a single `match` on the index, where every arm hands its
+/// future to [`poll_one`]. Keeping the per-future code out of line means it's
+/// generated once per future type rather than once per tuple position, which
+/// keeps the `poll` functions of the larger tuples small.
 // This is implemented as a tt-muncher of the future name `$($F:ident)`
-// and the future index `$($rest)`, taking advantage that we only support
-// tuples up to  12 elements
+// and the future index `$($rest)`, accumulating the match arms, taking
+// advantage that we only support tuples up to 16 elements
 //
 // # References
 // TT Muncher: https://veykril.github.io/tlborm/decl-macros/patterns/tt-muncher.html
 macro_rules! poll {
-    (@inner $iteration:ident, $this:ident, $futures:ident, $cx:ident, $fut_name:ident $($F:ident)* | $fut_idx:tt $($rest:tt)*) => {
-        if $fut_idx == $iteration {
-            if let Poll::Ready(value) = $futures.$fut_name.as_mut().poll(&mut $cx) {
-                $this.outputs.$fut_idx.write(value);
-                *$this.completed += 1;
-                $this.state[$fut_idx].set_ready();
-            }
-        }
-        poll!(@inner $iteration, $this, $futures, $cx, $($F)* | $($rest)*);
+    (@arms $iteration:ident, $this:ident, $futures:ident, $cx:ident, [$($arms:tt)*] $fut_name:ident $($F:ident)* | $fut_idx:tt $($rest:tt)*) => {
+        poll!(@arms $iteration, $this, $futures, $cx, [
+            $($arms)*
+            $fut_idx => poll_one($futures.$fut_name.as_mut(), &mut $this.outputs.$fut_idx, &mut $cx),
+        ] $($F)* | $($rest)*)
     };
 
-    // base condition, no more futures to poll
-    (@inner $iteration:ident, $this:ident, $futures:ident, $cx:ident, | $($rest:tt)*) => {};
+    // base condition, every future has an arm
+    (@arms $iteration:ident, $this:ident, $futures:ident, $cx:ident, [$($arms:tt)*] | $($rest:tt)*) => {
+        match $iteration {
+            $($arms)*
+            _ => unreachable!(),
+        }
+    };
 
     ($iteration:ident, $this:ident, $futures:ident, $cx:ident, $LEN:ident, $($F:ident,)+) => {
-        poll!(@inner $iteration, $this, $futures, $cx, $($F)+ | 0 1 2 3 4 5 6 7 8 9 10 11);
+        poll!(@arms $iteration, $this, $futures, $cx, [] $($F)+ | 0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15)
     };
 }
 
+/// Poll a single future, writing its output to `output` once it completes.
+///
+/// This is deliberately not inlined, see [`poll!`].
+#[inline(never)]
+fn poll_one<Fut: Future>(
+    fut: Pin<&mut Fut>,
+    output: &mut MaybeUninit<Fut::Output>,
+    cx: &mut Context<'_>,
+) -> bool {
+    match fut.poll(cx) {
+        Poll::Ready(value) => {
+            output.write(value);
+            true
+        }
+        Poll::Pending => false,
+    }
+}
+
 macro_rules! drop_outputs {
     (@drop $output:ident, $($rem_outs:ident,)* | $states:expr, $stix:tt, $($rem_idx:tt,)*) => {
         if $states[$stix].is_ready() {
@@ -51,7 +79,14 @@ macro_rules! drop_outputs {
     (@drop | $states:expr, $($rem_idx:tt,)*) => {};
 
     ($($outs:ident,)+ | $states:expr) => {
-        drop_outputs!(@drop $($outs,)+ | $states, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11,);
+        drop_outputs!(@drop $($outs,)+ | $states, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15,);
+    };
+}
+
+/// Expands to a `CancelHandle` for every future in the tuple.
+macro_rules! cancel_handle {
+    ($F:ident) => {
+        CancelHandle
     };
 }
 
@@ -68,6 +103,23 @@ macro_rules! impl_join_tuple {
         #[allow(non_snake_case)]
         pub struct $StructName {}
 
+        impl $StructName {
+            /// The number of futures which have completed so far.
+            pub fn completed(&self) -> usize {
+                0
+            }
+
+            /// The number of futures in the join.
+            pub fn len(&self) -> usize {
+                0
+            }
+
+            /// Returns `true` if the join has no futures.
+            pub fn is_empty(&self) -> bool {
+                true
+            }
+        }
+
         impl fmt::Debug for $StructName {
             fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
                 f.debug_tuple("Join").finish()
@@ -91,6 +143,15 @@ macro_rules! impl_join_tuple {
                 $StructName {}
             }
         }
+
+        impl JoinCancellable for () {
+            type Output = ();
+            type Handles = ();
+            type Future = Cancellable<$StructName>;
+            fn join_cancellable(self) -> (Self::Future, Self::Handles) {
+                (Cancellable::new($StructName {}, Shared::new()), ())
+            }
+        }
     };
     ($mod_name:ident $StructName:ident $($F:ident)+) => {
         mod $mod_name {
@@ -122,6 +183,26 @@ macro_rules! impl_join_tuple {
             state: PollArray<{$mod_name::LEN}>,
             wakers: WakerArray<{$mod_name::LEN}>,
             completed: usize,
+            location: &'static Location<'static>,
+        }
+
+        impl<$($F: Future),+> $StructName<$($F),+> {
+            /// The number of futures which have completed so far.
+            ///
+            /// Once the join has resolved this stays at [`len`][Self::len].
+            pub fn completed(&self) -> usize {
+                self.completed
+            }
+
+            /// The number of futures in the join.
+            pub fn len(&self) -> usize {
+                $mod_name::LEN
+            }
+
+            /// Returns `true` if the join has no futures.
+            pub fn is_empty(&self) -> bool {
+                false
+            }
         }
 
         impl<$($F),+> Debug for $StructName<$($F),+>
@@ -148,12 +229,13 @@ macro_rules! impl_join_tuple {
                 const LEN: usize = $mod_name::LEN;
 
                 let mut this = self.project();
-                let all_completed = !(*this.completed == LEN);
-                assert!(all_completed, "Futures must not be polled after completing");
+                if *this.completed == LEN {
+                    polled_after_completion!(LEN, this.location);
+                }
 
                 let mut futures = this.futures.project();
 
-                let mut readiness = this.wakers.readiness().lock().unwrap();
+                let readiness = this.wakers.readiness();
                 readiness.set_waker(cx.waker());
 
                 for index in 0..LEN {
@@ -166,14 +248,14 @@ macro_rules! impl_join_tuple {
                         continue;
                     }
 
-                    // unlock readiness so we don't deadlock when polling
-                    drop(readiness);
-
                     // obtain the intermediate waker
                     let mut cx = Context::from_waker(this.wakers.get(index).unwrap());
 
                     // generate the needed code to poll `futures.{index}`
-                    poll!(index, this, futures, cx, LEN, $($F,)+);
+                    if poll!(index, this, futures, cx, LEN, $($F,)+) {
+                        *this.completed += 1;
+                        this.state[index].set_ready();
+                    }
 
                     if *this.completed == LEN {
                         let out = {
@@ -187,7 +269,6 @@ macro_rules! impl_join_tuple {
 
                         return Poll::Ready(out);
                     }
-                    readiness = this.wakers.readiness().lock().unwrap();
                 }
 
                 Poll::Pending
@@ -214,6 +295,7 @@ macro_rules! impl_join_tuple {
             type Output = ($($F::Output,)*);
             type Future = $StructName<$($F::IntoFuture),*>;
 
+            #[track_caller]
             fn join(self) -> Self::Future {
                 let ($($F,)+): ($($F,)+) = self;
                 $StructName {
@@ -222,6 +304,77 @@ macro_rules! impl_join_tuple {
                     outputs: ($(MaybeUninit::<$F::Output>::uninit(),)+),
                     wakers: WakerArray::new(),
                     completed: 0,
+                    location: Location::caller(),
+                }
+            }
+        }
+
+        impl<$($F),+> JoinCancellable for ($($F,)+)
+        where $(
+            $F: IntoFuture,
+        )+ {
+            type Output = ($($F::Output,)*);
+            type Handles = ($(cancel_handle!($F),)+);
+            type Future = Cancellable<$StructName<$($F::IntoFuture),*>>;
+
+            #[track_caller]
+            fn join_cancellable(self) -> (Self::Future, Self::Handles) {
+                let shared = Shared::new();
+                let handles = ($(CancelHandle::new(&shared, $mod_name::Indexes::$F as usize),)+);
+                (Cancellable::new(self.join(), shared), handles)
+            }
+        }
+
+        impl<$($F),+> JoinProgressive for ($($F,)+)
+        where $(
+            $F: IntoFuture,
+        )+ {
+            type Output = ($($F::Output,)*);
+            type Stream = ProgressiveJoin<$StructName<$($F::IntoFuture),*>>;
+
+            #[track_caller]
+            fn join_progressive(self) -> Self::Stream {
+                ProgressiveJoin::new(self.join())
+            }
+        }
+
+        impl<$($F: Future),+> Stream for ProgressiveJoin<$StructName<$($F),+>> {
+            type Item = Progress<($($F::Output,)+)>;
+
+            fn poll_next(
+                self: Pin<&mut Self>, cx: &mut Context<'_>
+            ) -> Poll<Option<Self::Item>> {
+                const LEN: usize = $mod_name::LEN;
+
+                let mut this = self.project();
+                if *this.done {
+                    return Poll::Ready(None);
+                }
+
+                if this.output.is_none() {
+                    if let Poll::Ready(output) = this.join.as_mut().poll(cx) {
+                        *this.output = Some(output);
+                    }
+                }
+
+                // Report the futures which have completed, in index order.
+                // Once the join is done its states are consumed, but every
+                // future has completed by then.
+                for index in 0..LEN {
+                    let bit = 1 << index;
+                    let resolved = this.output.is_some() || this.join.state[index].is_ready();
+                    if *this.reported & bit == 0 && resolved {
+                        *this.reported |= bit;
+                        return Poll::Ready(Some(Progress::Resolved(index)));
+                    }
+                }
+
+                match this.output.take() {
+                    Some(output) => {
+                        *this.done = true;
+                        Poll::Ready(Some(Progress::Done(output)))
+                    }
+                    None => Poll::Pending,
                 }
             }
         }
@@ -242,6 +395,10 @@ impl_join_tuple! { join9 Join9 A B C D E F G H I }
 impl_join_tuple! { join10 Join10 A B C D E F G H I J }
 impl_join_tuple! { join11 Join11 A B C D E F G H I J K }
 impl_join_tuple! { join12 Join12 A B C D E F G H I J K L }
+impl_join_tuple! { join13 Join13 A B C D E F G H I J K L M }
+impl_join_tuple! { join14 Join14 A B C D E F G H I J K L M N }
+impl_join_tuple! { join15 Join15 A B C D E F G H I J K L M N O }
+impl_join_tuple! { join16 Join16 A B C D E F G H I J K L M N O P }
 
 #[cfg(test)]
 mod test {
@@ -256,6 +413,30 @@ mod test {
         });
     }
 
+    #[test]
+    fn join_progressive() {
+        use futures::channel::oneshot;
+        use futures_lite::future::poll_once;
+        use futures_lite::stream::StreamExt;
+
+        futures_lite::future::block_on(async {
+            let (send_a, recv_a) = oneshot::channel::<u8>();
+            let (send_c, recv_c) = oneshot::channel::<&str>();
+            let b = future::ready('b');
+            let mut s = (recv_a, b, recv_c).join_progressive();
+
+            assert_eq!(s.next().await, Some(Progress::Resolved(1)));
+            assert!(poll_once(s.next()).await.is_none());
+
+            send_c.send("c").unwrap();
+            assert_eq!(s.next().await, Some(Progress::Resolved(2)));
+            send_a.send(1).unwrap();
+            assert_eq!(s.next().await, Some(Progress::Resolved(0)));
+            assert_eq!(s.next().await, Some(Progress::Done((Ok(1), 'b', Ok("c")))));
+            assert_eq!(s.next().await, None);
+        });
+    }
+
     #[test]
     fn join_1() {
         futures_lite::future::block_on(async {
@@ -284,22 +465,142 @@ mod test {
     }
 
     #[test]
-    fn does_not_leak_memory() {
-        use core::cell::RefCell;
+    fn join_13() {
+        futures_lite::future::block_on(async {
+            let r = future::ready;
+            let futures = (
+                r(0u8),
+                r(1),
+                r(2),
+                r(3),
+                r(4),
+                r(5),
+                r(6),
+                r(7),
+                r(8),
+                r(9),
+                r(10),
+                r(11),
+                future::ready("thirteen"),
+            );
+            let outputs = futures.join().await;
+            assert_eq!(outputs.11, 11);
+            assert_eq!(outputs.12, "thirteen");
+        });
+    }
+
+    #[test]
+    fn join_16() {
+        futures_lite::future::block_on(async {
+            let y = |n: u8| async move {
+                for _ in 0..n % 3 {
+                    futures_lite::future::yield_now().await;
+                }
+                n
+            };
+            let futures = (
+                y(0),
+                y(1),
+                y(2),
+                y(3),
+                y(4),
+                y(5),
+                y(6),
+                y(7),
+                y(8),
+                y(9),
+                y(10),
+                y(11),
+                y(12),
+                y(13),
+                y(14),
+                y(15),
+            );
+            // tuples this large don't implement `PartialEq`
+            let outputs = futures.join().await;
+            assert_eq!(outputs.0, 0);
+            assert_eq!(outputs.12, 12);
+            assert_eq!(outputs.15, 15);
+        });
+    }
+
+    #[test]
+    fn counts_completed() {
+        futures_lite::future::block_on(async {
+            let mut join = (
+                future::ready(1),
+                futures_lite::future::pending::<u8>(),
+                future::ready(3),
+            )
+                .join();
+            assert_eq!((join.completed(), join.len()), (0, 3));
+            assert!(futures_lite::future::poll_once(&mut join).await.is_none());
+            assert_eq!(join.completed(), 2);
+        });
+    }
+
+    #[test]
+    fn join_cancellable_completes() {
+        futures_lite::future::block_on(async {
+            let a = future::ready("hello");
+            let b = future::ready(12);
+            let (fut, _handles) = (a, b).join_cancellable();
+            assert_eq!(fut.await, Ok(("hello", 12)));
+        });
+    }
+
+    #[test]
+    fn join_cancellable_cancel_slot() {
+        use crate::future::Cancelled;
         use futures_lite::future::pending;
 
-        thread_local! {
-            static NOT_LEAKING: RefCell<bool> = RefCell::new(false);
-        };
+        futures_lite::future::block_on(async {
+            let a = future::ready("hello");
+            let b = pending::<u8>();
+            let c = pending::<u8>();
+            let (mut fut, handles) = (a, b, c).join_cancellable();
+            assert!(futures_lite::future::poll_once(&mut fut).await.is_none());
+
+            handles.2.cancel();
+            handles.1.cancel();
+            assert_eq!(fut.await, Err(Cancelled { index: 2 }));
+        });
+    }
 
-        struct FlipFlagAtDrop;
-        impl Drop for FlipFlagAtDrop {
-            fn drop(&mut self) {
-                NOT_LEAKING.with(|v| {
-                    *v.borrow_mut() = true;
-                });
-            }
+    #[test]
+    fn join_cancellable_drops_outputs() {
+        use futures_lite::future::pending;
+        use std::sync::Arc;
+
+        futures_lite::future::block_on(async {
+            let value = Arc::new(());
+            let a = future::ready(value.clone());
+            let (mut fut, handles) = (a, pending::<u8>()).join_cancellable();
+            assert!(futures_lite::future::poll_once(&mut fut).await.is_none());
+            assert_eq!(Arc::strong_count(&value), 2);
+
+            handles.0.cancel();
+            assert!(fut.await.is_err());
+            assert_eq!(Arc::strong_count(&value), 1);
+        });
+    }
+
+    thread_local! {
+        static NOT_LEAKING: core::cell::RefCell<bool> = core::cell::RefCell::new(false);
+    }
+
+    struct FlipFlagAtDrop;
+    impl Drop for FlipFlagAtDrop {
+        fn drop(&mut self) {
+            NOT_LEAKING.with(|v| {
+                *v.borrow_mut() = true;
+            });
         }
+    }
+
+    #[test]
+    fn does_not_leak_memory() {
+        use futures_lite::future::pending;
 
         futures_lite::future::block_on(async {
             // this will trigger Miri if we don't drop the memory
@@ -317,4 +618,41 @@ mod test {
             assert!(*flag.borrow());
         })
     }
+
+    #[test]
+    fn does_not_leak_memory_16() {
+        use futures_lite::future::pending;
+
+        futures_lite::future::block_on(async {
+            let r = future::ready;
+
+            // the outputs past the 12th are the ones which need the larger
+            // index lists to be dropped
+            let leak = (
+                r(0u8),
+                r(1),
+                r(2),
+                r(3),
+                r(4),
+                r(5),
+                r(6),
+                r(7),
+                r(8),
+                r(9),
+                r(10),
+                r(11),
+                r(12),
+                future::ready("memory leak".to_owned()),
+                future::ready(FlipFlagAtDrop),
+                pending::<u8>(),
+            )
+                .join();
+
+            _ = futures_lite::future::poll_once(leak).await;
+        });
+
+        NOT_LEAKING.with(|flag| {
+            assert!(*flag.borrow());
+        })
+    }
 }
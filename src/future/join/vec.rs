@@ -1,8 +1,13 @@
+use super::cached::CachedJoin;
+use super::ordered_drop::{DropOrder, OrderedDropJoin};
+use super::results::JoinResults;
 use super::Join as JoinTrait;
-use crate::utils::{iter_pin_mut_vec, PollVec, WakerVec};
+use crate::future::PollFuture;
+use crate::utils::{iter_pin_mut_vec, polled_after_completion, PollVec, WakerVec};
 
 use core::fmt;
 use core::future::{Future, IntoFuture};
+use core::panic::Location;
 use core::pin::Pin;
 use core::task::{Context, Poll};
 use std::mem::{self, MaybeUninit};
@@ -10,6 +15,12 @@ use std::vec::Vec;
 
 use pin_project::{pin_project, pinned_drop};
 
+/// Joins with more futures than this are split into a tree of sub-joins.
+const TREE_THRESHOLD: usize = 256;
+
+/// The number of futures in each sub-join of a tree.
+const CHUNK_SIZE: usize = 64;
+
 /// Waits for two similarly-typed futures to complete.
 ///
 /// This `struct` is created by the [`join`] method on the [`Join`] trait. See
@@ -18,14 +29,38 @@ use pin_project::{pin_project, pinned_drop};
 /// [`join`]: crate::future::Join::join
 /// [`Join`]: crate::future::Join
 #[must_use = "futures do nothing unless you `.await` or poll them"]
-#[pin_project(PinnedDrop)]
+#[pin_project]
 pub struct Join<Fut>
 where
-    Fut: Future,
+    Fut: PollFuture,
+{
+    #[pin]
+    inner: Inner<Fut>,
+    len: usize,
+    location: &'static Location<'static>,
+}
+
+/// Large joins are split into chunks, so that a wakeup only requires
+/// scanning the readiness of the chunks and of one chunk's futures, rather
+/// than of every future.
+#[pin_project(project = InnerProj)]
+enum Inner<Fut>
+where
+    Fut: PollFuture,
+{
+    Flat(#[pin] Flat<Fut>),
+    Tree(#[pin] Flat<Flat<Fut>>),
+}
+
+/// A join which polls all of its futures directly.
+#[pin_project(PinnedDrop)]
+struct Flat<Fut>
+where
+    Fut: PollFuture,
 {
     consumed: bool,
     pending: usize,
-    items: Vec<MaybeUninit<<Fut as Future>::Output>>,
+    items: Vec<MaybeUninit<<Fut as PollFuture>::Output>>,
     wakers: WakerVec,
     state: PollVec,
     #[pin]
@@ -34,11 +69,210 @@ where
 
 impl<Fut> Join<Fut>
 where
-    Fut: Future,
+    Fut: PollFuture,
 {
+    #[track_caller]
     pub(crate) fn new(futures: Vec<Fut>) -> Self {
         let len = futures.len();
+        let inner = if futures.len() > TREE_THRESHOLD {
+            let mut futures = futures.into_iter();
+            let chunks = std::iter::from_fn(|| {
+                let chunk: Vec<_> = futures.by_ref().take(CHUNK_SIZE).collect();
+                (!chunk.is_empty()).then(|| Flat::new(chunk))
+            })
+            .collect();
+            Inner::Tree(Flat::new(chunks))
+        } else {
+            Inner::Flat(Flat::new(futures))
+        };
         Join {
+            inner,
+            len,
+            location: Location::caller(),
+        }
+    }
+
+    /// Create a new `Join` from a vector of [`PollFuture`]s.
+    ///
+    /// This is how futures from a runtime with its own future trait are
+    /// joined. Regular futures can use this too, but are usually joined
+    /// through the [`Join`](crate::future::Join) trait instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_concurrency::future::PollFuture;
+    /// use futures_concurrency::vec::Join;
+    /// use futures_lite::future::block_on;
+    /// use std::pin::Pin;
+    /// use std::task::{Context, Poll};
+    ///
+    /// /// A future shim which doesn't implement `Future`.
+    /// struct Shim(u8);
+    ///
+    /// impl PollFuture for Shim {
+    ///     type Output = u8;
+    ///
+    ///     fn poll_future(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<u8> {
+    ///         Poll::Ready(self.0)
+    ///     }
+    /// }
+    ///
+    /// block_on(async {
+    ///     let outputs = Join::from_poll_futures(vec![Shim(1), Shim(2)]).await;
+    ///     assert_eq!(outputs, vec![1, 2]);
+    /// })
+    /// ```
+    #[track_caller]
+    pub fn from_poll_futures(futures: Vec<Fut>) -> Self {
+        Self::new(futures)
+    }
+
+    /// The number of futures which have completed so far.
+    ///
+    /// Once the join has resolved this stays at [`len`][Self::len].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_concurrency::prelude::*;
+    /// use futures_lite::future::{block_on, pending, poll_once, ready, Boxed, FutureExt};
+    ///
+    /// block_on(async {
+    ///     let futures: Vec<Boxed<u8>> = vec![ready(1).boxed(), pending().boxed()];
+    ///     let mut join = futures.join();
+    ///     assert!(poll_once(&mut join).await.is_none());
+    ///     assert_eq!((join.completed(), join.len()), (1, 2));
+    /// })
+    /// ```
+    pub fn completed(&self) -> usize {
+        match &self.inner {
+            Inner::Flat(flat) => flat.completed(),
+            // Chunks keep their count once they've resolved.
+            Inner::Tree(tree) => tree.futures.iter().map(Flat::completed).sum(),
+        }
+    }
+
+    /// The number of futures in the join.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the join has no futures.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl<Fut> Join<Fut>
+where
+    Fut: Future,
+{
+    /// Wait for all futures to complete, and drop their outputs in `order`.
+    ///
+    /// The outputs are returned in an [`OrderedDrop`][crate::vec::OrderedDrop],
+    /// which drops them one by one in `order` of the index of the future
+    /// which produced them. This only applies once the join has completed:
+    /// if the join itself is dropped early, the outputs produced so far are
+    /// dropped in an unspecified order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_concurrency::prelude::*;
+    /// use futures_concurrency::vec::DropOrder;
+    /// use futures_lite::future::block_on;
+    /// use std::future;
+    ///
+    /// block_on(async {
+    ///     let futures = vec![future::ready(1), future::ready(2)];
+    ///     let outputs = futures.join().ordered_drop(DropOrder::Reverse).await;
+    ///     assert_eq!(&*outputs, &[1, 2]);
+    /// })
+    /// ```
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub fn ordered_drop(self, order: DropOrder) -> OrderedDropJoin<Fut> {
+        OrderedDropJoin::new(self, order)
+    }
+
+    /// Wait for all futures to complete, and keep their outputs so the join
+    /// can be awaited again.
+    ///
+    /// The futures run exactly once. Once they have all completed, their
+    /// outputs are stored in the returned future, and every poll after that,
+    /// including the first one to complete, resolves to a clone of them.
+    /// This requires the outputs to be `Clone`. To await the join more than
+    /// once, await `&mut join` if the futures are `Unpin`, or pin it and
+    /// await `join.as_mut()`. The outputs can also be read without awaiting
+    /// through [`outputs`][crate::vec::CachedJoin::outputs].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_concurrency::prelude::*;
+    /// use futures_lite::future::block_on;
+    /// use std::future;
+    ///
+    /// block_on(async {
+    ///     let mut join = vec![future::ready(1), future::ready(2)]
+    ///         .join()
+    ///         .join_cached();
+    ///     assert_eq!((&mut join).await, vec![1, 2]);
+    ///     assert_eq!((&mut join).await, vec![1, 2]);
+    /// })
+    /// ```
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub fn join_cached(self) -> CachedJoin<Fut>
+    where
+        Fut::Output: Clone,
+    {
+        CachedJoin::new(self)
+    }
+}
+
+impl<Fut, T, E> Join<Fut>
+where
+    Fut: Future<Output = Result<T, E>>,
+{
+    /// Wait for all futures to complete, and split their outputs into the
+    /// successes and the failures.
+    ///
+    /// Unlike [`TryJoin`][crate::future::TryJoin], this doesn't stop at the
+    /// first error: every future runs to completion. The successes are
+    /// returned in the order of their futures, and each error is returned
+    /// along with the index of the future which produced it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_concurrency::prelude::*;
+    /// use futures_lite::future::block_on;
+    /// use std::future;
+    ///
+    /// block_on(async {
+    ///     let futures = vec![
+    ///         future::ready(Ok(1)),
+    ///         future::ready(Err("refused")),
+    ///         future::ready(Ok(3)),
+    ///     ];
+    ///     let (oks, errs) = futures.join().join_results().await;
+    ///     assert_eq!(oks, vec![1, 3]);
+    ///     assert_eq!(errs, vec![(1, "refused")]);
+    /// })
+    /// ```
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub fn join_results(self) -> JoinResults<Fut> {
+        JoinResults::new(self)
+    }
+}
+
+impl<Fut> Flat<Fut>
+where
+    Fut: PollFuture,
+{
+    fn new(futures: Vec<Fut>) -> Self {
+        let len = futures.len();
+        Flat {
             consumed: false,
             pending: len,
             items: std::iter::repeat_with(MaybeUninit::uninit)
@@ -49,6 +283,42 @@ where
             futures,
         }
     }
+
+    fn completed(&self) -> usize {
+        self.futures.len() - self.pending
+    }
+}
+
+impl<'a, Fut> Join<Pin<&'a mut Fut>>
+where
+    Fut: Future,
+{
+    /// Create a new `Join` from a vector of pinned futures.
+    ///
+    /// This makes it possible to join `!Unpin` futures which have been pinned
+    /// on the stack using [`pin!`](core::pin::pin), without boxing them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_concurrency::vec::Join;
+    /// use futures_lite::future::block_on;
+    /// use std::pin::pin;
+    ///
+    /// async fn double(n: u32) -> u32 {
+    ///     n * 2
+    /// }
+    ///
+    /// block_on(async {
+    ///     let a = pin!(double(1));
+    ///     let b = pin!(double(2));
+    ///     assert_eq!(Join::from_pinned(vec![a, b]).await, vec![2, 4]);
+    /// })
+    /// ```
+    #[track_caller]
+    pub fn from_pinned(futures: Vec<Pin<&'a mut Fut>>) -> Self {
+        Self::new(futures)
+    }
 }
 
 impl<Fut> JoinTrait for Vec<Fut>
@@ -58,38 +328,98 @@ where
     type Output = Vec<Fut::Output>;
     type Future = Join<Fut::IntoFuture>;
 
+    #[track_caller]
     fn join(self) -> Self::Future {
         Join::new(self.into_iter().map(IntoFuture::into_future).collect())
     }
 }
 
+/// Wait for all futures in an iterator to complete.
+///
+/// This is a free-function version of [`Join::join`] for `Vec`, matching the
+/// signature of `futures::future::join_all`. The outputs are returned in the
+/// same order as the futures were yielded by the iterator.
+///
+/// [`Join::join`]: crate::future::Join::join
+///
+/// # Examples
+///
+/// ```
+/// use futures_concurrency::future::join_all;
+/// use futures_lite::future::block_on;
+///
+/// async fn foo(i: u32) -> u32 {
+///     i
+/// }
+///
+/// block_on(async {
+///     let futures = vec![foo(1), foo(2), foo(3)];
+///     assert_eq!(join_all(futures).await, [1, 2, 3]);
+/// })
+/// ```
+#[track_caller]
+pub fn join_all<I>(iter: I) -> Join<<I::Item as IntoFuture>::IntoFuture>
+where
+    I: IntoIterator,
+    I::Item: IntoFuture,
+{
+    Join::new(iter.into_iter().map(IntoFuture::into_future).collect())
+}
+
 impl<Fut> fmt::Debug for Join<Fut>
 where
-    Fut: Future + fmt::Debug,
+    Fut: PollFuture + fmt::Debug,
     Fut::Output: fmt::Debug,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_list().entries(self.state.iter()).finish()
+        match &self.inner {
+            Inner::Flat(flat) => f.debug_list().entries(flat.state.iter()).finish(),
+            Inner::Tree(tree) => {
+                let states = tree.futures.iter().flat_map(|chunk| chunk.state.iter());
+                f.debug_list().entries(states).finish()
+            }
+        }
     }
 }
 
 impl<Fut> Future for Join<Fut>
 where
-    Fut: Future,
+    Fut: PollFuture,
 {
     type Output = Vec<Fut::Output>;
 
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-        let mut this = self.project();
+        let this = self.project();
+        let consumed = match &*this.inner {
+            Inner::Flat(flat) => flat.consumed,
+            Inner::Tree(tree) => tree.consumed,
+        };
+        if consumed {
+            polled_after_completion!(this.len, this.location);
+        }
 
-        assert!(
-            !*this.consumed,
-            "Futures must not be polled after completing"
-        );
+        match this.inner.project() {
+            InnerProj::Flat(flat) => flat.poll(cx),
+            InnerProj::Tree(tree) => tree
+                .poll(cx)
+                .map(|chunks| chunks.into_iter().flatten().collect()),
+        }
+    }
+}
+
+impl<Fut> Future for Flat<Fut>
+where
+    Fut: PollFuture,
+{
+    type Output = Vec<Fut::Output>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+        debug_assert!(!*this.consumed, "chunks are never polled after completing");
 
         let mut readiness = this.wakers.readiness().lock().unwrap();
         readiness.set_waker(cx.waker());
-        if !readiness.any_ready() {
+        if !readiness.any_ready() && *this.pending > 0 {
             // Nothing is ready yet
             return Poll::Pending;
         }
@@ -105,7 +435,7 @@ where
                 // Obtain the intermediate waker.
                 let mut cx = Context::from_waker(this.wakers.get(i).unwrap());
 
-                if let Poll::Ready(value) = fut.poll(&mut cx) {
+                if let Poll::Ready(value) = fut.poll_future(&mut cx) {
                     this.items[i] = MaybeUninit::new(value);
                     states[i].set_ready();
                     *this.pending -= 1;
@@ -143,9 +473,9 @@ where
 
 /// Drop the already initialized values on cancellation.
 #[pinned_drop]
-impl<Fut> PinnedDrop for Join<Fut>
+impl<Fut> PinnedDrop for Flat<Fut>
 where
-    Fut: Future,
+    Fut: PollFuture,
 {
     fn drop(self: Pin<&mut Self>) {
         let this = self.project();
@@ -185,6 +515,43 @@ mod test {
         });
     }
 
+    /// `!Send` futures can be joined on a single-threaded executor: the
+    /// wakers are `Send`, but they don't require anything of the futures.
+    #[test]
+    fn local_box_futures() {
+        use futures::executor::LocalPool;
+        use futures::future::{FutureExt, LocalBoxFuture};
+        use futures::task::LocalSpawnExt;
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        let mut pool = LocalPool::new();
+        let log = Rc::new(RefCell::new(vec![]));
+        let futures: Vec<LocalBoxFuture<'static, usize>> = (0..3)
+            .map(|n| {
+                let log = log.clone();
+                async move {
+                    futures_lite::future::yield_now().await;
+                    log.borrow_mut().push(n);
+                    n
+                }
+                .boxed_local()
+            })
+            .collect();
+
+        let output = Rc::new(RefCell::new(None));
+        let output2 = output.clone();
+        pool.spawner()
+            .spawn_local(async move {
+                *output2.borrow_mut() = Some(futures.join().await);
+            })
+            .unwrap();
+        pool.run();
+
+        assert_eq!(*output.borrow(), Some(vec![0, 1, 2]));
+        assert_eq!(log.borrow().len(), 3);
+    }
+
     #[test]
     fn debug() {
         let mut fut = vec![future::ready("hello"), future::ready("world")].join();
@@ -196,4 +563,99 @@ mod test {
         let _ = fut.as_mut().poll(&mut cx);
         assert_eq!(format!("{:?}", fut), "[Consumed, Consumed]");
     }
+
+    #[test]
+    fn poll_after_completion() {
+        let mut fut = vec![future::ready(1)].join();
+        let mut fut = Pin::new(&mut fut);
+
+        let waker = Arc::new(DummyWaker()).into();
+        let mut cx = Context::from_waker(&waker);
+        assert_eq!(fut.as_mut().poll(&mut cx), Poll::Ready(vec![1]));
+
+        let res =
+            std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| fut.as_mut().poll(&mut cx)));
+        if cfg!(feature = "permissive-polling") {
+            assert_eq!(res.unwrap(), Poll::Pending);
+        } else {
+            // The message points at where the join was created.
+            let err = res.unwrap_err();
+            let msg = err.downcast_ref::<String>().unwrap();
+            assert!(msg.contains("join of 1 futures"), "{}", msg);
+            assert!(msg.contains(file!()), "{}", msg);
+        }
+    }
+
+    #[test]
+    fn from_pinned() {
+        futures_lite::future::block_on(async {
+            let a = std::pin::pin!(future::ready("hello"));
+            let b = std::pin::pin!(future::ready("world"));
+            assert_eq!(Join::from_pinned(vec![a, b]).await, vec!["hello", "world"]);
+        });
+    }
+
+    #[test]
+    fn join_all_free_fn() {
+        futures_lite::future::block_on(async {
+            let futures = (1..=3).map(future::ready);
+            assert_eq!(super::join_all(futures).await, vec![1, 2, 3]);
+        });
+    }
+
+    #[test]
+    fn empty() {
+        futures_lite::future::block_on(async {
+            let futures: Vec<future::Ready<u8>> = vec![];
+            assert_eq!(futures.join().await, vec![]);
+        });
+    }
+
+    #[test]
+    fn tree_preserves_order() {
+        futures_lite::future::block_on(async {
+            let len = TREE_THRESHOLD * 4 + 3;
+            let futures: Vec<_> = (0..len).map(future::ready).collect();
+            let fut = futures.join();
+            assert!(matches!(fut.inner, Inner::Tree(_)));
+            assert_eq!(fut.await, (0..len).collect::<Vec<_>>());
+        });
+    }
+
+    #[test]
+    fn tree_drops_completed_outputs() {
+        use futures_lite::future::{pending, ready, Boxed, FutureExt};
+
+        let output = Arc::new(());
+        let mut futures: Vec<Boxed<Option<Arc<()>>>> = (0..TREE_THRESHOLD * 2)
+            .map(|_| ready(Some(output.clone())).boxed())
+            .collect();
+        futures.push(pending().boxed());
+
+        let mut fut = futures.join();
+        let waker = Arc::new(DummyWaker()).into();
+        let mut cx = Context::from_waker(&waker);
+        assert!(Pin::new(&mut fut).poll(&mut cx).is_pending());
+        assert_eq!(Arc::strong_count(&output), TREE_THRESHOLD * 2 + 1);
+
+        drop(fut);
+        assert_eq!(Arc::strong_count(&output), 1);
+    }
+
+    #[test]
+    fn tree_counts_completed() {
+        use futures_lite::future::{pending, ready, Boxed, FutureExt};
+
+        let mut futures: Vec<Boxed<usize>> =
+            (0..TREE_THRESHOLD * 2).map(|n| ready(n).boxed()).collect();
+        futures.push(pending().boxed());
+
+        let mut fut = futures.join();
+        assert_eq!((fut.completed(), fut.len()), (0, TREE_THRESHOLD * 2 + 1));
+
+        let waker = Arc::new(DummyWaker()).into();
+        let mut cx = Context::from_waker(&waker);
+        assert!(Pin::new(&mut fut).poll(&mut cx).is_pending());
+        assert_eq!(fut.completed(), TREE_THRESHOLD * 2);
+    }
 }
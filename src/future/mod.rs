@@ -68,12 +68,37 @@
 //! - `future::RaceOk`: wait for the first _successful_ future in the set to
 //! complete, or return an `Err` if *no* futures complete successfully.
 //!
+/// Derive a `join_fields` method for structs of futures.
+#[cfg(feature = "derive")]
+pub use futures_concurrency_macros::Join;
+pub use join::cancellable::{CancelHandle, Cancellable, Cancelled, JoinCancellable};
+pub use join::keyed::JoinKeyed;
+pub use join::limit::JoinWithLimit;
+pub use join::progressive::{JoinProgressive, Progress, ProgressiveJoin};
+pub use join::reduce::JoinReduce;
+pub use join::settled::{JoinSettled, SettledResult};
+#[cfg(feature = "spawn-parallel")]
+pub use join::spawn::{BoxedTask, JoinSpawned, Spawn, SpawnHandle};
+pub use join::staggered::JoinStaggered;
+#[cfg(feature = "timed-join")]
+pub use join::timed::JoinTimed;
+pub use join::vec::join_all;
 pub use join::Join;
+pub use poll_future::PollFuture;
+pub use race::either::{
+    Either10, Either11, Either12, Either2, Either3, Either4, Either5, Either6, Either7, Either8,
+    Either9, RaceEither,
+};
+pub use race::vec::select_all;
 pub use race::Race;
 pub use race_ok::RaceOk;
-pub use try_join::TryJoin;
+pub use timeout::{timeout, Elapsed, Timeout, Timer};
+pub use try_join::vec::try_join_all;
+pub use try_join::{Residual, Try, TryJoin};
 
 pub(crate) mod join;
+mod poll_future;
 pub(crate) mod race;
 pub(crate) mod race_ok;
+pub(crate) mod timeout;
 pub(crate) mod try_join;
@@ -0,0 +1,40 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+/// A minimal future trait, for joining and racing futures which don't
+/// implement [`Future`].
+///
+/// Some runtimes, such as `no_std` executors, define their own future
+/// abstraction. Implementing `PollFuture` for those types makes it possible
+/// to combine them with [`vec::Join::from_poll_futures`] and
+/// [`vec::Race::from_poll_futures`]. The combinators themselves implement
+/// [`Future`], so they can be nested.
+///
+/// Every [`Future`] implements this trait through a blanket impl, so existing
+/// futures work unchanged. This also means a type can't implement both
+/// traits: types implementing `Future` already get `PollFuture` for free.
+///
+/// [`vec::Join::from_poll_futures`]: crate::vec::Join::from_poll_futures
+/// [`vec::Race::from_poll_futures`]: crate::vec::Race::from_poll_futures
+pub trait PollFuture {
+    /// The type of value produced on completion.
+    type Output;
+
+    /// Attempt to resolve the future to a final value, registering the
+    /// current task for wakeup if the value is not yet available.
+    ///
+    /// This has the same contract as [`Future::poll`].
+    fn poll_future(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output>;
+}
+
+impl<F> PollFuture for F
+where
+    F: Future + ?Sized,
+{
+    type Output = F::Output;
+
+    fn poll_future(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.poll(cx)
+    }
+}
@@ -1,4 +1,4 @@
-use crate::utils::{self, Indexer};
+use crate::utils::{self, Indexer, LengthError};
 
 use super::Race as RaceTrait;
 
@@ -28,6 +28,39 @@ where
     done: bool,
 }
 
+impl<Fut, const N: usize> Race<Fut, N>
+where
+    Fut: Future,
+{
+    /// Create a new `Race` from exactly `N` futures yielded by an iterator.
+    ///
+    /// This returns an error if the iterator yields fewer or more than `N`
+    /// futures, in which case the futures it yielded are dropped.
+    ///
+    /// # Panics
+    ///
+    /// Like [`race`][crate::future::Race::race], this panics if `N` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_concurrency::array::Race;
+    /// use futures_lite::future::block_on;
+    /// use std::future::ready;
+    ///
+    /// block_on(async {
+    ///     let race = Race::<_, 2>::try_from_iter([ready(1), ready(2)]).unwrap();
+    ///     assert!(matches!(race.await, 1 | 2));
+    /// })
+    /// ```
+    pub fn try_from_iter<I>(iter: I) -> Result<Self, LengthError>
+    where
+        I: IntoIterator<Item = Fut>,
+    {
+        Ok(utils::collect_array(iter)?.race())
+    }
+}
+
 impl<Fut, const N: usize> fmt::Debug for Race<Fut, N>
 where
     Fut: Future + fmt::Debug,
@@ -70,12 +103,19 @@ where
     type Future = Race<Fut::IntoFuture, N>;
 
     fn race(self) -> Self::Future {
+        assert!(N > 0, "Race requires at least one future");
         Race {
             futures: self.map(|fut| fut.into_future()),
             indexer: Indexer::new(N),
             done: false,
         }
     }
+
+    fn race_biased(self) -> Self::Future {
+        let mut race = self.race();
+        race.indexer.bias();
+        race
+    }
 }
 
 #[cfg(test)]
@@ -93,4 +133,44 @@ mod test {
             assert!(matches!(res, "hello" | "world"));
         });
     }
+
+    #[test]
+    fn race_biased_favours_first() {
+        futures_lite::future::block_on(async {
+            let futures = [1, 2, 3].map(|n| async move {
+                futures_lite::future::yield_now().await;
+                n
+            });
+            assert_eq!(futures.race_biased().await, 1);
+        });
+    }
+
+    #[test]
+    fn into_futures() {
+        /// A value which isn't a future itself, but can be turned into one.
+        struct Deferred(u8);
+
+        impl IntoFuture for Deferred {
+            type Output = u8;
+            type IntoFuture = future::Ready<u8>;
+
+            fn into_future(self) -> Self::IntoFuture {
+                future::ready(self.0)
+            }
+        }
+
+        futures_lite::future::block_on(async {
+            assert!(matches!([Deferred(1), Deferred(2)].race().await, 1 | 2));
+
+            let futures = [1, 2].map(|n| async move { n * 10 });
+            assert!(matches!(futures.race().await, 10 | 20));
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Race requires at least one future")]
+    fn empty() {
+        let futures: [future::Ready<u8>; 0] = [];
+        let _fut = futures.race();
+    }
 }
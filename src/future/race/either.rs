@@ -0,0 +1,194 @@
+use crate::utils;
+
+use core::fmt::{self, Debug};
+use core::future::{Future, IntoFuture};
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use pin_project::pin_project;
+
+/// Wait for the first of several futures with different output types to
+/// complete.
+///
+/// This is implemented for tuples of up to twelve futures. Unlike
+/// [`Race`][crate::future::Race], the futures don't need to share an output
+/// type: the race resolves to an `EitherN` enum, with one variant per
+/// position in the tuple, which tells which future won along with its
+/// output.
+pub trait RaceEither {
+    /// The resulting output type.
+    type Output;
+
+    /// Which kind of future are we turning this into?
+    type Future: Future<Output = Self::Output>;
+
+    /// Wait for the first future to complete, returning which one won
+    /// together with its output.
+    ///
+    /// The other futures are cancelled. This is useful for racing genuinely
+    /// different operations, such as a request against a timeout, without
+    /// boxing them or mapping their outputs to a common type first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_concurrency::future::Either2;
+    /// use futures_concurrency::prelude::*;
+    /// use futures_lite::future::{block_on, pending, ready};
+    ///
+    /// block_on(async {
+    ///     let request = ready("response");
+    ///     let timeout = pending::<()>();
+    ///     match (request, timeout).race_either().await {
+    ///         Either2::A(response) => assert_eq!(response, "response"),
+    ///         Either2::B(()) => panic!("timed out"),
+    ///     }
+    /// })
+    /// ```
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    fn race_either(self) -> Self::Future;
+}
+
+macro_rules! impl_race_either {
+    ($Either:ident $StructName:ident $($F:ident)+) => {
+        /// The output of a [`race_either`][RaceEither::race_either], telling
+        /// which future won the race.
+        ///
+        /// Each variant is named after the position of its future in the
+        /// tuple, starting from `A`.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum $Either<$($F),*> {
+            $(
+                #[doc = concat!("The future at position `", stringify!($F), "` won the race.")]
+                $F($F),
+            )*
+        }
+
+        /// Wait for the first of several futures with different output
+        /// types to complete.
+        ///
+        /// This `struct` is created by the [`race_either`] method on the
+        /// [`RaceEither`] trait. See its documentation for more.
+        ///
+        /// [`race_either`]: RaceEither::race_either
+        #[pin_project]
+        #[must_use = "futures do nothing unless you `.await` or poll them"]
+        #[allow(non_snake_case)]
+        pub struct $StructName<$($F),*>
+        where $(
+            $F: Future,
+        )* {
+            done: bool,
+            indexer: utils::Indexer,
+            $(#[pin] $F: $F,)*
+        }
+
+        impl<$($F),*> Debug for $StructName<$($F),*>
+        where $(
+            $F: Future + Debug,
+        )* {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_tuple("RaceEither")
+                    $(.field(&self.$F))*
+                    .finish()
+            }
+        }
+
+        impl<$($F),*> RaceEither for ($($F,)*)
+        where $(
+            $F: IntoFuture,
+        )* {
+            type Output = $Either<$($F::Output),*>;
+            type Future = $StructName<$($F::IntoFuture),*>;
+
+            fn race_either(self) -> Self::Future {
+                let ($($F,)*): ($($F,)*) = self;
+                $StructName {
+                    done: false,
+                    indexer: utils::Indexer::new(utils::tuple_len!($($F,)*)),
+                    $($F: $F.into_future()),*
+                }
+            }
+        }
+
+        impl<$($F),*> Future for $StructName<$($F),*>
+        where
+            $($F: Future),*
+        {
+            type Output = $Either<$($F::Output),*>;
+
+            fn poll(
+                self: Pin<&mut Self>, cx: &mut Context<'_>
+            ) -> Poll<Self::Output> {
+                let mut this = self.project();
+                assert!(!*this.done, "Futures must not be polled after completing");
+
+                #[repr(usize)]
+                enum Indexes {
+                    $($F),*
+                }
+
+                for i in this.indexer.iter() {
+                    utils::gen_conditions!(i, this, cx, poll, $((Indexes::$F as usize; $F, {
+                        Poll::Ready(output) => {
+                            *this.done = true;
+                            return Poll::Ready($Either::$F(output));
+                        },
+                        _ => continue,
+                    }))*);
+                }
+
+                Poll::Pending
+            }
+        }
+    };
+}
+
+impl_race_either! { Either2 RaceEither2 A B }
+impl_race_either! { Either3 RaceEither3 A B C }
+impl_race_either! { Either4 RaceEither4 A B C D }
+impl_race_either! { Either5 RaceEither5 A B C D E }
+impl_race_either! { Either6 RaceEither6 A B C D E F }
+impl_race_either! { Either7 RaceEither7 A B C D E F G }
+impl_race_either! { Either8 RaceEither8 A B C D E F G H }
+impl_race_either! { Either9 RaceEither9 A B C D E F G H I }
+impl_race_either! { Either10 RaceEither10 A B C D E F G H I J }
+impl_race_either! { Either11 RaceEither11 A B C D E F G H I J K }
+impl_race_either! { Either12 RaceEither12 A B C D E F G H I J K L }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::future;
+
+    #[test]
+    fn race_either_2() {
+        futures_lite::future::block_on(async {
+            let a = future::pending::<u8>();
+            let b = future::ready("world");
+            assert_eq!((a, b).race_either().await, Either2::B("world"));
+        });
+    }
+
+    #[test]
+    fn race_either_12() {
+        futures_lite::future::block_on(async {
+            let p = future::pending::<()>;
+            let futures = (
+                p(),
+                p(),
+                p(),
+                p(),
+                p(),
+                p(),
+                p(),
+                p(),
+                p(),
+                p(),
+                p(),
+                future::ready(12u64),
+            );
+            assert_eq!(futures.race_either().await, Either12::L(12));
+        });
+    }
+}
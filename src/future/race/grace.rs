@@ -0,0 +1,172 @@
+use crate::future::Timer;
+use crate::utils::{self, Indexer, PollVec};
+
+use core::fmt;
+use core::future::Future;
+use core::mem;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use core::time::Duration;
+
+use pin_project::pin_project;
+
+/// Wait for the first future to complete, then collect the futures which
+/// complete within a grace period.
+///
+/// This `struct` is created by the [`race_with_grace`] method on [`Race`].
+/// See its documentation for more.
+///
+/// [`race_with_grace`]: super::vec::Race::race_with_grace
+/// [`Race`]: super::vec::Race
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+#[pin_project]
+pub struct RaceGrace<Fut, T>
+where
+    Fut: Future,
+    T: Timer,
+{
+    #[pin]
+    futures: Vec<Fut>,
+    indexer: Indexer,
+    state: PollVec,
+    pending: usize,
+    /// Started once the winner completes.
+    #[pin]
+    sleep: Option<T::Sleep>,
+    timer: T,
+    dur: Duration,
+    winner: Option<Fut::Output>,
+    late: Vec<(usize, Fut::Output)>,
+    done: bool,
+}
+
+impl<Fut, T> RaceGrace<Fut, T>
+where
+    Fut: Future,
+    T: Timer,
+{
+    pub(crate) fn new(futures: Vec<Fut>, indexer: Indexer, dur: Duration, timer: T) -> Self {
+        let len = futures.len();
+        Self {
+            futures,
+            indexer,
+            state: PollVec::new(len),
+            pending: len,
+            sleep: None,
+            timer,
+            dur,
+            winner: None,
+            late: Vec::new(),
+            done: false,
+        }
+    }
+}
+
+impl<Fut, T> fmt::Debug for RaceGrace<Fut, T>
+where
+    Fut: Future + fmt::Debug,
+    T: Timer,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RaceGrace")
+            .field("futures", &self.futures)
+            .field("dur", &self.dur)
+            .finish()
+    }
+}
+
+impl<Fut, T> Future for RaceGrace<Fut, T>
+where
+    Fut: Future,
+    T: Timer,
+{
+    type Output = (Fut::Output, Vec<(usize, Fut::Output)>);
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+        assert!(!*this.done, "Futures must not be polled after completing");
+
+        for index in this.indexer.iter() {
+            if this.state[index].is_consumed() {
+                continue;
+            }
+
+            let fut = utils::get_pin_mut_from_vec(this.futures.as_mut(), index).unwrap();
+            if let Poll::Ready(item) = fut.poll(cx) {
+                this.state[index].set_consumed();
+                *this.pending -= 1;
+                if this.winner.is_none() {
+                    *this.winner = Some(item);
+                    this.sleep.set(Some(this.timer.sleep(*this.dur)));
+                } else {
+                    this.late.push((index, item));
+                }
+            }
+        }
+
+        let elapsed = match this.sleep.as_mut().as_pin_mut() {
+            Some(sleep) => sleep.poll(cx).is_ready(),
+            None => false,
+        };
+        if *this.pending == 0 || elapsed {
+            *this.done = true;
+            // Drop the futures which didn't make it within the grace period.
+            this.futures.set(Vec::new());
+            this.sleep.set(None);
+            let winner = this.winner.take().unwrap();
+            return Poll::Ready((winner, mem::take(this.late)));
+        }
+        Poll::Pending
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::future::Race as _;
+    use futures_lite::future::{self, block_on, Boxed, FutureExt};
+    use std::time::Duration;
+
+    #[test]
+    fn collects_near_ties() {
+        block_on(async {
+            let futures: Vec<Boxed<u8>> = vec![
+                future::pending().boxed(),
+                future::ready(1).boxed(),
+                async {
+                    future::yield_now().await;
+                    2
+                }
+                .boxed(),
+            ];
+            // The grace period ends after every other future has had a
+            // chance to complete.
+            let timer = |_: Duration| async {
+                for _ in 0..2 {
+                    future::yield_now().await;
+                }
+            };
+            let (winner, late) = futures
+                .race()
+                .race_with_grace(Duration::from_millis(1), timer)
+                .await;
+            assert_eq!(winner, 1);
+            assert_eq!(late, vec![(2, 2)]);
+        })
+    }
+
+    #[test]
+    fn ends_once_all_complete() {
+        block_on(async {
+            let futures = vec![future::ready(1), future::ready(2)];
+            let timer = |_: Duration| future::pending::<()>();
+            let (winner, late) = futures
+                .race()
+                .race_with_grace(Duration::from_secs(1), timer)
+                .await;
+            let mut all = vec![winner, late[0].1];
+            all.sort_unstable();
+            assert_eq!(all, vec![1, 2]);
+            assert_eq!(late.len(), 1);
+        })
+    }
+}
@@ -1,6 +1,10 @@
 use core::future::Future;
 
 pub(crate) mod array;
+pub(crate) mod either;
+pub(crate) mod grace;
+pub(crate) mod racer;
+pub(crate) mod slice;
 pub(crate) mod tuple;
 pub(crate) mod vec;
 
@@ -8,6 +12,27 @@ pub(crate) mod vec;
 ///
 /// Awaits multiple future at once, returning as soon as one completes. The
 /// other futures are cancelled.
+///
+/// # Racing fallible futures
+///
+/// Racing futures which return a `Result` resolves to whichever completes
+/// first, whether it succeeded or failed. This is the "fastest success or
+/// earliest failure" policy: an error fails the race straight away and the
+/// remaining futures are dropped. To ignore errors and wait for the first
+/// success instead, use [`RaceOk`][crate::future::RaceOk].
+///
+/// ```
+/// use futures_concurrency::prelude::*;
+/// use futures_lite::future::{block_on, pending, ready, Boxed, FutureExt};
+///
+/// block_on(async {
+///     let futures: Vec<Boxed<Result<u8, &str>>> = vec![
+///         pending().boxed(),
+///         ready(Err("refused")).boxed(),
+///     ];
+///     assert_eq!(futures.race().await, Err("refused"));
+/// })
+/// ```
 pub trait Race {
     /// The resulting output type.
     type Output;
@@ -21,5 +46,51 @@ pub trait Race {
     /// other futures are cancelled.
     ///
     /// This function returns a new future which polls all futures concurrently.
+    ///
+    /// # Panics
+    ///
+    /// An empty race has no output to resolve to, so calling this on an empty
+    /// array, vec or slice panics rather than returning a future which never
+    /// completes.
     fn race(self) -> Self::Future;
+
+    /// Wait for the first future to complete, favouring the earlier futures.
+    ///
+    /// This is the same as [`race`][Race::race], except that the futures are
+    /// always polled in order, starting from the first one, rather than from
+    /// a rotating starting point. When several futures are ready at once the
+    /// first one listed wins, which makes the outcome deterministic. This
+    /// suits a cheap first future with slower fallbacks, but a first future
+    /// which is always ready starves the others.
+    ///
+    /// # Panics
+    ///
+    /// Like [`race`][Race::race], this panics on an empty array, vec or
+    /// slice.
+    ///
+    /// # Implementing
+    ///
+    /// The default implementation falls back to [`race`][Race::race], which
+    /// doesn't favour any future. The implementations in this crate all
+    /// override it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_concurrency::prelude::*;
+    /// use futures_lite::future::block_on;
+    /// use std::future::ready;
+    ///
+    /// block_on(async {
+    ///     let cache = ready("cached");
+    ///     let origin = ready("fetched");
+    ///     assert_eq!((cache, origin).race_biased().await, "cached");
+    /// })
+    /// ```
+    fn race_biased(self) -> Self::Future
+    where
+        Self: Sized,
+    {
+        self.race()
+    }
 }
@@ -0,0 +1,201 @@
+use crate::utils::Indexer;
+
+use core::fmt;
+use core::future::{Future, IntoFuture};
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+/// A reusable vec race, which keeps its allocations between rounds.
+///
+/// Racing a fresh vec of futures on every iteration of a loop allocates a
+/// new vec each time. A `Racer` instead holds on to the storage for its
+/// futures, and each call to [`race_next`][Racer::race_next] moves a new set
+/// of futures into it. The losing futures of a round are dropped as soon as
+/// the winner completes.
+///
+/// # Examples
+///
+/// ```
+/// use futures_concurrency::vec::Racer;
+/// use futures_lite::future::{block_on, pending, ready, Boxed, FutureExt};
+///
+/// block_on(async {
+///     let mut racer = Racer::new();
+///     for round in 0..3 {
+///         let futures: Vec<Boxed<u32>> = vec![pending().boxed(), ready(round).boxed()];
+///         assert_eq!(racer.race_next(futures).await, round);
+///     }
+/// })
+/// ```
+pub struct Racer<Fut> {
+    futures: Vec<Fut>,
+    indexer: Indexer,
+}
+
+impl<Fut> Racer<Fut>
+where
+    Fut: Future,
+{
+    /// Create a new `Racer`.
+    pub fn new() -> Self {
+        Self {
+            futures: Vec::new(),
+            indexer: Indexer::new(0),
+        }
+    }
+
+    /// Race a new set of futures, reusing the storage of earlier rounds.
+    ///
+    /// Any futures left over from an earlier round which didn't run to
+    /// completion are dropped first.
+    ///
+    /// # Panics
+    ///
+    /// This panics if `futures` is empty.
+    pub fn race_next<I>(&mut self, futures: I) -> RaceNext<'_, Fut>
+    where
+        I: IntoIterator,
+        I::Item: IntoFuture<IntoFuture = Fut>,
+    {
+        self.futures.clear();
+        self.futures
+            .extend(futures.into_iter().map(IntoFuture::into_future));
+        assert!(
+            !self.futures.is_empty(),
+            "Race requires at least one future"
+        );
+        self.indexer.set_max(self.futures.len());
+        RaceNext { racer: self }
+    }
+}
+
+impl<Fut> Default for Racer<Fut>
+where
+    Fut: Future,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Fut> fmt::Debug for Racer<Fut>
+where
+    Fut: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.futures.iter()).finish()
+    }
+}
+
+/// Wait for the first future of a round to complete.
+///
+/// This `struct` is created by the [`race_next`] method on [`Racer`]. See its
+/// documentation for more.
+///
+/// [`race_next`]: Racer::race_next
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct RaceNext<'a, Fut>
+where
+    Fut: Future,
+{
+    racer: &'a mut Racer<Fut>,
+}
+
+impl<Fut> fmt::Debug for RaceNext<'_, Fut>
+where
+    Fut: Future + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.racer.fmt(f)
+    }
+}
+
+impl<Fut> Future for RaceNext<'_, Fut>
+where
+    Fut: Future,
+{
+    type Output = Fut::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let racer = &mut *self.racer;
+        assert!(
+            !racer.futures.is_empty(),
+            "Futures must not be polled after completing"
+        );
+
+        for index in racer.indexer.iter() {
+            // SAFETY: the racer never moves the futures it holds. They only
+            // leave its vec by being dropped in place, and the vec is only
+            // grown after it's been cleared.
+            let fut = unsafe { Pin::new_unchecked(&mut racer.futures[index]) };
+            if let Poll::Ready(output) = fut.poll(cx) {
+                racer.futures.clear();
+                return Poll::Ready(output);
+            }
+        }
+        Poll::Pending
+    }
+}
+
+/// Cancel the remaining futures of the round.
+impl<Fut> Drop for RaceNext<'_, Fut>
+where
+    Fut: Future,
+{
+    fn drop(&mut self) {
+        self.racer.futures.clear();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use futures_lite::future::{block_on, pending, ready, Boxed, FutureExt};
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    #[test]
+    fn reuses_storage() {
+        block_on(async {
+            let mut racer = Racer::new();
+            let futures: Vec<Boxed<u8>> = vec![pending().boxed(), ready(1).boxed()];
+            assert_eq!(racer.race_next(futures).await, 1);
+            let capacity = racer.futures.capacity();
+
+            let futures: Vec<Boxed<u8>> = vec![ready(2).boxed(), pending().boxed()];
+            assert_eq!(racer.race_next(futures).await, 2);
+            assert_eq!(racer.futures.capacity(), capacity);
+        });
+    }
+
+    #[test]
+    fn drops_losers() {
+        struct Guard(Rc<Cell<usize>>);
+        impl Drop for Guard {
+            fn drop(&mut self) {
+                self.0.set(self.0.get() + 1);
+            }
+        }
+
+        let dropped = Rc::new(Cell::new(0));
+        let loser = |dropped: &Rc<Cell<usize>>| {
+            let guard = Guard(dropped.clone());
+            async move {
+                let _guard = guard;
+                pending::<u8>().await
+            }
+            .boxed_local()
+        };
+
+        block_on(async {
+            let mut racer = Racer::new();
+            let futures = vec![loser(&dropped), ready(1).boxed_local()];
+            assert_eq!(racer.race_next(futures).await, 1);
+            assert_eq!(dropped.get(), 1);
+
+            // Cancelling a round drops its futures too.
+            drop(racer.race_next(vec![loser(&dropped)]));
+            assert_eq!(dropped.get(), 2);
+        });
+    }
+}
@@ -0,0 +1,95 @@
+use crate::utils::Indexer;
+
+use super::Race as RaceTrait;
+
+use core::fmt;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+/// Wait for the first future in a borrowed slice to complete.
+///
+/// This `struct` is created by the [`race`] method on the [`Race`] trait. See
+/// its documentation for more.
+///
+/// [`race`]: crate::future::Race::race
+/// [`Race`]: crate::future::Race
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Race<'a, Fut>
+where
+    Fut: Future + Unpin,
+{
+    futures: &'a mut [Fut],
+    indexer: Indexer,
+    done: bool,
+}
+
+impl<Fut> fmt::Debug for Race<'_, Fut>
+where
+    Fut: Future + Unpin + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.futures.iter()).finish()
+    }
+}
+
+impl<Fut> Future for Race<'_, Fut>
+where
+    Fut: Future + Unpin,
+{
+    type Output = (usize, Fut::Output);
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        assert!(!this.done, "Futures must not be polled after completing");
+
+        for index in this.indexer.iter() {
+            if let Poll::Ready(item) = Pin::new(&mut this.futures[index]).poll(cx) {
+                this.done = true;
+                return Poll::Ready((index, item));
+            }
+        }
+        Poll::Pending
+    }
+}
+
+impl<'a, Fut> RaceTrait for &'a mut [Fut]
+where
+    Fut: Future + Unpin,
+{
+    type Output = (usize, Fut::Output);
+    type Future = Race<'a, Fut>;
+
+    fn race(self) -> Self::Future {
+        assert!(!self.is_empty(), "Race requires at least one future");
+        Race {
+            indexer: Indexer::new(self.len()),
+            futures: self,
+            done: false,
+        }
+    }
+
+    fn race_biased(self) -> Self::Future {
+        let mut race = self.race();
+        race.indexer.bias();
+        race
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use futures_lite::future::{block_on, pending, ready, Boxed, FutureExt};
+
+    #[test]
+    fn race_borrowed_slice() {
+        block_on(async {
+            let mut futures: Vec<Boxed<u8>> = vec![pending().boxed(), ready(2).boxed()];
+            let (index, output) = futures.as_mut_slice().race().await;
+            assert_eq!((index, output), (1, 2));
+
+            // The losing futures are still owned by the caller.
+            assert_eq!(futures.len(), 2);
+        });
+    }
+}
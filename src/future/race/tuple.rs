@@ -56,6 +56,12 @@ macro_rules! impl_race_tuple {
                     $($F: $F.into_future()),*
                 }
             }
+
+            fn race_biased(self) -> Self::Future {
+                let mut race = self.race();
+                race.indexer.bias();
+                race
+            }
         }
 
         impl<T, $($F: Future),*> Future for $StructName<T, $($F),*>
@@ -126,6 +132,25 @@ mod test {
         });
     }
 
+    #[test]
+    fn race_biased_favours_first() {
+        use futures_lite::future::yield_now;
+
+        futures_lite::future::block_on(async {
+            // Both futures become ready on the second poll, which a fair race
+            // would start from the second future.
+            let a = async {
+                yield_now().await;
+                "hello"
+            };
+            let b = async {
+                yield_now().await;
+                "world"
+            };
+            assert_eq!((a, b).race_biased().await, "hello");
+        });
+    }
+
     #[test]
     fn race_3() {
         futures_lite::future::block_on(async {
@@ -1,12 +1,17 @@
-use crate::utils::{self, Indexer};
+use crate::future::{PollFuture, Timer};
+use crate::utils::{self, Indexer, PollVec, RaceScheduler, WakerVec};
 
+use super::grace::RaceGrace;
 use super::Race as RaceTrait;
 
 use core::fmt;
 use core::future::{Future, IntoFuture};
+use core::mem;
 use core::pin::Pin;
 use core::task::{Context, Poll};
+use core::time::Duration;
 
+use futures_core::Stream;
 use pin_project::pin_project;
 
 /// Wait for the first future to complete.
@@ -20,7 +25,7 @@ use pin_project::pin_project;
 #[pin_project]
 pub struct Race<Fut>
 where
-    Fut: Future,
+    Fut: PollFuture,
 {
     #[pin]
     futures: Vec<Fut>,
@@ -28,10 +33,196 @@ where
     done: bool,
 }
 
+impl<Fut> Race<Fut>
+where
+    Fut: PollFuture,
+{
+    /// Create a new `Race` from a vector of [`PollFuture`]s.
+    ///
+    /// This is how futures from a runtime with its own future trait are
+    /// raced. Regular futures are usually raced through the
+    /// [`Race`](crate::future::Race) trait instead.
+    ///
+    /// # Panics
+    ///
+    /// This panics if `futures` is empty.
+    pub fn from_poll_futures(futures: Vec<Fut>) -> Self {
+        assert!(!futures.is_empty(), "Race requires at least one future");
+        Race {
+            indexer: Indexer::new(futures.len()),
+            futures,
+            done: false,
+        }
+    }
+
+    /// Drop every future in the race right away, without a winner.
+    ///
+    /// This releases the futures and their resources without having to drop
+    /// the race itself, for example when it's stored in a struct which stays
+    /// around. An aborted race never resolves: polling it again returns
+    /// `Poll::Pending` forever, and the waker is never woken. Aborting a race
+    /// which has already completed drops its remaining futures, and polling
+    /// it still panics.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_concurrency::prelude::*;
+    /// use futures_lite::future::{block_on, pending, poll_once};
+    /// use std::pin::pin;
+    ///
+    /// block_on(async {
+    ///     let mut race = pin!(vec![pending::<u8>(), pending()].race());
+    ///     assert_eq!(poll_once(race.as_mut()).await, None);
+    ///
+    ///     race.as_mut().abort();
+    ///     assert_eq!(poll_once(race.as_mut()).await, None);
+    /// })
+    /// ```
+    pub fn abort(self: Pin<&mut Self>) {
+        let mut this = self.project();
+        // Replacing the vector drops the futures in place, and frees its
+        // allocation as well. With no futures and an empty range to iterate
+        // over, polling finds nothing to wait on.
+        this.futures.set(Vec::new());
+        this.indexer.set_max(0);
+    }
+}
+
+impl<Fut> Race<Fut>
+where
+    Fut: Future,
+{
+    /// Wait for the first future whose output satisfies `predicate`.
+    ///
+    /// Outputs for which `predicate` returns `false` are discarded, and the
+    /// remaining futures keep running. If no output satisfies `predicate`,
+    /// the output of the last future to complete is returned instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_concurrency::prelude::*;
+    /// use futures_lite::future::block_on;
+    /// use std::future::ready;
+    ///
+    /// block_on(async {
+    ///     let futures = vec![ready(1), ready(2), ready(3)];
+    ///     let even = futures.race().race_until(|n| n % 2 == 0).await;
+    ///     assert_eq!(even, 2);
+    /// })
+    /// ```
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub fn race_until<P>(self, predicate: P) -> RaceUntil<Fut, P>
+    where
+        P: FnMut(&Fut::Output) -> bool,
+    {
+        let len = self.futures.len();
+        RaceUntil {
+            futures: self.futures,
+            indexer: self.indexer,
+            state: PollVec::new(len),
+            pending: len,
+            predicate,
+            done: self.done,
+        }
+    }
+
+    /// Wait for the first future to complete, polling the futures which have
+    /// won the fewest earlier races using `scheduler` first.
+    ///
+    /// The winning index is recorded in `scheduler` once the race completes.
+    /// See [`RaceScheduler`] for more.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub fn race_with(self, scheduler: &mut RaceScheduler) -> RaceWith<'_, Fut> {
+        let order = scheduler.order(self.futures.len());
+        RaceWith {
+            futures: self.futures,
+            order,
+            scheduler,
+            done: self.done,
+        }
+    }
+
+    /// Wait for the first future to complete, then keep polling the others
+    /// for `dur` to collect any which complete shortly after.
+    ///
+    /// This is meant for hedged requests, where only the fastest response is
+    /// needed but near-ties are worth recording. A sleep created by `timer`
+    /// is started once the first future completes. The race resolves to the
+    /// winner's output together with the index and output of every future
+    /// which completed before the sleep did, in the order they completed. The
+    /// remaining futures are then dropped. If every future completes, the
+    /// race resolves without waiting for the sleep.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_concurrency::prelude::*;
+    /// use futures_lite::future::{block_on, pending, ready, Boxed, FutureExt};
+    /// use std::time::Duration;
+    ///
+    /// block_on(async {
+    ///     let futures: Vec<Boxed<u8>> = vec![ready(1).boxed(), pending().boxed()];
+    ///     let timer = |dur| async_std::task::sleep(dur);
+    ///     let (winner, late) = futures
+    ///         .race()
+    ///         .race_with_grace(Duration::from_millis(10), timer)
+    ///         .await;
+    ///     assert_eq!(winner, 1);
+    ///     assert!(late.is_empty());
+    /// })
+    /// ```
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub fn race_with_grace<T>(self, dur: Duration, timer: T) -> RaceGrace<Fut, T>
+    where
+        T: Timer,
+    {
+        RaceGrace::new(self.futures, self.indexer, dur, timer)
+    }
+
+    /// Turn the race into a stream which yields every future's output, in
+    /// the order the futures complete.
+    ///
+    /// Each item is the index of a future together with its output. A future
+    /// counts as complete on the poll where it returns `Poll::Ready`, and
+    /// every call to `poll_next` yields at most one output: no other futures
+    /// are polled once one of them completes. This means the items are
+    /// always emitted in exactly the order the futures were seen to
+    /// complete, however their wakeups are interleaved. The stream ends once
+    /// every future has completed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_concurrency::prelude::*;
+    /// use futures_lite::future::block_on;
+    /// use futures_lite::stream::StreamExt;
+    /// use std::future::ready;
+    ///
+    /// block_on(async {
+    ///     let futures = vec![ready('a'), ready('b'), ready('c')];
+    ///     let mut ranking: Vec<_> = futures.race().race_all().collect().await;
+    ///     ranking.sort();
+    ///     assert_eq!(ranking, vec![(0, 'a'), (1, 'b'), (2, 'c')]);
+    /// })
+    /// ```
+    #[must_use = "streams do nothing unless you `.await` or poll them"]
+    pub fn race_all(self) -> RaceAll<Fut> {
+        let len = self.futures.len();
+        RaceAll {
+            futures: self.futures,
+            indexer: self.indexer,
+            state: PollVec::new(len),
+            wakers: WakerVec::new(len),
+            pending: len,
+        }
+    }
+}
+
 impl<Fut> fmt::Debug for Race<Fut>
 where
-    Fut: Future + fmt::Debug,
-    Fut::Output: fmt::Debug,
+    Fut: PollFuture + fmt::Debug,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_list().entries(self.futures.iter()).finish()
@@ -40,7 +231,7 @@ where
 
 impl<Fut> Future for Race<Fut>
 where
-    Fut: Future,
+    Fut: PollFuture,
 {
     type Output = Fut::Output;
 
@@ -50,7 +241,7 @@ where
 
         for index in this.indexer.iter() {
             let fut = utils::get_pin_mut_from_vec(this.futures.as_mut(), index).unwrap();
-            match fut.poll(cx) {
+            match fut.poll_future(cx) {
                 Poll::Ready(item) => {
                     *this.done = true;
                     return Poll::Ready(item);
@@ -70,11 +261,287 @@ where
     type Future = Race<Fut::IntoFuture>;
 
     fn race(self) -> Self::Future {
-        Race {
-            indexer: Indexer::new(self.len()),
-            futures: self.into_iter().map(|fut| fut.into_future()).collect(),
-            done: false,
+        Race::from_poll_futures(self.into_iter().map(|fut| fut.into_future()).collect())
+    }
+
+    fn race_biased(self) -> Self::Future {
+        let mut race = self.race();
+        race.indexer.bias();
+        race
+    }
+}
+
+/// Wait for the first future whose output satisfies a predicate.
+///
+/// This `struct` is created by the [`race_until`] method on [`Race`]. See its
+/// documentation for more.
+///
+/// [`race_until`]: Race::race_until
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+#[pin_project]
+pub struct RaceUntil<Fut, P>
+where
+    Fut: Future,
+{
+    #[pin]
+    futures: Vec<Fut>,
+    indexer: Indexer,
+    state: PollVec,
+    pending: usize,
+    predicate: P,
+    done: bool,
+}
+
+impl<Fut, P> fmt::Debug for RaceUntil<Fut, P>
+where
+    Fut: Future + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.futures.iter()).finish()
+    }
+}
+
+impl<Fut, P> Future for RaceUntil<Fut, P>
+where
+    Fut: Future,
+    P: FnMut(&Fut::Output) -> bool,
+{
+    type Output = Fut::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+        assert!(!*this.done, "Futures must not be polled after completing");
+
+        for index in this.indexer.iter() {
+            if this.state[index].is_consumed() {
+                continue;
+            }
+
+            let fut = utils::get_pin_mut_from_vec(this.futures.as_mut(), index).unwrap();
+            if let Poll::Ready(item) = fut.poll(cx) {
+                this.state[index].set_consumed();
+                *this.pending -= 1;
+                if *this.pending == 0 || (this.predicate)(&item) {
+                    *this.done = true;
+                    return Poll::Ready(item);
+                }
+            }
+        }
+        Poll::Pending
+    }
+}
+
+/// Wait for the first future to complete, favoring the futures which have won
+/// the fewest earlier races.
+///
+/// This `struct` is created by the [`race_with`] method on [`Race`]. See its
+/// documentation for more.
+///
+/// [`race_with`]: Race::race_with
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+#[pin_project]
+pub struct RaceWith<'a, Fut>
+where
+    Fut: Future,
+{
+    #[pin]
+    futures: Vec<Fut>,
+    order: Vec<usize>,
+    scheduler: &'a mut RaceScheduler,
+    done: bool,
+}
+
+impl<Fut> fmt::Debug for RaceWith<'_, Fut>
+where
+    Fut: Future + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.futures.iter()).finish()
+    }
+}
+
+impl<Fut> Future for RaceWith<'_, Fut>
+where
+    Fut: Future,
+{
+    type Output = Fut::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+        assert!(!*this.done, "Futures must not be polled after completing");
+
+        for &index in this.order.iter() {
+            let fut = utils::get_pin_mut_from_vec(this.futures.as_mut(), index).unwrap();
+            if let Poll::Ready(item) = fut.poll(cx) {
+                *this.done = true;
+                this.scheduler.record_win(index);
+                return Poll::Ready(item);
+            }
+        }
+        Poll::Pending
+    }
+}
+
+/// A stream which yields the output of every future in a race, in the order
+/// they complete.
+///
+/// This `struct` is created by the [`race_all`] method on [`Race`]. See its
+/// documentation for more.
+///
+/// [`race_all`]: Race::race_all
+#[must_use = "streams do nothing unless you `.await` or poll them"]
+#[pin_project]
+pub struct RaceAll<Fut>
+where
+    Fut: Future,
+{
+    #[pin]
+    futures: Vec<Fut>,
+    indexer: Indexer,
+    state: PollVec,
+    wakers: WakerVec,
+    pending: usize,
+}
+
+impl<Fut> fmt::Debug for RaceAll<Fut>
+where
+    Fut: Future + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.futures.iter()).finish()
+    }
+}
+
+impl<Fut> Stream for RaceAll<Fut>
+where
+    Fut: Future,
+{
+    type Item = (usize, Fut::Output);
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+        if *this.pending == 0 {
+            return Poll::Ready(None);
+        }
+
+        let mut readiness = this.wakers.readiness().lock().unwrap();
+        readiness.set_waker(cx.waker());
+
+        for index in this.indexer.iter() {
+            if !readiness.any_ready() {
+                // Nothing is ready yet
+                return Poll::Pending;
+            } else if !readiness.clear_ready(index) || this.state[index].is_consumed() {
+                continue;
+            }
+
+            // unlock readiness so we don't deadlock when polling
+            drop(readiness);
+
+            // Obtain the intermediate waker.
+            let mut cx = Context::from_waker(this.wakers.get(index).unwrap());
+
+            let fut = utils::get_pin_mut_from_vec(this.futures.as_mut(), index).unwrap();
+            if let Poll::Ready(item) = fut.poll(&mut cx) {
+                this.state[index].set_consumed();
+                *this.pending -= 1;
+                return Poll::Ready(Some((index, item)));
+            }
+
+            // Lock readiness so we can use it again
+            readiness = this.wakers.readiness().lock().unwrap();
+        }
+        Poll::Pending
+    }
+}
+
+/// Wait for the first future to complete, returning the remaining futures.
+///
+/// This `struct` is created by the [`select_all`] function. See its
+/// documentation for more.
+///
+/// [`select_all`]: crate::future::select_all
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct SelectAll<Fut> {
+    futures: Vec<Fut>,
+    indexer: Indexer,
+    done: bool,
+}
+
+impl<Fut> fmt::Debug for SelectAll<Fut>
+where
+    Fut: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.futures.iter()).finish()
+    }
+}
+
+impl<Fut> Future for SelectAll<Fut>
+where
+    Fut: Future + Unpin,
+{
+    type Output = (Fut::Output, usize, Vec<Fut>);
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        assert!(!this.done, "Futures must not be polled after completing");
+
+        for index in this.indexer.iter() {
+            if let Poll::Ready(item) = Pin::new(&mut this.futures[index]).poll(cx) {
+                this.done = true;
+                let mut rest = mem::take(&mut this.futures);
+                rest.remove(index);
+                return Poll::Ready((item, index, rest));
+            }
         }
+        Poll::Pending
+    }
+}
+
+/// Wait for the first future in an iterator to complete.
+///
+/// This is a variant of [`Race::race`] for `Vec` which doesn't cancel the
+/// other futures, matching the signature of `futures::future::select_all`.
+/// It resolves to the output of the first future to complete, its index, and
+/// the remaining futures. Unlike the `futures` version, the remaining futures
+/// keep their original relative order.
+///
+/// [`Race::race`]: crate::future::Race::race
+///
+/// # Panics
+///
+/// This function will panic if the iterator is empty.
+///
+/// # Examples
+///
+/// ```
+/// use futures_concurrency::future::select_all;
+/// use futures_lite::future::{block_on, pending, ready, Boxed, FutureExt};
+///
+/// block_on(async {
+///     let futures: Vec<Boxed<u8>> = vec![pending().boxed(), ready(1).boxed(), pending().boxed()];
+///     let (output, index, rest) = select_all(futures).await;
+///     assert_eq!(output, 1);
+///     assert_eq!(index, 1);
+///     assert_eq!(rest.len(), 2);
+/// })
+/// ```
+pub fn select_all<I>(iter: I) -> SelectAll<<I::Item as IntoFuture>::IntoFuture>
+where
+    I: IntoIterator,
+    I::Item: IntoFuture,
+    <I::Item as IntoFuture>::IntoFuture: Unpin,
+{
+    let futures: Vec<_> = iter.into_iter().map(IntoFuture::into_future).collect();
+    assert!(
+        !futures.is_empty(),
+        "iterator provided to `select_all` was empty"
+    );
+    SelectAll {
+        indexer: Indexer::new(futures.len()),
+        futures,
+        done: false,
     }
 }
 
@@ -83,6 +550,60 @@ mod test {
     use super::*;
     use std::future;
 
+    /// A future which only implements `PollFuture`.
+    struct Shim(Option<u8>);
+
+    impl PollFuture for Shim {
+        type Output = u8;
+
+        fn poll_future(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<u8> {
+            match self.0 {
+                Some(n) => Poll::Ready(n),
+                None => Poll::Pending,
+            }
+        }
+    }
+
+    #[test]
+    fn local_box_futures() {
+        use futures::executor::LocalPool;
+        use futures::future::{FutureExt, LocalBoxFuture};
+        use futures::task::LocalSpawnExt;
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        let mut pool = LocalPool::new();
+        let shared = Rc::new(Cell::new(0));
+        let futures: Vec<LocalBoxFuture<'static, u8>> =
+            vec![futures_lite::future::pending().boxed_local(), {
+                let shared = shared.clone();
+                async move {
+                    futures_lite::future::yield_now().await;
+                    shared.set(1);
+                    1
+                }
+                .boxed_local()
+            }];
+
+        let winner = Rc::new(Cell::new(0));
+        let winner2 = winner.clone();
+        pool.spawner()
+            .spawn_local(async move { winner2.set(futures.race().await) })
+            .unwrap();
+        pool.run();
+
+        assert_eq!(winner.get(), 1);
+        assert_eq!(shared.get(), 1);
+    }
+
+    #[test]
+    fn poll_futures() {
+        futures_lite::future::block_on(async {
+            let race = Race::from_poll_futures(vec![Shim(None), Shim(Some(2))]);
+            assert_eq!(race.await, 2);
+        });
+    }
+
     // NOTE: we should probably poll in random order.
     #[test]
     fn no_fairness() {
@@ -93,4 +614,142 @@ mod test {
             assert!(matches!(res, "hello" | "world"));
         });
     }
+
+    #[test]
+    fn race_all_yields_in_completion_order() {
+        use futures::channel::oneshot;
+        use futures_lite::future::{block_on, poll_once};
+        use futures_lite::stream::StreamExt;
+
+        block_on(async {
+            let (mut senders, receivers): (Vec<_>, Vec<_>) = (0..4)
+                .map(|_| oneshot::channel::<usize>())
+                .map(|(s, r)| (Some(s), r))
+                .unzip();
+            let mut ranking = receivers.race().race_all();
+            assert!(poll_once(ranking.next()).await.is_none());
+
+            // Complete the futures in an order which differs both from their
+            // indexes and from the order the stream starts polling in.
+            for &index in &[2, 0, 3, 1] {
+                senders[index].take().unwrap().send(index).unwrap();
+                let (winner, output) = ranking.next().await.unwrap();
+                assert_eq!(winner, index);
+                assert_eq!(output, Ok(index));
+            }
+            assert!(ranking.next().await.is_none());
+        });
+    }
+
+    #[test]
+    fn select_all_keeps_rest_in_order() {
+        use futures_lite::future::{pending, ready, Boxed, FutureExt};
+
+        futures_lite::future::block_on(async {
+            let futures: Vec<Boxed<u8>> = vec![
+                pending().boxed(),
+                ready(1).boxed(),
+                ready(2).boxed(),
+                ready(3).boxed(),
+            ];
+            let (output, index, rest) = super::select_all(futures).await;
+            assert_eq!((output, index), (1, 1));
+            assert_eq!(rest.len(), 3);
+
+            // The remaining futures keep their relative order.
+            let (output, index, rest) = super::select_all(rest).await;
+            assert_eq!((output, index), (2, 1));
+            let (output, index, rest) = super::select_all(rest).await;
+            assert_eq!((output, index), (3, 1));
+            assert_eq!(rest.len(), 1);
+        });
+    }
+
+    #[test]
+    #[should_panic]
+    fn select_all_empty() {
+        let futures: Vec<future::Ready<u8>> = vec![];
+        let _fut = super::select_all(futures);
+    }
+
+    #[test]
+    #[should_panic(expected = "Race requires at least one future")]
+    fn empty() {
+        let futures: Vec<future::Ready<u8>> = vec![];
+        let _fut = futures.race();
+    }
+
+    #[test]
+    fn race_until_skips_rejected_outputs() {
+        futures_lite::future::block_on(async {
+            let futures = vec![future::ready(1), future::ready(2), future::ready(3)];
+            let res = futures.race().race_until(|n| *n == 3).await;
+            assert_eq!(res, 3);
+        });
+    }
+
+    #[test]
+    fn race_until_returns_last_output() {
+        futures_lite::future::block_on(async {
+            let futures = vec![future::ready(1), future::ready(2)];
+            let res = futures.race().race_until(|_| false).await;
+            assert!(matches!(res, 1 | 2));
+        });
+    }
+
+    #[test]
+    fn race_with_spreads_wins() {
+        futures_lite::future::block_on(async {
+            let mut scheduler = RaceScheduler::new();
+            let mut winners = vec![];
+            for _ in 0..6 {
+                let futures = vec![future::ready(0), future::ready(1), future::ready(2)];
+                winners.push(futures.race().race_with(&mut scheduler).await);
+            }
+            assert_eq!(winners, vec![0, 1, 2, 0, 1, 2]);
+            assert_eq!(scheduler.wins(), &[2, 2, 2]);
+        });
+    }
+
+    #[test]
+    fn race_biased_favours_first() {
+        use futures_lite::future::yield_now;
+
+        futures_lite::future::block_on(async {
+            let futures: Vec<_> = (0..3)
+                .map(|n| async move {
+                    yield_now().await;
+                    n
+                })
+                .collect();
+            let race = futures.race_biased();
+            assert_eq!(race.race_until(|n| *n > 0).await, 1);
+        });
+    }
+
+    #[test]
+    fn abort_drops_futures_now() {
+        use futures_lite::future::{pending, poll_once};
+        use std::rc::Rc;
+
+        futures_lite::future::block_on(async {
+            let guard = Rc::new(());
+            let futures: Vec<_> = (0..3)
+                .map(|_| {
+                    let guard = guard.clone();
+                    async move {
+                        let _guard = guard;
+                        pending::<u8>().await
+                    }
+                })
+                .collect();
+            let mut race = std::pin::pin!(futures.race());
+            assert_eq!(poll_once(race.as_mut()).await, None);
+            assert_eq!(Rc::strong_count(&guard), 4);
+
+            race.as_mut().abort();
+            assert_eq!(Rc::strong_count(&guard), 1);
+            assert_eq!(poll_once(race.as_mut()).await, None);
+        });
+    }
 }
@@ -1,6 +1,7 @@
 use super::RaceOk as RaceOkTrait;
 use crate::utils::array_assume_init;
 use crate::utils::iter_pin_mut;
+use crate::utils::PollArray;
 
 use core::array;
 use core::fmt;
@@ -9,7 +10,7 @@ use core::mem::{self, MaybeUninit};
 use core::pin::Pin;
 use core::task::{Context, Poll};
 
-use pin_project::pin_project;
+use pin_project::{pin_project, pinned_drop};
 
 mod error;
 
@@ -23,7 +24,7 @@ pub use error::AggregateError;
 /// [`race_ok`]: crate::future::RaceOk::race_ok
 /// [`RaceOk`]: crate::future::RaceOk
 #[must_use = "futures do nothing unless you `.await` or poll them"]
-#[pin_project]
+#[pin_project(PinnedDrop)]
 pub struct RaceOk<Fut, T, E, const N: usize>
 where
     T: fmt::Debug,
@@ -32,6 +33,8 @@ where
     #[pin]
     futures: [Fut; N],
     errors: [MaybeUninit<E>; N],
+    /// Marks the futures which failed, and whose error is initialized.
+    state: PollArray<N>,
     completed: usize,
 }
 
@@ -59,12 +62,17 @@ where
 
         let futures = iter_pin_mut(this.futures);
 
-        for (fut, out) in futures.zip(this.errors.iter_mut()) {
+        for (i, fut) in futures.enumerate() {
+            // A future which failed has completed, and mustn't be polled again.
+            if !this.state[i].is_pending() {
+                continue;
+            }
             if let Poll::Ready(output) = fut.poll(cx) {
                 match output {
                     Ok(ok) => return Poll::Ready(Ok(ok)),
                     Err(err) => {
-                        *out = MaybeUninit::new(err);
+                        this.errors[i] = MaybeUninit::new(err);
+                        this.state[i].set_ready();
                         *this.completed += 1;
                     }
                 }
@@ -73,6 +81,9 @@ where
 
         let all_completed = *this.completed == N;
         if all_completed {
+            // The errors are moved out, so they mustn't be dropped again.
+            this.state.set_all_completed();
+
             let mut errors = array::from_fn(|_| MaybeUninit::uninit());
             mem::swap(&mut errors, this.errors);
 
@@ -100,16 +111,37 @@ where
         RaceOk {
             futures: self.map(|fut| fut.into_future()),
             errors: array::from_fn(|_| MaybeUninit::uninit()),
+            state: PollArray::new(),
             completed: 0,
         }
     }
 }
 
+/// Drop the errors of the futures which already failed.
+#[pinned_drop]
+impl<Fut, T, E, const N: usize> PinnedDrop for RaceOk<Fut, T, E, N>
+where
+    T: fmt::Debug,
+    Fut: Future<Output = Result<T, E>>,
+{
+    fn drop(self: Pin<&mut Self>) {
+        let this = self.project();
+        for (state, err) in this.state.iter_mut().zip(this.errors.iter_mut()) {
+            if state.is_ready() {
+                // SAFETY: only the errors of failed futures are marked as ready.
+                unsafe { err.assume_init_drop() };
+                state.set_consumed();
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
     use std::future;
     use std::io::{Error, ErrorKind};
+    use std::rc::Rc;
 
     #[test]
     fn all_ok() {
@@ -148,4 +180,30 @@ mod test {
             assert_eq!(errs[1].to_string(), "oh no");
         });
     }
+
+    #[test]
+    fn later_ok_after_err() {
+        futures_lite::future::block_on(async {
+            #[derive(Debug)]
+            struct Guarded(#[allow(dead_code)] Rc<()>);
+            impl fmt::Display for Guarded {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    write!(f, "guarded")
+                }
+            }
+            impl std::error::Error for Guarded {}
+
+            type Fut = Pin<Box<dyn Future<Output = Result<&'static str, Guarded>>>>;
+            let guard = Rc::new(());
+            let err: Fut = Box::pin(future::ready(Err(Guarded(guard.clone()))));
+            let ok: Fut = Box::pin(async {
+                futures_lite::future::yield_now().await;
+                Ok("hello")
+            });
+            // The failed future isn't polled again, and its error doesn't
+            // outlive the race.
+            assert_eq!([err, ok].race_ok().await.unwrap(), "hello");
+            assert_eq!(Rc::strong_count(&guard), 1);
+        });
+    }
 }
@@ -96,6 +96,11 @@ macro_rules! impl_race_ok_tuple {
                 }
 
                 for i in this.indexer.iter() {
+                    // A future which failed has completed, and mustn't be
+                    // polled again.
+                    if this.errors_states[i].is_ready() {
+                        continue;
+                    }
                     utils::gen_conditions!(i, this, cx, poll, $((Indexes::$F as usize; $F, {
                         Poll::Ready(output) => match output {
                             Ok(output) => {
@@ -217,4 +222,17 @@ mod test {
             assert_eq!(errors[1], "world");
         });
     }
+
+    #[test]
+    fn race_ok_later_ok_after_err() {
+        futures_lite::future::block_on(async {
+            let a = future::ready(Err::<&str, _>("oops"));
+            let b = async {
+                futures_lite::future::yield_now().await;
+                Ok("world")
+            };
+            let res = (a, b).race_ok().await;
+            assert!(matches!(res, Ok("world")));
+        });
+    }
 }
@@ -0,0 +1,153 @@
+use super::AggregateError;
+use crate::utils::{iter_pin_mut, MaybeDone, PollVec};
+
+use core::fmt;
+use core::future::Future;
+use core::mem;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use std::boxed::Box;
+use std::vec::Vec;
+
+use pin_project::pin_project;
+
+/// Wait for the first successful future to complete, keeping the errors of
+/// the futures which failed before it.
+///
+/// This `struct` is created by the [`race_ok_with_failures`] method on
+/// [`RaceOk`]. See its documentation for more.
+///
+/// [`race_ok_with_failures`]: super::RaceOk::race_ok_with_failures
+/// [`RaceOk`]: super::RaceOk
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+#[pin_project]
+pub struct RaceOkWithFailures<Fut, T, E>
+where
+    Fut: Future<Output = Result<T, E>>,
+{
+    elems: Pin<Box<[MaybeDone<Fut>]>>,
+    state: PollVec,
+    failures: Vec<(usize, E)>,
+    done: bool,
+}
+
+impl<Fut, T, E> RaceOkWithFailures<Fut, T, E>
+where
+    Fut: Future<Output = Result<T, E>>,
+{
+    pub(crate) fn new(elems: Pin<Box<[MaybeDone<Fut>]>>) -> Self {
+        let len = elems.len();
+        Self {
+            elems,
+            state: PollVec::new(len),
+            failures: Vec::new(),
+            done: false,
+        }
+    }
+}
+
+impl<Fut, T, E> fmt::Debug for RaceOkWithFailures<Fut, T, E>
+where
+    Fut: Future<Output = Result<T, E>> + fmt::Debug,
+    Fut::Output: fmt::Debug,
+    E: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RaceOkWithFailures")
+            .field("elems", &self.elems)
+            .field("failures", &self.failures)
+            .finish()
+    }
+}
+
+impl<Fut, T, E> Future for RaceOkWithFailures<Fut, T, E>
+where
+    Fut: Future<Output = Result<T, E>>,
+{
+    type Output = Result<(T, Vec<(usize, E)>), AggregateError<E>>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        assert!(!*this.done, "Futures must not be polled after completing");
+
+        for (index, mut elem) in iter_pin_mut(this.elems.as_mut()).enumerate() {
+            if this.state[index].is_consumed() || elem.as_mut().poll(cx).is_pending() {
+                continue;
+            }
+            this.state[index].set_consumed();
+            match elem.take().unwrap() {
+                Ok(output) => {
+                    *this.done = true;
+                    return Poll::Ready(Ok((output, mem::take(this.failures))));
+                }
+                Err(err) => this.failures.push((index, err)),
+            }
+        }
+
+        if this.failures.len() == this.elems.len() {
+            *this.done = true;
+            // Match `race_ok`, which reports errors in the order of the futures.
+            let mut failures = mem::take(this.failures);
+            failures.sort_by_key(|(index, _)| *index);
+            let errors = failures.into_iter().map(|(_, err)| err).collect();
+            Poll::Ready(Err(AggregateError::new(errors)))
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::future::RaceOk as _;
+    use futures_lite::future::{self, block_on, Boxed, FutureExt};
+
+    #[test]
+    fn returns_earlier_failures() {
+        block_on(async {
+            let futures: Vec<Boxed<Result<u8, &str>>> = vec![
+                async {
+                    future::yield_now().await;
+                    Ok(1)
+                }
+                .boxed(),
+                async {
+                    future::yield_now().await;
+                    Err("b")
+                }
+                .boxed(),
+                future::ready(Err("c")).boxed(),
+            ];
+            let (output, failures) = futures
+                .race_ok()
+                .race_ok_with_failures()
+                .await
+                .ok()
+                .unwrap();
+            assert_eq!(output, 1);
+            // Only `c` had failed by the time `a` succeeded.
+            assert_eq!(failures, vec![(2, "c")]);
+        })
+    }
+
+    #[test]
+    fn all_fail() {
+        block_on(async {
+            let futures: Vec<Boxed<Result<u8, &str>>> = vec![
+                async {
+                    future::yield_now().await;
+                    Err("a")
+                }
+                .boxed(),
+                future::ready(Err("b")).boxed(),
+            ];
+            let errors = futures
+                .race_ok()
+                .race_ok_with_failures()
+                .await
+                .err()
+                .unwrap();
+            assert_eq!(*errors, vec!["a", "b"]);
+        })
+    }
+}
@@ -11,8 +11,10 @@ use std::boxed::Box;
 use std::vec::Vec;
 
 pub use error::AggregateError;
+pub use failures::RaceOkWithFailures;
 
 mod error;
+mod failures;
 
 /// Wait for the first successful future to complete.
 ///
@@ -29,6 +31,38 @@ where
     elems: Pin<Box<[MaybeDone<Fut>]>>,
 }
 
+impl<Fut, T, E> RaceOk<Fut, T, E>
+where
+    Fut: Future<Output = Result<T, E>>,
+{
+    /// Wait for the first successful future to complete, and also return the
+    /// errors of the futures which failed before it did.
+    ///
+    /// Each error is paired with the index of the future which produced it,
+    /// and the errors are listed in the order they occurred. Futures which
+    /// were still running when the winner completed aren't included. If every
+    /// future fails, this resolves to an [`AggregateError`] like
+    /// [`race_ok`][crate::future::RaceOk::race_ok] does.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_concurrency::prelude::*;
+    /// use futures_lite::future::block_on;
+    /// use std::future::ready;
+    ///
+    /// block_on(async {
+    ///     let futures = vec![ready(Err("down")), ready(Ok(2))];
+    ///     let result = futures.race_ok().race_ok_with_failures().await;
+    ///     assert!(matches!(result, Ok((2, failures)) if failures == [(0, "down")]));
+    /// })
+    /// ```
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub fn race_ok_with_failures(self) -> RaceOkWithFailures<Fut, T, E> {
+        RaceOkWithFailures::new(self.elems)
+    }
+}
+
 impl<Fut, T, E> fmt::Debug for RaceOk<Fut, T, E>
 where
     Fut: Future<Output = Result<T, E>> + fmt::Debug,
@@ -49,15 +83,23 @@ where
 
     fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         let mut all_done = true;
+        let mut winner = None;
 
         for mut elem in iter_pin_mut(self.elems.as_mut()) {
             if elem.as_mut().poll(cx).is_pending() {
                 all_done = false
             } else if let Some(Ok(_)) = elem.as_ref().output() {
-                return Poll::Ready(Ok(elem.take().unwrap().unwrap()));
+                winner = elem.take().unwrap().ok();
+                break;
             }
         }
 
+        if let Some(output) = winner {
+            // Drop the futures which lost the race straight away.
+            self.elems = Box::pin([]);
+            return Poll::Ready(Ok(output));
+        }
+
         if all_done {
             let mut elems = mem::replace(&mut self.elems, Box::pin([]));
             let result: Vec<E> = iter_pin_mut(elems.as_mut())
@@ -97,6 +139,7 @@ mod test {
     use super::*;
     use std::future;
     use std::io::{Error, ErrorKind};
+    use std::rc::Rc;
 
     #[test]
     fn all_ok() {
@@ -135,4 +178,28 @@ mod test {
             assert_eq!(errs[1].to_string(), "oh no");
         });
     }
+
+    #[test]
+    fn drops_losers_on_win() {
+        futures_lite::future::block_on(async {
+            type Fut = Pin<Box<dyn Future<Output = Result<&'static str, Error>>>>;
+            let guard = Rc::new(());
+            let err: Fut = Box::pin(future::ready(Err(ErrorKind::NotFound.into())));
+            let ok: Fut = Box::pin(async {
+                futures_lite::future::yield_now().await;
+                Ok("hello")
+            });
+            let loser: Fut = {
+                let guard = guard.clone();
+                Box::pin(async move {
+                    let _guard = guard;
+                    future::pending().await
+                })
+            };
+            let mut race = vec![err, ok, loser].race_ok();
+            assert_eq!((&mut race).await.unwrap(), "hello");
+            // The race is still around, but the pending future is gone.
+            assert_eq!(Rc::strong_count(&guard), 1);
+        });
+    }
 }
@@ -0,0 +1,201 @@
+use core::fmt;
+use core::future::{Future, IntoFuture};
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use core::time::Duration;
+use std::error::Error;
+
+use pin_project::pin_project;
+
+/// A source of sleep futures.
+///
+/// Time-based operations take a `Timer` rather than depending on a specific
+/// runtime, so any executor's timer can be plugged in. This trait is
+/// implemented for all closures which take a [`Duration`] and return a future.
+///
+/// # Examples
+///
+/// ```
+/// use futures_concurrency::future::Timer;
+/// use std::time::Duration;
+///
+/// fn assert_timer(_: impl Timer) {}
+/// assert_timer(|dur: Duration| async_std::task::sleep(dur));
+/// ```
+pub trait Timer {
+    /// The future returned by [`sleep`][Timer::sleep].
+    type Sleep: Future;
+
+    /// Create a future which completes once `dur` has elapsed.
+    fn sleep(&mut self, dur: Duration) -> Self::Sleep;
+}
+
+impl<F, Fut> Timer for F
+where
+    F: FnMut(Duration) -> Fut,
+    Fut: Future,
+{
+    type Sleep = Fut;
+
+    fn sleep(&mut self, dur: Duration) -> Self::Sleep {
+        self(dur)
+    }
+}
+
+/// An error returned when a future times out.
+///
+/// This is returned by the [`Timeout`] future when its deadline elapses before
+/// the wrapped future completes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Elapsed(());
+
+impl Elapsed {
+    pub(crate) fn new() -> Self {
+        Self(())
+    }
+}
+
+impl fmt::Display for Elapsed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "deadline has elapsed")
+    }
+}
+
+impl Error for Elapsed {}
+
+/// Wait for a future to complete, or for a deadline to elapse.
+///
+/// This `struct` is created by the [`timeout`] function. See its
+/// documentation for more.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+#[pin_project]
+pub struct Timeout<Fut, D> {
+    #[pin]
+    future: Fut,
+    #[pin]
+    deadline: D,
+    done: bool,
+}
+
+impl<Fut, D> fmt::Debug for Timeout<Fut, D>
+where
+    Fut: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Timeout").field(&self.future).finish()
+    }
+}
+
+impl<Fut, D> Future for Timeout<Fut, D>
+where
+    Fut: Future,
+    D: Future,
+{
+    type Output = Result<Fut::Output, Elapsed>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        assert!(!*this.done, "Futures must not be polled after completing");
+
+        // Always poll the future first, so that an output which is ready at
+        // the same time as the deadline isn't discarded.
+        if let Poll::Ready(output) = this.future.poll(cx) {
+            *this.done = true;
+            return Poll::Ready(Ok(output));
+        }
+
+        if this.deadline.poll(cx).is_ready() {
+            *this.done = true;
+            return Poll::Ready(Err(Elapsed(())));
+        }
+
+        Poll::Pending
+    }
+}
+
+/// Wait for a future to complete, or for a deadline to elapse.
+///
+/// The future and a sleep future created by `timer` are polled concurrently.
+/// If the future completes first its output is returned, otherwise the future
+/// is no longer polled and [`Elapsed`] is returned.
+///
+/// # Examples
+///
+/// ```
+/// use futures_concurrency::future::timeout;
+/// use futures_lite::future::{block_on, pending};
+/// use std::time::Duration;
+///
+/// block_on(async {
+///     let timer = |dur| async_std::task::sleep(dur);
+///     let res = timeout(Duration::from_millis(10), pending::<()>(), timer).await;
+///     assert!(res.is_err());
+/// })
+/// ```
+pub fn timeout<Fut, T>(
+    dur: Duration,
+    future: Fut,
+    mut timer: T,
+) -> Timeout<Fut::IntoFuture, T::Sleep>
+where
+    Fut: IntoFuture,
+    T: Timer,
+{
+    Timeout {
+        future: future.into_future(),
+        deadline: timer.sleep(dur),
+        done: false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::future;
+
+    /// A timer whose sleep futures complete after being polled `polls` times.
+    fn countdown(polls: usize) -> impl Timer {
+        move |_| {
+            let mut remaining = polls;
+            futures_lite::future::poll_fn(move |cx| {
+                if remaining == 0 {
+                    Poll::Ready(())
+                } else {
+                    remaining -= 1;
+                    cx.waker().wake_by_ref();
+                    Poll::Pending
+                }
+            })
+        }
+    }
+
+    #[test]
+    fn completes_before_deadline() {
+        futures_lite::future::block_on(async {
+            let timer = |_| future::pending::<()>();
+            let res = timeout(Duration::from_secs(1), future::ready(12), timer).await;
+            assert_eq!(res, Ok(12));
+        });
+    }
+
+    #[test]
+    fn deadline_elapses() {
+        futures_lite::future::block_on(async {
+            let res = timeout(
+                Duration::from_secs(1),
+                future::pending::<u8>(),
+                countdown(3),
+            )
+            .await;
+            assert_eq!(res, Err(Elapsed(())));
+        });
+    }
+
+    #[test]
+    fn ready_future_wins_tie() {
+        futures_lite::future::block_on(async {
+            let res = timeout(Duration::ZERO, future::ready(12), countdown(0)).await;
+            assert_eq!(res, Ok(12));
+        });
+    }
+}
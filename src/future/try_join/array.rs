@@ -1,8 +1,9 @@
 use super::TryJoin as TryJoinTrait;
-use crate::utils::MaybeDone;
+use super::{Residual, Try, TryMaybeDone, TryOutput, TryResidual};
 
 use core::fmt;
 use core::future::{Future, IntoFuture};
+use core::ops::ControlFlow;
 use core::pin::Pin;
 use core::task::{Context, Poll};
 
@@ -17,32 +18,32 @@ use pin_project::pin_project;
 /// [`TryJoin`]: crate::future::TryJoin
 #[must_use = "futures do nothing unless you `.await` or poll them"]
 #[pin_project]
-pub struct TryJoin<Fut, T, E, const N: usize>
+pub struct TryJoin<Fut, const N: usize>
 where
-    T: fmt::Debug,
-    Fut: Future<Output = Result<T, E>>,
+    Fut: Future,
+    Fut::Output: Try,
 {
-    elems: [MaybeDone<Fut>; N],
+    elems: [TryMaybeDone<Fut>; N],
 }
 
-impl<Fut, T, E, const N: usize> fmt::Debug for TryJoin<Fut, T, E, N>
+impl<Fut, const N: usize> fmt::Debug for TryJoin<Fut, N>
 where
-    Fut: Future<Output = Result<T, E>> + fmt::Debug,
-    Fut::Output: fmt::Debug,
-    T: fmt::Debug,
+    Fut: Future + fmt::Debug,
+    Fut::Output: Try,
+    TryOutput<Fut>: fmt::Debug,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_list().entries(self.elems.iter()).finish()
     }
 }
 
-impl<Fut, T, E, const N: usize> Future for TryJoin<Fut, T, E, N>
+impl<Fut, const N: usize> Future for TryJoin<Fut, N>
 where
-    T: fmt::Debug,
-    Fut: Future<Output = Result<T, E>>,
-    E: fmt::Debug,
+    Fut: Future,
+    Fut::Output: Try,
+    TryResidual<Fut>: Residual<[TryOutput<Fut>; N]>,
 {
-    type Output = Result<[T; N], E>;
+    type Output = <TryResidual<Fut> as Residual<[TryOutput<Fut>; N]>>::TryType;
 
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         let mut all_done = true;
@@ -51,11 +52,13 @@ where
 
         for elem in this.elems.iter_mut() {
             // SAFETY: we don't ever move the pinned container here; we only pin project
-            let mut elem = unsafe { Pin::new_unchecked(elem) };
-            if elem.as_mut().poll(cx).is_pending() {
-                all_done = false
-            } else if let Some(Err(_)) = elem.as_ref().output() {
-                return Poll::Ready(Err(elem.take().unwrap().unwrap_err()));
+            let elem = unsafe { Pin::new_unchecked(elem) };
+            match elem.poll_try(cx) {
+                Poll::Pending => all_done = false,
+                Poll::Ready(ControlFlow::Break(residual)) => {
+                    return Poll::Ready(residual.into_try_type());
+                }
+                Poll::Ready(ControlFlow::Continue(())) => {}
             }
         }
 
@@ -71,30 +74,30 @@ where
             #[allow(clippy::needless_range_loop)]
             for (i, el) in this.elems.iter_mut().enumerate() {
                 // SAFETY: we don't ever move the pinned container here; we only pin project
-                let el = unsafe { Pin::new_unchecked(el) }.take().unwrap().unwrap();
+                let el = unsafe { Pin::new_unchecked(el) }.take().unwrap();
                 out[i] = MaybeUninit::new(el);
             }
-            let result = unsafe { out.as_ptr().cast::<[T; N]>().read() };
-            Poll::Ready(Ok(result))
+            let result = unsafe { out.as_ptr().cast::<[TryOutput<Fut>; N]>().read() };
+            Poll::Ready(<Self::Output as Try>::from_output(result))
         } else {
             Poll::Pending
         }
     }
 }
 
-impl<Fut, T, E, const N: usize> TryJoinTrait for [Fut; N]
+impl<Fut, const N: usize> TryJoinTrait for [Fut; N]
 where
-    T: std::fmt::Debug,
-    Fut: IntoFuture<Output = Result<T, E>>,
-    E: fmt::Debug,
+    Fut: IntoFuture,
+    Fut::Output: Try,
+    TryResidual<Fut::IntoFuture>: Residual<[TryOutput<Fut::IntoFuture>; N]>,
 {
-    type Output = [T; N];
-    type Error = E;
-    type Future = TryJoin<Fut::IntoFuture, T, E, N>;
+    type Output = [TryOutput<Fut::IntoFuture>; N];
+    type Residual = TryResidual<Fut::IntoFuture>;
+    type Future = TryJoin<Fut::IntoFuture, N>;
 
     fn try_join(self) -> Self::Future {
         TryJoin {
-            elems: self.map(|fut| MaybeDone::new(fut.into_future())),
+            elems: self.map(|fut| TryMaybeDone::new(fut.into_future())),
         }
     }
 }
@@ -125,4 +128,39 @@ mod test {
             assert_eq!(res.unwrap_err().to_string(), String::from("oh no"));
         });
     }
+
+    #[test]
+    fn all_some() {
+        futures_lite::future::block_on(async {
+            let res = [future::ready(Some(1)), future::ready(Some(2))]
+                .try_join()
+                .await;
+            assert_eq!(res, Some([1, 2]));
+        })
+    }
+
+    #[test]
+    fn one_none() {
+        futures_lite::future::block_on(async {
+            let res = [future::ready(Some(1)), future::ready(None)]
+                .try_join()
+                .await;
+            assert_eq!(res, None);
+        })
+    }
+
+    #[test]
+    fn control_flow_break() {
+        use core::ops::ControlFlow;
+
+        futures_lite::future::block_on(async {
+            let res = [
+                future::ready(ControlFlow::Continue(1)),
+                future::ready(ControlFlow::Break("stop")),
+            ]
+            .try_join()
+            .await;
+            assert_eq!(res, ControlFlow::Break("stop"));
+        })
+    }
 }
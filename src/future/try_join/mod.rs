@@ -1,8 +1,12 @@
 use core::future::Future;
 
 pub(crate) mod array;
+mod try_trait;
 pub(crate) mod vec;
 
+pub use try_trait::{Residual, Try};
+pub(crate) use try_trait::{TryMaybeDone, TryOutput, TryResidual};
+
 /// Wait for all futures to complete successfully, or abort early on error.
 ///
 /// In the case a future errors, all other futures will be cancelled. If
@@ -10,15 +14,36 @@ pub(crate) mod vec;
 ///
 /// If you want to keep partial data in the case of failure, see the `merge`
 /// operation.
+///
+/// What counts as an error is decided by the [`Try`] trait: futures may
+/// output a `Result`, which aborts on the first `Err`, or an `Option`, which
+/// aborts on the first `None`. The combined output is wrapped the same way.
+///
+/// # Examples
+///
+/// ```
+/// use futures_concurrency::prelude::*;
+/// use futures_lite::future::block_on;
+/// use std::future::ready;
+///
+/// block_on(async {
+///     let res = [ready(Ok::<u8, &str>(1)), ready(Ok(2))].try_join().await;
+///     assert_eq!(res, Ok([1, 2]));
+///
+///     let res = [ready(Some(1)), ready(None)].try_join().await;
+///     assert_eq!(res, None);
+/// })
+/// ```
 pub trait TryJoin {
     /// The resulting output type.
     type Output;
 
-    /// The resulting error type.
-    type Error;
+    /// The value which ended the join early, such as `Result<Infallible, E>`
+    /// or `Option<Infallible>`.
+    type Residual: Residual<Self::Output>;
 
     /// Which kind of future are we turning this into?
-    type Future: Future<Output = Result<Self::Output, Self::Error>>;
+    type Future: Future<Output = <Self::Residual as Residual<Self::Output>>::TryType>;
 
     /// Waits for multiple futures to complete, either returning when all
     /// futures complete successfully, or return early when any future completes
@@ -0,0 +1,212 @@
+use core::convert::Infallible;
+use core::fmt;
+use core::future::Future;
+use core::ops::ControlFlow;
+use core::pin::Pin;
+use core::task::{ready, Context, Poll};
+
+/// A type which can short-circuit, like [`Result`] or [`Option`].
+///
+/// This is a stable stand-in for the unstable `core::ops::Try` trait, and is
+/// used by [`TryJoin`][super::TryJoin] to decide when to stop early. It's
+/// implemented for `Result`, `Option` and `ControlFlow`.
+pub trait Try {
+    /// The value produced when this doesn't short-circuit.
+    type Output;
+
+    /// The value carried over when this short-circuits.
+    type Residual;
+
+    /// Construct `Self` from its output.
+    fn from_output(output: Self::Output) -> Self;
+
+    /// Decide whether to continue with the output, or short-circuit with
+    /// the residual.
+    fn branch(self) -> ControlFlow<Self::Residual, Self::Output>;
+}
+
+/// Allows a residual to be re-wrapped with a different output type.
+///
+/// The residual of a `Result<T, E>` is `Result<Infallible, E>`, which turns
+/// back into a `Result<O, E>`. Likewise the residual of an `Option<T>` is
+/// `Option<Infallible>`, which turns back into an `Option<O>`.
+pub trait Residual<O> {
+    /// The type with output `O` and this residual.
+    type TryType: Try<Output = O>;
+
+    /// Construct the short-circuited value from this residual.
+    fn into_try_type(self) -> Self::TryType;
+}
+
+impl<T, E> Try for Result<T, E> {
+    type Output = T;
+    type Residual = Result<Infallible, E>;
+
+    fn from_output(output: T) -> Self {
+        Ok(output)
+    }
+
+    fn branch(self) -> ControlFlow<Self::Residual, T> {
+        match self {
+            Ok(output) => ControlFlow::Continue(output),
+            Err(err) => ControlFlow::Break(Err(err)),
+        }
+    }
+}
+
+impl<O, E> Residual<O> for Result<Infallible, E> {
+    type TryType = Result<O, E>;
+
+    fn into_try_type(self) -> Self::TryType {
+        match self {
+            Ok(never) => match never {},
+            Err(err) => Err(err),
+        }
+    }
+}
+
+impl<T> Try for Option<T> {
+    type Output = T;
+    type Residual = Option<Infallible>;
+
+    fn from_output(output: T) -> Self {
+        Some(output)
+    }
+
+    fn branch(self) -> ControlFlow<Self::Residual, T> {
+        match self {
+            Some(output) => ControlFlow::Continue(output),
+            None => ControlFlow::Break(None),
+        }
+    }
+}
+
+impl<O> Residual<O> for Option<Infallible> {
+    type TryType = Option<O>;
+
+    fn into_try_type(self) -> Self::TryType {
+        None
+    }
+}
+
+impl<B, C> Try for ControlFlow<B, C> {
+    type Output = C;
+    type Residual = ControlFlow<B, Infallible>;
+
+    fn from_output(output: C) -> Self {
+        ControlFlow::Continue(output)
+    }
+
+    fn branch(self) -> ControlFlow<Self::Residual, C> {
+        match self {
+            ControlFlow::Continue(output) => ControlFlow::Continue(output),
+            ControlFlow::Break(residual) => ControlFlow::Break(ControlFlow::Break(residual)),
+        }
+    }
+}
+
+impl<B, O> Residual<O> for ControlFlow<B, Infallible> {
+    type TryType = ControlFlow<B, O>;
+
+    fn into_try_type(self) -> Self::TryType {
+        match self {
+            ControlFlow::Continue(never) => match never {},
+            ControlFlow::Break(residual) => ControlFlow::Break(residual),
+        }
+    }
+}
+
+/// The output of a short-circuiting future.
+pub(crate) type TryOutput<Fut> = <<Fut as Future>::Output as Try>::Output;
+
+/// The residual of a short-circuiting future.
+pub(crate) type TryResidual<Fut> = <<Fut as Future>::Output as Try>::Residual;
+
+/// A short-circuiting future that may have completed.
+///
+/// This is like [`MaybeDone`][crate::utils::MaybeDone], except that the output
+/// is branched on as soon as the future completes. Only outputs which don't
+/// short-circuit are stored.
+pub(crate) enum TryMaybeDone<Fut>
+where
+    Fut: Future,
+    Fut::Output: Try,
+{
+    /// A not-yet-completed future
+    Future(Fut),
+
+    /// The output of the completed future
+    Done(TryOutput<Fut>),
+
+    /// The empty variant after the output has been taken, or the future
+    /// short-circuited.
+    Gone,
+}
+
+impl<Fut> fmt::Debug for TryMaybeDone<Fut>
+where
+    Fut: Future + fmt::Debug,
+    Fut::Output: Try,
+    TryOutput<Fut>: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Future(fut) => f.debug_tuple("Future").field(fut).finish(),
+            Self::Done(output) => f.debug_tuple("Done").field(output).finish(),
+            Self::Gone => write!(f, "Gone"),
+        }
+    }
+}
+
+impl<Fut> TryMaybeDone<Fut>
+where
+    Fut: Future,
+    Fut::Output: Try,
+{
+    /// Create a new instance of `TryMaybeDone`.
+    pub(crate) fn new(future: Fut) -> Self {
+        Self::Future(future)
+    }
+
+    /// Drive the future, returning the residual if it short-circuited.
+    pub(crate) fn poll_try(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<ControlFlow<TryResidual<Fut>>> {
+        // SAFETY: the future is never moved out of, and is dropped in place
+        // by `set` once it has completed.
+        let output = unsafe {
+            match self.as_mut().get_unchecked_mut() {
+                Self::Future(fut) => ready!(Pin::new_unchecked(fut).poll(cx)),
+                Self::Done(_) => return Poll::Ready(ControlFlow::Continue(())),
+                Self::Gone => panic!("TryMaybeDone polled after value taken"),
+            }
+        };
+        match output.branch() {
+            ControlFlow::Continue(output) => {
+                self.set(Self::Done(output));
+                Poll::Ready(ControlFlow::Continue(()))
+            }
+            ControlFlow::Break(residual) => {
+                self.set(Self::Gone);
+                Poll::Ready(ControlFlow::Break(residual))
+            }
+        }
+    }
+
+    /// Attempt to take the output without driving the future towards
+    /// completion.
+    pub(crate) fn take(self: Pin<&mut Self>) -> Option<TryOutput<Fut>> {
+        // SAFETY: only the output is moved out, which isn't pinned.
+        let this = unsafe { self.get_unchecked_mut() };
+        match this {
+            Self::Done(_) => {}
+            Self::Future(_) | Self::Gone => return None,
+        };
+        if let Self::Done(output) = core::mem::replace(this, Self::Gone) {
+            Some(output)
+        } else {
+            unreachable!()
+        }
+    }
+}
@@ -1,10 +1,11 @@
 use super::TryJoin as TryJoinTrait;
+use super::{Residual, Try, TryMaybeDone, TryOutput, TryResidual};
 use crate::utils::iter_pin_mut;
-use crate::utils::MaybeDone;
 
 use core::fmt;
 use core::future::{Future, IntoFuture};
 use core::mem;
+use core::ops::ControlFlow;
 use core::pin::Pin;
 use core::task::{Context, Poll};
 use std::boxed::Box;
@@ -18,38 +19,82 @@ use std::vec::Vec;
 /// [`try_join`]: crate::future::TryJoin::try_join
 /// [`TryJoin`]: crate::future::TryJoin
 #[must_use = "futures do nothing unless you `.await` or poll them"]
-pub struct TryJoin<Fut, T, E>
+pub struct TryJoin<Fut>
 where
-    Fut: Future<Output = Result<T, E>>,
+    Fut: Future,
+    Fut::Output: Try,
 {
-    elems: Pin<Box<[MaybeDone<Fut>]>>,
+    elems: Pin<Box<[TryMaybeDone<Fut>]>>,
 }
 
-impl<Fut, T, E> fmt::Debug for TryJoin<Fut, T, E>
+/// Wait for all futures in an iterator to complete successfully, or abort
+/// early on error.
+///
+/// This is a free-function version of [`TryJoin::try_join`] for `Vec`,
+/// matching the signature of `futures::future::try_join_all`. The outputs are
+/// returned in the same order as the futures were yielded by the iterator.
+///
+/// [`TryJoin::try_join`]: crate::future::TryJoin::try_join
+///
+/// # Examples
+///
+/// ```
+/// use futures_concurrency::future::try_join_all;
+/// use futures_lite::future::block_on;
+/// use std::future::ready;
+///
+/// block_on(async {
+///     let futures = vec![ready(Ok::<u32, u32>(1)), ready(Ok(2)), ready(Ok(3))];
+///     assert_eq!(try_join_all(futures).await, Ok(vec![1, 2, 3]));
+///
+///     let futures = vec![ready(Ok::<u32, u32>(1)), ready(Err(2)), ready(Ok(3))];
+///     assert_eq!(try_join_all(futures).await, Err(2));
+/// })
+/// ```
+pub fn try_join_all<I>(iter: I) -> TryJoin<<I::Item as IntoFuture>::IntoFuture>
 where
-    Fut: Future<Output = Result<T, E>> + fmt::Debug,
-    Fut::Output: fmt::Debug,
+    I: IntoIterator,
+    I::Item: IntoFuture,
+    <I::Item as IntoFuture>::Output: Try,
+{
+    let elems: Box<[_]> = iter
+        .into_iter()
+        .map(|fut| TryMaybeDone::new(fut.into_future()))
+        .collect();
+    TryJoin {
+        elems: elems.into(),
+    }
+}
+
+impl<Fut> fmt::Debug for TryJoin<Fut>
+where
+    Fut: Future + fmt::Debug,
+    Fut::Output: Try,
+    TryOutput<Fut>: fmt::Debug,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_list().entries(self.elems.iter()).finish()
     }
 }
 
-impl<Fut, T, E> Future for TryJoin<Fut, T, E>
+impl<Fut> Future for TryJoin<Fut>
 where
-    T: std::fmt::Debug,
-    Fut: Future<Output = Result<T, E>>,
+    Fut: Future,
+    Fut::Output: Try,
+    TryResidual<Fut>: Residual<Vec<TryOutput<Fut>>>,
 {
-    type Output = Result<Vec<T>, E>;
+    type Output = <TryResidual<Fut> as Residual<Vec<TryOutput<Fut>>>>::TryType;
 
     fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         let mut all_done = true;
 
-        for mut elem in iter_pin_mut(self.elems.as_mut()) {
-            if elem.as_mut().poll(cx).is_pending() {
-                all_done = false
-            } else if let Some(Err(_)) = elem.as_ref().output() {
-                return Poll::Ready(Err(elem.take().unwrap().unwrap_err()));
+        for elem in iter_pin_mut(self.elems.as_mut()) {
+            match elem.poll_try(cx) {
+                Poll::Pending => all_done = false,
+                Poll::Ready(ControlFlow::Break(residual)) => {
+                    return Poll::Ready(residual.into_try_type());
+                }
+                Poll::Ready(ControlFlow::Continue(())) => {}
             }
         }
 
@@ -58,33 +103,202 @@ where
             let result = iter_pin_mut(elems.as_mut())
                 .map(|e| e.take().unwrap())
                 .collect();
-            Poll::Ready(result)
+            Poll::Ready(<Self::Output as Try>::from_output(result))
         } else {
             Poll::Pending
         }
     }
 }
 
-impl<Fut, T, E> TryJoinTrait for Vec<Fut>
+impl<Fut, T, E> TryJoin<Fut>
 where
-    T: std::fmt::Debug,
-    Fut: IntoFuture<Output = Result<T, E>>,
+    Fut: Future<Output = Result<T, E>>,
 {
-    type Output = Vec<T>;
-    type Error = E;
-    type Future = TryJoin<Fut::IntoFuture, T, E>;
+    /// Like awaiting the join directly, but on error also report how many
+    /// futures had already completed successfully.
+    ///
+    /// The count is taken when the error is observed. The outputs of the
+    /// futures which succeeded are dropped along with the remaining futures,
+    /// so this is only meant for diagnostics, such as reporting that a batch
+    /// "failed after 7/10 succeeded".
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_concurrency::prelude::*;
+    /// use futures_lite::future::block_on;
+    /// use std::future::{pending, ready, Future};
+    /// use std::pin::Pin;
+    ///
+    /// block_on(async {
+    ///     let futures: Vec<Pin<Box<dyn Future<Output = Result<u8, &str>>>>> = vec![
+    ///         Box::pin(ready(Ok(1))),
+    ///         Box::pin(ready(Ok(2))),
+    ///         Box::pin(ready(Err("oh no"))),
+    ///         Box::pin(pending()),
+    ///     ];
+    ///     let res = futures.try_join().try_join_counted().await;
+    ///     assert_eq!(res, Err(("oh no", 2)));
+    /// })
+    /// ```
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub fn try_join_counted(self) -> TryJoinCounted<Fut> {
+        TryJoinCounted {
+            inner: self.try_join_collect(),
+        }
+    }
 
-    fn try_join(self) -> Self::Future {
-        let elems: Box<[_]> = self
-            .into_iter()
-            .map(|fut| MaybeDone::new(fut.into_future()))
-            .collect();
-        TryJoin {
-            elems: elems.into(),
+    /// Like awaiting the join directly, but on error also return the outputs
+    /// of the futures which had already completed successfully.
+    ///
+    /// The outputs are returned in the order of their futures. The future
+    /// which failed, and any which hadn't completed yet, leave a `None` in
+    /// their slot. The futures which hadn't completed are dropped as soon as
+    /// the error is observed. If the join itself is dropped early, the
+    /// outputs it was holding are dropped along with it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_concurrency::prelude::*;
+    /// use futures_lite::future::block_on;
+    /// use std::future::{pending, ready, Future};
+    /// use std::pin::Pin;
+    ///
+    /// block_on(async {
+    ///     let futures: Vec<Pin<Box<dyn Future<Output = Result<u8, &str>>>>> = vec![
+    ///         Box::pin(ready(Ok(1))),
+    ///         Box::pin(ready(Err("oh no"))),
+    ///         Box::pin(pending()),
+    ///     ];
+    ///     let res = futures.try_join().try_join_collect().await;
+    ///     assert_eq!(res, Err(("oh no", vec![Some(1), None, None])));
+    /// })
+    /// ```
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub fn try_join_collect(self) -> TryJoinCollect<Fut> {
+        TryJoinCollect { elems: self.elems }
+    }
+}
+
+/// Wait for all futures to complete successfully, or abort early on error
+/// while reporting how many succeeded.
+///
+/// This `struct` is created by the [`try_join_counted`] method on
+/// [`TryJoin`]. See its documentation for more.
+///
+/// [`try_join_counted`]: TryJoin::try_join_counted
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct TryJoinCounted<Fut>
+where
+    Fut: Future,
+    Fut::Output: Try,
+{
+    /// The outputs collected on error are only counted.
+    inner: TryJoinCollect<Fut>,
+}
+
+impl<Fut> fmt::Debug for TryJoinCounted<Fut>
+where
+    Fut: Future + fmt::Debug,
+    Fut::Output: Try,
+    TryOutput<Fut>: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
+impl<Fut, T, E> Future for TryJoinCounted<Fut>
+where
+    Fut: Future<Output = Result<T, E>>,
+{
+    type Output = Result<Vec<T>, (E, usize)>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.inner)
+            .poll(cx)
+            .map(|res| res.map_err(|(err, outputs)| (err, outputs.iter().flatten().count())))
+    }
+}
+
+/// Wait for all futures to complete successfully, or abort early on error
+/// while returning the outputs collected so far.
+///
+/// This `struct` is created by the [`try_join_collect`] method on
+/// [`TryJoin`]. See its documentation for more.
+///
+/// [`try_join_collect`]: TryJoin::try_join_collect
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct TryJoinCollect<Fut>
+where
+    Fut: Future,
+    Fut::Output: Try,
+{
+    elems: Pin<Box<[TryMaybeDone<Fut>]>>,
+}
+
+impl<Fut> fmt::Debug for TryJoinCollect<Fut>
+where
+    Fut: Future + fmt::Debug,
+    Fut::Output: Try,
+    TryOutput<Fut>: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.elems.iter()).finish()
+    }
+}
+
+impl<Fut, T, E> Future for TryJoinCollect<Fut>
+where
+    Fut: Future<Output = Result<T, E>>,
+{
+    type Output = Result<Vec<T>, (E, Vec<Option<T>>)>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut all_done = true;
+        let mut error = None;
+
+        for elem in iter_pin_mut(self.elems.as_mut()) {
+            match elem.poll_try(cx) {
+                Poll::Pending => all_done = false,
+                Poll::Ready(ControlFlow::Break(Err(err))) => {
+                    error = Some(err);
+                    break;
+                }
+                Poll::Ready(ControlFlow::Break(Ok(never))) => match never {},
+                Poll::Ready(ControlFlow::Continue(())) => {}
+            }
+        }
+
+        if error.is_none() && !all_done {
+            return Poll::Pending;
+        }
+
+        let mut elems = mem::replace(&mut self.elems, Box::pin([]));
+        let outputs = iter_pin_mut(elems.as_mut()).map(|e| e.take());
+        match error {
+            Some(err) => Poll::Ready(Err((err, outputs.collect()))),
+            None => Poll::Ready(Ok(outputs.map(Option::unwrap).collect())),
         }
     }
 }
 
+impl<Fut> TryJoinTrait for Vec<Fut>
+where
+    Fut: IntoFuture,
+    Fut::Output: Try,
+    TryResidual<Fut::IntoFuture>: Residual<Vec<TryOutput<Fut::IntoFuture>>>,
+{
+    type Output = Vec<TryOutput<Fut::IntoFuture>>;
+    type Residual = TryResidual<Fut::IntoFuture>;
+    type Future = TryJoin<Fut::IntoFuture>;
+
+    fn try_join(self) -> Self::Future {
+        try_join_all(self)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -111,4 +325,92 @@ mod test {
             assert_eq!(res.unwrap_err().to_string(), String::from("oh no"));
         });
     }
+
+    #[test]
+    fn try_join_all_free_fn() {
+        futures_lite::future::block_on(async {
+            let futures = (1..=3).map(|i| future::ready(Ok::<_, Error>(i)));
+            assert_eq!(super::try_join_all(futures).await.unwrap(), vec![1, 2, 3]);
+
+            let futures = vec![
+                future::ready(Ok(1)),
+                future::ready(Err(Error::new(ErrorKind::InvalidData, "oh no"))),
+                future::ready(Ok(3)),
+            ];
+            let res = super::try_join_all(futures).await;
+            assert_eq!(res.unwrap_err().to_string(), String::from("oh no"));
+        });
+    }
+
+    #[test]
+    fn counted() {
+        futures_lite::future::block_on(async {
+            let futures = vec![future::ready(Ok(1)), future::ready(Ok(2))];
+            let res: Result<_, (&str, _)> = futures.try_join().try_join_counted().await;
+            assert_eq!(res, Ok(vec![1, 2]));
+
+            let futures = vec![
+                future::ready(Ok(1)),
+                future::ready(Err("oh no")),
+                future::ready(Ok(3)),
+            ];
+            let res = futures.try_join().try_join_counted().await;
+            assert_eq!(res, Err(("oh no", 1)));
+        });
+    }
+
+    #[test]
+    fn collect() {
+        futures_lite::future::block_on(async {
+            let futures = vec![future::ready(Ok(1)), future::ready(Ok(2))];
+            let res: Result<_, (&str, _)> = futures.try_join().try_join_collect().await;
+            assert_eq!(res, Ok(vec![1, 2]));
+
+            let futures = vec![
+                future::ready(Ok(1)),
+                future::ready(Err("oh no")),
+                future::ready(Ok(3)),
+            ];
+            let res = futures.try_join().try_join_collect().await;
+            assert_eq!(res, Err(("oh no", vec![Some(1), None, None])));
+        });
+    }
+
+    #[test]
+    fn collect_drops_outputs_when_abandoned() {
+        use futures_lite::future::{pending, poll_once, ready, Boxed, FutureExt};
+        use std::sync::Arc;
+
+        futures_lite::future::block_on(async {
+            let output = Arc::new(());
+            let futures: Vec<Boxed<Result<Arc<()>, ()>>> =
+                vec![ready(Ok(output.clone())).boxed(), pending().boxed()];
+            let mut join = futures.try_join().try_join_collect();
+            assert!(poll_once(&mut join).await.is_none());
+            assert_eq!(Arc::strong_count(&output), 2);
+
+            drop(join);
+            assert_eq!(Arc::strong_count(&output), 1);
+        });
+    }
+
+    #[test]
+    fn all_some() {
+        futures_lite::future::block_on(async {
+            let res = vec![future::ready(Some(1)), future::ready(Some(2))]
+                .try_join()
+                .await;
+            assert_eq!(res, Some(vec![1, 2]));
+        })
+    }
+
+    #[test]
+    fn one_none() {
+        futures_lite::future::block_on(async {
+            let res = vec![future::ready(Some(1)), future::ready(None)]
+                .try_join()
+                .await;
+            assert_eq!(res, None);
+        })
+    }
 }
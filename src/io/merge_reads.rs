@@ -0,0 +1,217 @@
+use crate::utils::{self, Indexer, PollVec, WakerVec};
+
+use core::fmt;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures_core::Stream;
+use futures_io::AsyncRead;
+use std::io;
+
+/// The default maximum number of bytes in a chunk.
+const DEFAULT_CHUNK_SIZE: usize = 8 * 1024;
+
+/// A stream of chunks read from multiple readers.
+///
+/// This `struct` is created by the [`merge_reads`] function. See its
+/// documentation for more.
+#[must_use = "streams do nothing unless you `.await` or poll them"]
+#[pin_project::pin_project]
+pub struct MergeReads<R>
+where
+    R: AsyncRead,
+{
+    #[pin]
+    readers: Vec<R>,
+    indexer: Indexer,
+    complete: usize,
+    wakers: WakerVec,
+    state: PollVec,
+    buf: Vec<u8>,
+}
+
+impl<R> MergeReads<R>
+where
+    R: AsyncRead,
+{
+    /// Set the maximum number of bytes read into a single chunk.
+    ///
+    /// Defaults to 8 KiB.
+    ///
+    /// # Panics
+    ///
+    /// This panics if `size` is `0`.
+    #[must_use = "streams do nothing unless you `.await` or poll them"]
+    pub fn chunk_size(mut self, size: usize) -> Self {
+        assert!(size > 0, "chunk size must be greater than zero");
+        self.buf = vec![0; size];
+        self
+    }
+}
+
+impl<R> fmt::Debug for MergeReads<R>
+where
+    R: AsyncRead + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.readers.iter()).finish()
+    }
+}
+
+impl<R> Stream for MergeReads<R>
+where
+    R: AsyncRead,
+{
+    type Item = (usize, io::Result<Vec<u8>>);
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        if *this.complete == this.readers.len() {
+            return Poll::Ready(None);
+        }
+
+        let mut readiness = this.wakers.readiness().lock().unwrap();
+        readiness.set_waker(cx.waker());
+
+        for index in this.indexer.iter() {
+            if !readiness.any_ready() {
+                // Nothing is ready yet
+                return Poll::Pending;
+            } else if !readiness.clear_ready(index) || this.state[index].is_consumed() {
+                continue;
+            }
+
+            // unlock readiness so we don't deadlock when polling
+            drop(readiness);
+
+            // Obtain the intermediate waker.
+            let mut cx = Context::from_waker(this.wakers.get(index).unwrap());
+
+            let reader = utils::get_pin_mut_from_vec(this.readers.as_mut(), index).unwrap();
+            match reader.poll_read(&mut cx, this.buf) {
+                Poll::Ready(Ok(0)) => {
+                    // The reader has reached EOF.
+                    *this.complete += 1;
+                    this.state[index].set_consumed();
+                    if *this.complete == this.readers.len() {
+                        return Poll::Ready(None);
+                    }
+                }
+                Poll::Ready(Ok(n)) => {
+                    // There may be more data, so read again on the next poll.
+                    this.wakers.readiness().lock().unwrap().set_ready(index);
+                    return Poll::Ready(Some((index, Ok(this.buf[..n].to_vec()))));
+                }
+                Poll::Ready(Err(err)) => {
+                    // A failed reader isn't read from again.
+                    *this.complete += 1;
+                    this.state[index].set_consumed();
+                    return Poll::Ready(Some((index, Err(err))));
+                }
+                Poll::Pending => {}
+            }
+
+            // Lock readiness so we can use it again
+            readiness = this.wakers.readiness().lock().unwrap();
+        }
+
+        Poll::Pending
+    }
+}
+
+/// Read from multiple readers concurrently, yielding chunks of bytes as soon
+/// as any of them has data available.
+///
+/// Each chunk is tagged with the index of the reader it was read from. A
+/// reader is no longer read from once it reaches EOF or returns an error, and
+/// the stream ends once every reader has. Errors are yielded alongside the
+/// index of the reader that failed, so the other readers keep going.
+///
+/// # Examples
+///
+/// ```
+/// use futures_concurrency::io::merge_reads;
+/// use futures_lite::future::block_on;
+/// use futures_lite::io::Cursor;
+/// use futures_lite::stream::StreamExt;
+///
+/// block_on(async {
+///     let a = Cursor::new(b"hello".to_vec());
+///     let b = Cursor::new(b"world".to_vec());
+///
+///     let mut chunks: Vec<_> = merge_reads(vec![a, b])
+///         .map(|(index, chunk)| (index, chunk.unwrap()))
+///         .collect()
+///         .await;
+///     chunks.sort();
+///     assert_eq!(chunks, vec![(0, b"hello".to_vec()), (1, b"world".to_vec())]);
+/// })
+/// ```
+pub fn merge_reads<R>(readers: Vec<R>) -> MergeReads<R>
+where
+    R: AsyncRead,
+{
+    let len = readers.len();
+    MergeReads {
+        wakers: WakerVec::new(len),
+        state: PollVec::new(len),
+        indexer: Indexer::new(len),
+        readers,
+        complete: 0,
+        buf: vec![0; DEFAULT_CHUNK_SIZE],
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use futures_lite::future::block_on;
+    use futures_lite::io::Cursor;
+    use futures_lite::stream::StreamExt;
+
+    #[test]
+    fn chunks_are_tagged_by_source() {
+        block_on(async {
+            let a = Cursor::new(b"abcdef".to_vec());
+            let b = Cursor::new(b"xyz".to_vec());
+            let chunks: Vec<_> = merge_reads(vec![a, b]).chunk_size(2).collect().await;
+
+            let mut sources = [vec![], vec![]];
+            for (index, chunk) in chunks {
+                let chunk = chunk.unwrap();
+                assert!(chunk.len() <= 2);
+                sources[index].extend(chunk);
+            }
+            assert_eq!(sources, [b"abcdef".to_vec(), b"xyz".to_vec()]);
+        })
+    }
+
+    #[test]
+    fn error_ends_only_that_source() {
+        struct Failing;
+
+        impl AsyncRead for Failing {
+            fn poll_read(
+                self: Pin<&mut Self>,
+                _cx: &mut Context<'_>,
+                _buf: &mut [u8],
+            ) -> Poll<io::Result<usize>> {
+                Poll::Ready(Err(io::ErrorKind::BrokenPipe.into()))
+            }
+        }
+
+        block_on(async {
+            let a: Pin<Box<dyn AsyncRead>> = Box::pin(Failing);
+            let b: Pin<Box<dyn AsyncRead>> = Box::pin(Cursor::new(b"ok".to_vec()));
+            let chunks: Vec<_> = merge_reads(vec![a, b]).collect().await;
+
+            assert_eq!(chunks.len(), 2);
+            for (index, chunk) in chunks {
+                match index {
+                    0 => assert_eq!(chunk.unwrap_err().kind(), io::ErrorKind::BrokenPipe),
+                    _ => assert_eq!(chunk.unwrap(), b"ok".to_vec()),
+                }
+            }
+        })
+    }
+}
@@ -0,0 +1,7 @@
+//! Concurrency operations for asynchronous I/O.
+//!
+//! This module is only available when the `futures-io` feature is enabled.
+
+pub use merge_reads::{merge_reads, MergeReads};
+
+mod merge_reads;
@@ -30,6 +30,31 @@
 //! remove the need to think of "merge" as a verb, and would enable treating
 //! sets of futures concurrently.
 //!
+//! # Features
+//!
+//! - `derive`: enables `#[derive(Join)]`, which generates a method joining
+//!   all fields of a struct of futures.
+//! - `futures-io`: enables the `io` module, for combining multiple
+//!   `AsyncRead` sources.
+//! - `permissive-polling`: polling a join after it has completed returns
+//!   `Poll::Pending` instead of panicking. Some executors poll futures again
+//!   after they've completed, and this avoids crashing them.
+//! - `spawn-parallel`: enables `JoinSpawned`, which joins a vec of futures by
+//!   spawning each of them onto an executor so they can run in parallel.
+//! - `test-determinism`: combinators which rotate their starting index for
+//!   fairness always start polling at the first index instead. This makes
+//!   the order in which futures and streams are polled reproducible across
+//!   runs, at the cost of fairness. It's strictly meant for test suites and
+//!   should never be enabled in production code.
+//! - `test-util`: enables the `test_util` module, with a driver which polls a
+//!   future by hand with a counting waker. It's useful to test polling
+//!   behavior precisely without pulling in an executor.
+//! - `timed-join`: enables `JoinTimed`, which joins a vec of futures and
+//!   reports how long each of them took to complete.
+//! - `waker-stats`: enables `Merge::waker_stats` on the vec merge, which
+//!   counts how often its streams woke it and how many of its polls didn't
+//!   yield anything. Without the feature none of this is compiled in.
+//!
 //! # Examples
 //!
 //! Concurrently await multiple heterogenous futures:
@@ -50,22 +75,72 @@
 #![warn(missing_docs, unreachable_pub)]
 #![allow(non_snake_case)]
 
+mod macros;
 mod utils;
 
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::macros::SelectBranch;
+}
+
 /// The futures concurrency prelude.
+///
+/// This brings every extension trait of this crate into scope at once, with
+/// `use futures_concurrency::prelude::*`. The traits are imported anonymously,
+/// so only their methods come into scope, and none of their names clash with
+/// the traits of the same name in `futures` or `futures-lite`. Traits which
+/// are behind a feature flag, like `JoinSpawned`, are
+/// only included when it's enabled.
+///
+/// The prelude can be glob-imported next to the preludes of `futures` and
+/// `futures-lite` without making any method calls ambiguous: the traits for
+/// containers, like [`Join`][future::Join] and [`Merge`][stream::Merge], are
+/// only implemented for tuples, arrays and vectors, and their `StreamExt`
+/// traits have no `merge` method. The known conflicts are:
+///
+/// - [`StreamCombineExt`][stream::StreamCombineExt] isn't included,
+///   since its `chain` and `zip` methods are also provided by those
+///   `StreamExt` traits. Import it on its own where it's used.
+/// - [`MergeExt::merge`][stream::MergeExt::merge] is ambiguous with the
+///   `merge` method of `tokio-stream`'s `StreamExt`. Call it as
+///   `MergeExt::merge(a, b)` when both are in scope.
 pub mod prelude {
     pub use super::future::Join as _;
+    pub use super::future::JoinCancellable as _;
+    pub use super::future::JoinKeyed as _;
+    pub use super::future::JoinProgressive as _;
+    pub use super::future::JoinReduce as _;
+    pub use super::future::JoinSettled as _;
+    #[cfg(feature = "spawn-parallel")]
+    pub use super::future::JoinSpawned as _;
+    pub use super::future::JoinStaggered as _;
+    #[cfg(feature = "timed-join")]
+    pub use super::future::JoinTimed as _;
+    pub use super::future::JoinWithLimit as _;
     pub use super::future::Race as _;
+    pub use super::future::RaceEither as _;
     pub use super::future::RaceOk as _;
     pub use super::future::TryJoin as _;
     pub use super::stream::Chain as _;
+    pub use super::stream::CollectConcurrentExt as _;
+    pub use super::stream::ConcurrentStream as _;
+    pub use super::stream::ConcurrentStreamExt as _;
     pub use super::stream::IntoStream as _;
     pub use super::stream::Merge as _;
+    pub use super::stream::MergeDeficitRoundRobin as _;
+    pub use super::stream::MergeExt as _;
+    pub use super::stream::MergeKeyed as _;
+    pub use super::stream::MergeRoundRobin as _;
+    pub use super::stream::MergeSorted as _;
     pub use super::stream::Zip as _;
 }
 
 pub mod future;
+#[cfg(feature = "futures-io")]
+pub mod io;
 pub mod stream;
+#[cfg(feature = "test-util")]
+pub mod test_util;
 
 /// Helper functions and types for fixed-length arrays.
 pub mod array {
@@ -73,18 +148,117 @@ pub mod array {
     pub use crate::future::race::array::Race;
     pub use crate::future::race_ok::array::{AggregateError, RaceOk};
     pub use crate::future::try_join::array::TryJoin;
-    pub use crate::stream::chain::array::Chain;
+    pub use crate::stream::chain::array::{Chain, ChainTransitions};
     pub use crate::stream::merge::array::Merge;
     pub use crate::stream::zip::array::Zip;
+    pub use crate::utils::LengthError;
+}
+
+/// A dynamically-sized view into a contiguous sequence, `[T]`.
+pub mod slice {
+    pub use crate::future::race::slice::Race;
 }
 
 /// A contiguous growable array type with heap-allocated contents, written `Vec<T>`.
 pub mod vec {
+    pub use crate::future::join::cached::CachedJoin;
+    pub use crate::future::join::keyed::{KeyedBTreeJoin, KeyedHashJoin, KeyedJoin};
+    pub use crate::future::join::limit::JoinLimit;
+    pub use crate::future::join::ordered_drop::{DropOrder, OrderedDrop, OrderedDropJoin};
+    pub use crate::future::join::reduce::ReduceJoin;
+    pub use crate::future::join::results::JoinResults;
+    pub use crate::future::join::settled::SettledJoin;
+    pub use crate::future::join::staggered::{Staggered, StaggeredJoin};
+    #[cfg(feature = "timed-join")]
+    pub use crate::future::join::timed::{Timed, TimedJoin};
     pub use crate::future::join::vec::Join;
-    pub use crate::future::race::vec::Race;
-    pub use crate::future::race_ok::vec::{AggregateError, RaceOk};
-    pub use crate::future::try_join::vec::TryJoin;
-    pub use crate::stream::chain::vec::Chain;
+    pub use crate::future::race::grace::RaceGrace;
+    pub use crate::future::race::racer::{RaceNext, Racer};
+    pub use crate::future::race::vec::{Race, RaceAll, RaceUntil, RaceWith, SelectAll};
+    pub use crate::future::race_ok::vec::{AggregateError, RaceOk, RaceOkWithFailures};
+    pub use crate::future::try_join::vec::{TryJoin, TryJoinCollect, TryJoinCounted};
+    pub use crate::stream::chain::skip_errors::ChainSkipErrors;
+    pub use crate::stream::chain::vec::{Chain, ChainTransitions};
+    pub use crate::stream::merge::by_key::MergeByKey;
+    pub use crate::stream::merge::catch_unwind::CatchUnwind;
+    pub use crate::stream::merge::circuit_break::CircuitBreaker;
+    pub use crate::stream::merge::drr::MergeDrr;
+    pub use crate::stream::merge::idle_timeout::IdleTimeout;
+    pub use crate::stream::merge::keyed::KeyedMerge;
+    pub use crate::stream::merge::liveness::WithLiveness;
+    pub use crate::stream::merge::rate_limit::RateLimited;
+    pub use crate::stream::merge::summary::{Summarized, WithSummary};
+    pub use crate::stream::merge::then::MergeThen;
     pub use crate::stream::merge::vec::Merge;
+    pub use crate::stream::zip::skew::SkewedZip;
     pub use crate::stream::zip::vec::Zip;
+    pub use crate::utils::RaceScheduler;
+    #[cfg(feature = "waker-stats")]
+    pub use crate::utils::WakerStats;
+}
+
+/// Building blocks for implementing custom concurrency combinators.
+///
+/// These are the same primitives this crate's own combinators are built on.
+/// [`PollArray`][primitives::PollArray] and [`PollVec`][primitives::PollVec]
+/// track the state of each child, while [`WakerArray`][primitives::WakerArray]
+/// and [`WakerVec`][primitives::WakerVec] hand out one intermediate waker per child
+/// and record which children have been woken, waking the parent task when any
+/// of them become ready. For simple cases, [`combinator`][primitives::combinator]
+/// wraps all of this up behind a closure.
+///
+/// # Examples
+///
+/// A combinator which resolves to the output of the first future to complete:
+///
+/// ```
+/// use futures_concurrency::primitives::{Readiness, WakerVec};
+/// use futures_lite::future::{self, block_on};
+/// use std::future::Future;
+/// use std::pin::Pin;
+/// use std::task::{Context, Poll};
+///
+/// struct First<F> {
+///     futures: Vec<F>,
+///     wakers: WakerVec,
+/// }
+///
+/// impl<F: Future + Unpin> Future for First<F> {
+///     type Output = F::Output;
+///
+///     fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+///         let this = &mut *self;
+///         let mut readiness = this.wakers.readiness().lock().unwrap();
+///         readiness.set_waker(cx.waker());
+///
+///         for index in 0..this.futures.len() {
+///             if !readiness.clear_ready(index) {
+///                 continue;
+///             }
+///
+///             // The child's waker needs the lock, so release it while polling.
+///             drop(readiness);
+///             let mut cx = Context::from_waker(this.wakers.get(index).unwrap());
+///             if let Poll::Ready(output) = Pin::new(&mut this.futures[index]).poll(&mut cx) {
+///                 return Poll::Ready(output);
+///             }
+///             readiness = this.wakers.readiness().lock().unwrap();
+///         }
+///         Poll::Pending
+///     }
+/// }
+///
+/// block_on(async {
+///     let futures: Vec<future::Boxed<u8>> = vec![
+///         Box::pin(future::pending()),
+///         Box::pin(future::ready(2)),
+///     ];
+///     let wakers = WakerVec::new(futures.len());
+///     assert_eq!(First { futures, wakers }.await, 2);
+/// })
+/// ```
+pub mod primitives {
+    pub use crate::utils::{combinator, Combinator, ReadySet};
+    pub use crate::utils::{AtomicReadinessArray, Readiness, ReadinessVec, WakerArray, WakerVec};
+    pub use crate::utils::{PollArray, PollState, PollVec};
 }
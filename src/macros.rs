@@ -0,0 +1,371 @@
+//! Macros for selecting between multiple futures.
+
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures_core::future::FusedFuture;
+
+/// Wait for the first of several futures to complete, trying them in the
+/// order they're written.
+///
+/// Each branch has the form `pattern = future => handler`. The futures are
+/// polled in source order on every poll, so an earlier branch always wins
+/// over a later one when both are ready. Once a future completes its output
+/// is matched against `pattern` (which must be irrefutable), and the
+/// handler's value becomes the value of the whole macro. Futures are
+/// borrowed rather than moved, which makes it possible to select over the
+/// same futures in a loop.
+///
+/// A branch can be disabled by adding a guard: `pattern = future, if
+/// condition => handler`. The guard is evaluated once, when the macro is
+/// entered, and a disabled branch is never polled.
+///
+/// Two special branches are supported:
+///
+/// - `complete => handler` runs when every branch is disabled or its future
+///   has already terminated.
+/// - `default => handler` runs when none of the futures are ready, instead
+///   of waiting for one of them. This makes the selection non-blocking.
+///
+/// If every branch is disabled or terminated and there is no `complete`
+/// branch, the `default` branch runs instead. Without either of them this
+/// panics.
+///
+/// Like `futures::select_biased!`, the futures must implement
+/// [`FusedFuture`][futures_core::future::FusedFuture] and `Unpin`, so that
+/// completed futures aren't polled again. This macro can only be used inside
+/// `async` functions and blocks, and supports up to 16 branches, like the
+/// tuple joins. The futures are raced with
+/// [`Race::race_biased`][crate::future::Race::race_biased].
+///
+/// # Examples
+///
+/// ```
+/// use futures::future::{self, FutureExt};
+/// use futures_concurrency::select_biased;
+/// use futures_lite::future::block_on;
+///
+/// block_on(async {
+///     let mut a = future::ready(1).fuse();
+///     let mut b = future::ready(2).fuse();
+///     let mut total = 0;
+///
+///     loop {
+///         select_biased! {
+///             n = a => total += n * 10,
+///             n = b => total += n,
+///             complete => break,
+///         }
+///     }
+///     // `a` always wins while it's ready, `b` completes after it.
+///     assert_eq!(total, 12);
+/// })
+/// ```
+///
+/// Check whether any future is ready without waiting:
+///
+/// ```
+/// use futures::future::{self, FutureExt};
+/// use futures_concurrency::select_biased;
+/// use futures_lite::future::block_on;
+///
+/// block_on(async {
+///     let mut slow = future::pending::<u8>().fuse();
+///     let mut enabled = future::ready(2).fuse();
+///     let mut disabled = future::ready(3).fuse();
+///
+///     let out = select_biased! {
+///         n = slow => n,
+///         n = disabled, if false => n,
+///         n = enabled => n,
+///         default => 0,
+///     };
+///     assert_eq!(out, 2);
+///
+///     let out = select_biased! {
+///         n = slow => n,
+///         default => 0,
+///     };
+///     assert_eq!(out, 0);
+/// })
+/// ```
+#[macro_export]
+macro_rules! select_biased {
+    ($($tokens:tt)*) => {
+        $crate::__select_biased! {
+            @parse
+            [__b0 __b1 __b2 __b3 __b4 __b5 __b6 __b7 __b8 __b9 __b10 __b11 __b12 __b13 __b14 __b15]
+            [] [] []
+            $($tokens)*
+        }
+    };
+}
+
+/// Implementation detail of [`select_biased!`].
+///
+/// Branches are parsed one at a time, and each one is assigned a name from
+/// the list of identifiers passed in by `select_biased!`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __select_biased {
+    // All branches have been parsed.
+    (@parse [$($names:ident)*] [$($branches:tt)*] [$($complete:tt)*] [$($default:tt)*]) => {
+        $crate::__select_biased!(@expand [$($branches)*] [$($complete)*] [$($default)*])
+    };
+
+    // Separators between branches.
+    (@parse $names:tt $branches:tt $complete:tt $default:tt , $($rest:tt)*) => {
+        $crate::__select_biased!(@parse $names $branches $complete $default $($rest)*)
+    };
+
+    (@parse $names:tt $branches:tt [] $default:tt complete => $body:block $($rest:tt)*) => {
+        $crate::__select_biased!(@parse $names $branches [$body] $default $($rest)*)
+    };
+    (@parse $names:tt $branches:tt [] $default:tt complete => $body:expr $(, $($rest:tt)*)?) => {
+        $crate::__select_biased!(@parse $names $branches [$body] $default $($($rest)*)?)
+    };
+
+    (@parse $names:tt $branches:tt $complete:tt [] default => $body:block $($rest:tt)*) => {
+        $crate::__select_biased!(@parse $names $branches $complete [$body] $($rest)*)
+    };
+    (@parse $names:tt $branches:tt $complete:tt [] default => $body:expr $(, $($rest:tt)*)?) => {
+        $crate::__select_biased!(@parse $names $branches $complete [$body] $($($rest)*)?)
+    };
+
+    // Branches with a guard.
+    (@parse [$name:ident $($names:ident)*] [$($branches:tt)*] $complete:tt $default:tt
+        $pat:pat = $fut:expr, if $guard:expr => $body:block $($rest:tt)*) => {
+        $crate::__select_biased!(@parse [$($names)*]
+            [$($branches)* ($name [$pat] [$fut] [$guard] [$body])] $complete $default $($rest)*)
+    };
+    (@parse [$name:ident $($names:ident)*] [$($branches:tt)*] $complete:tt $default:tt
+        $pat:pat = $fut:expr, if $guard:expr => $body:expr $(, $($rest:tt)*)?) => {
+        $crate::__select_biased!(@parse [$($names)*]
+            [$($branches)* ($name [$pat] [$fut] [$guard] [$body])] $complete $default $($($rest)*)?)
+    };
+
+    // Branches without a guard.
+    (@parse [$name:ident $($names:ident)*] [$($branches:tt)*] $complete:tt $default:tt
+        $pat:pat = $fut:expr => $body:block $($rest:tt)*) => {
+        $crate::__select_biased!(@parse [$($names)*]
+            [$($branches)* ($name [$pat] [$fut] [true] [$body])] $complete $default $($rest)*)
+    };
+    (@parse [$name:ident $($names:ident)*] [$($branches:tt)*] $complete:tt $default:tt
+        $pat:pat = $fut:expr => $body:expr $(, $($rest:tt)*)?) => {
+        $crate::__select_biased!(@parse [$($names)*]
+            [$($branches)* ($name [$pat] [$fut] [true] [$body])] $complete $default $($($rest)*)?)
+    };
+
+    (@parse [] $($rest:tt)*) => {
+        ::core::compile_error!("`select_biased!` supports at most 16 branches")
+    };
+
+    // Without any branches, there's nothing to wait for.
+    (@expand [] [$($complete:expr)?] [$($default:expr)?]) => {
+        $crate::__select_biased!(@complete [$($complete)?] [$($default)?])
+    };
+
+    (@expand
+        [$(($name:ident [$pat:pat] [$fut:expr] [$guard:expr] [$body:expr]))*]
+        [$($complete:expr)?] [$($default:expr)?]
+    ) => {{
+        enum __Selected<T> {
+            Branch(T),
+            Complete,
+            Default,
+        }
+
+        // The output of the branch which won, tagged with the branch.
+        #[allow(non_camel_case_types)]
+        enum __Output<$($name),*> {
+            $($name($name),)*
+        }
+
+        // Each branch borrows its future, so that variables can be selected
+        // over again, while temporaries live until the end of the block.
+        $(
+            let mut $name = $crate::__private::SelectBranch::new(&mut $fut, $guard, __Output::$name);
+        )*
+        let __any_active = false $(|| $name.is_active())*;
+        let __has_default = $crate::__select_biased!(@has [$($default)?]);
+
+        let mut __race = $crate::future::Race::race_biased([
+            $($crate::__private::SelectBranch::erase(&mut $name),)*
+        ]);
+        let __selected = ::core::future::poll_fn(|__cx| {
+            if !__any_active {
+                return ::core::task::Poll::Ready(__Selected::Complete);
+            }
+            let __race = ::core::pin::Pin::new(&mut __race);
+            match ::core::future::Future::poll(__race, __cx) {
+                ::core::task::Poll::Ready(__out) => ::core::task::Poll::Ready(__Selected::Branch(__out)),
+                ::core::task::Poll::Pending if __has_default => ::core::task::Poll::Ready(__Selected::Default),
+                ::core::task::Poll::Pending => ::core::task::Poll::Pending,
+            }
+        })
+        .await;
+
+        match __selected {
+            $(
+                __Selected::Branch(__Output::$name(__out)) => {
+                    #[allow(clippy::match_single_binding)]
+                    match __out {
+                        $pat => $body,
+                    }
+                }
+            )*
+            __Selected::Complete => {
+                $crate::__select_biased!(@complete [$($complete)?] [$($default)?])
+            }
+            __Selected::Default => {
+                $crate::__select_biased!(@default [$($default)?])
+            }
+        }
+    }};
+
+    (@has []) => { false };
+    (@has [$default:expr]) => { true };
+
+    (@complete [$complete:expr] [$($default:expr)?]) => { $complete };
+    (@complete [] [$default:expr]) => { $default };
+    (@complete [] []) => {
+        ::core::panic!("all futures in `select_biased!` have completed, but no `complete` or `default` branch was provided")
+    };
+
+    (@default []) => { ::core::unreachable!() };
+    (@default [$default:expr]) => { $default };
+}
+
+/// A branch of [`select_biased!`], which only polls its future while it's
+/// enabled and hasn't terminated, and tags its output with the branch.
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct SelectBranch<'a, F, W> {
+    fut: &'a mut F,
+    active: bool,
+    wrap: W,
+}
+
+impl<'a, F, W, O> SelectBranch<'a, F, W>
+where
+    F: FusedFuture + Unpin,
+    W: FnMut(F::Output) -> O,
+{
+    pub fn new(fut: &'a mut F, enabled: bool, wrap: W) -> Self {
+        let active = enabled && !fut.is_terminated();
+        Self { fut, active, wrap }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    /// Erase the type of the branch, so the branches fit in one array.
+    pub fn erase(&mut self) -> Pin<&mut (dyn Future<Output = O> + 'a)>
+    where
+        W: Unpin + 'a,
+    {
+        let this: Pin<&mut Self> = Pin::new(self);
+        this
+    }
+}
+
+impl<F, W, O> Future for SelectBranch<'_, F, W>
+where
+    F: Future + Unpin,
+    W: FnMut(F::Output) -> O + Unpin,
+{
+    type Output = O;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<O> {
+        // A disabled branch never wins, but another one always can.
+        if !self.active {
+            return Poll::Pending;
+        }
+        match Pin::new(&mut *self.fut).poll(cx) {
+            Poll::Ready(out) => Poll::Ready((self.wrap)(out)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use futures::future::{self, FutureExt};
+    use futures_lite::future::block_on;
+
+    #[test]
+    fn biased_towards_first_branch() {
+        block_on(async {
+            let mut a = future::ready("a").fuse();
+            let mut b = future::ready("b").fuse();
+            let mut order = vec![];
+            loop {
+                select_biased! {
+                    s = a => order.push(s),
+                    s = b => order.push(s),
+                    complete => break,
+                }
+            }
+            assert_eq!(order, vec!["a", "b"]);
+        })
+    }
+
+    #[test]
+    fn guard_disables_branch() {
+        block_on(async {
+            let mut a = future::ready(1).fuse();
+            let mut b = future::ready(2).fuse();
+            let out = select_biased! {
+                n = a, if false => n,
+                n = b => { n * 10 }
+            };
+            assert_eq!(out, 20);
+            assert!(!futures_core::future::FusedFuture::is_terminated(&a));
+        })
+    }
+
+    #[test]
+    fn all_disabled_runs_complete() {
+        block_on(async {
+            let mut a = future::ready(1).fuse();
+            let out = select_biased! {
+                n = a, if false => n,
+                complete => 0,
+                default => 5,
+            };
+            assert_eq!(out, 0);
+        })
+    }
+
+    #[test]
+    #[should_panic(expected = "no `complete` or `default` branch")]
+    fn all_terminated_without_complete_panics() {
+        block_on(async {
+            let mut a = future::ready(1).fuse();
+            let _ = (&mut a).await;
+            let _: u8 = select_biased! {
+                n = a => n,
+            };
+        })
+    }
+
+    #[test]
+    fn sixteen_branches() {
+        block_on(async {
+            let p = || future::pending::<u8>().fuse();
+            let (mut a, mut b, mut c, mut d, mut e) = (p(), p(), p(), p(), p());
+            let (mut f, mut g, mut h, mut i, mut j) = (p(), p(), p(), p(), p());
+            let (mut k, mut l, mut m, mut n, mut o) = (p(), p(), p(), p(), p());
+            let mut last = future::ready("last").fuse();
+            let out = select_biased! {
+                _ = a => "a", _ = b => "b", _ = c => "c", _ = d => "d",
+                _ = e => "e", _ = f => "f", _ = g => "g", _ = h => "h",
+                _ = i => "i", _ = j => "j", _ = k => "k", _ = l => "l",
+                _ = m => "m", _ = n => "n", _ = o => "o",
+                s = last => s,
+            };
+            assert_eq!(out, "last");
+        })
+    }
+}
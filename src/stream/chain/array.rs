@@ -7,49 +7,84 @@ use pin_project::pin_project;
 
 use crate::utils;
 
-use super::Chain as ChainTrait;
+use super::{poll_chain, Chain as ChainTrait, ChainEvent, Next};
 
 /// A stream that chains multiple streams one after another.
 ///
 /// This `struct` is created by the [`chain`] method on the [`Chain`] trait. See its
 /// documentation for more.
 ///
-/// [`chain`]: trait.Chain.html#method.merge
-/// [`Chain`]: trait.Chain.html
+/// [`chain`]: ChainTrait::chain
+/// [`Chain`]: ChainTrait
+#[must_use = "streams do nothing unless you `.await` or poll them"]
 #[pin_project]
 pub struct Chain<S, const N: usize> {
     #[pin]
     streams: [S; N],
     index: usize,
-    len: usize,
     done: bool,
 }
 
+impl<S, const N: usize> Chain<S, N> {
+    /// Report each time the chain moves on to its next stream.
+    ///
+    /// Items are yielded as [`ChainEvent::Item`], and once the stream at
+    /// index `i` is exhausted the chain yields [`ChainEvent::SegmentDone(i)`]
+    /// before polling the stream after it. This is useful for progress
+    /// tracking over concatenated sources, such as a chain of file reads.
+    /// The `SegmentDone` of the last stream is yielded right before the
+    /// chain ends with `None`.
+    ///
+    /// [`ChainEvent::SegmentDone(i)`]: ChainEvent::SegmentDone
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_concurrency::prelude::*;
+    /// use futures_concurrency::stream::ChainEvent;
+    /// use futures_lite::future::block_on;
+    /// use futures_lite::stream::{self, StreamExt};
+    ///
+    /// block_on(async {
+    ///     let s = [stream::iter(vec![1, 2]), stream::iter(vec![3])].chain();
+    ///     let events: Vec<_> = s.chain_with_transitions().collect().await;
+    ///     assert_eq!(
+    ///         events,
+    ///         vec![
+    ///             ChainEvent::Item(1),
+    ///             ChainEvent::Item(2),
+    ///             ChainEvent::SegmentDone(0),
+    ///             ChainEvent::Item(3),
+    ///             ChainEvent::SegmentDone(1),
+    ///         ]
+    ///     );
+    /// })
+    /// ```
+    #[must_use = "streams do nothing unless you `.await` or poll them"]
+    pub fn chain_with_transitions(self) -> ChainTransitions<S, N> {
+        ChainTransitions {
+            streams: self.streams,
+            index: self.index,
+            done: self.done,
+        }
+    }
+}
+
 impl<S: Stream, const N: usize> Stream for Chain<S, N> {
     type Item = S::Item;
 
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        let mut this = self.project();
-
-        assert!(!*this.done, "Stream should not be polled after completion");
-
-        loop {
-            if this.index == this.len {
-                *this.done = true;
-                return Poll::Ready(None);
-            }
-            let stream = utils::iter_pin_mut(this.streams.as_mut())
-                .nth(*this.index)
-                .unwrap();
-            match stream.poll_next(cx) {
-                Poll::Ready(Some(item)) => return Poll::Ready(Some(item)),
-                Poll::Ready(None) => {
-                    *this.index += 1;
-                    continue;
-                }
-                Poll::Pending => return Poll::Pending,
-            }
-        }
+        let this = self.project();
+        poll_chain(
+            this.streams,
+            this.index,
+            this.done,
+            cx,
+            |_, item| match item {
+                Some(item) => Next::Yield(item),
+                None => Next::Advance,
+            },
+        )
     }
 }
 
@@ -62,6 +97,51 @@ where
     }
 }
 
+/// A stream that chains multiple streams one after another, reporting
+/// each time it moves on to the next one.
+///
+/// This `struct` is created by the [`chain_with_transitions`] method on
+/// [`Chain`]. See its documentation for more.
+///
+/// [`chain_with_transitions`]: Chain::chain_with_transitions
+#[must_use = "streams do nothing unless you `.await` or poll them"]
+#[pin_project]
+pub struct ChainTransitions<S, const N: usize> {
+    #[pin]
+    streams: [S; N],
+    index: usize,
+    done: bool,
+}
+
+impl<S: Stream, const N: usize> Stream for ChainTransitions<S, N> {
+    type Item = ChainEvent<S::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        poll_chain(
+            this.streams,
+            this.index,
+            this.done,
+            cx,
+            |index, item| match item {
+                Some(item) => Next::Yield(ChainEvent::Item(item)),
+                None => Next::AdvanceWith(ChainEvent::SegmentDone(index)),
+            },
+        )
+    }
+}
+
+impl<S, const N: usize> fmt::Debug for ChainTransitions<S, N>
+where
+    S: Stream + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.streams.iter()).finish()
+    }
+}
+
+utils::impl_boxed_stream!({S, const N: usize} Chain<S, N>);
+
 impl<S: Stream, const N: usize> ChainTrait for [S; N] {
     type Item = S::Item;
 
@@ -69,7 +149,6 @@ impl<S: Stream, const N: usize> ChainTrait for [S; N] {
 
     fn chain(self) -> Self::Stream {
         Chain {
-            len: self.len(),
             streams: self,
             index: 0,
             done: false,
@@ -98,4 +177,67 @@ mod tests {
             assert_eq!(s.next().await, None);
         })
     }
+
+    #[test]
+    fn boxed_local() {
+        use std::rc::Rc;
+
+        block_on(async {
+            let a = stream::once(Rc::new(1));
+            let b = stream::once(Rc::new(2));
+            let mut s = [a, b].chain().boxed_local();
+
+            assert_eq!(s.next().await, Some(Rc::new(1)));
+            assert_eq!(s.next().await, Some(Rc::new(2)));
+            assert_eq!(s.next().await, None);
+        })
+    }
+
+    #[test]
+    fn transitions() {
+        block_on(async {
+            let mut s = [stream::once(1), stream::once(2)]
+                .chain()
+                .chain_with_transitions();
+
+            assert_eq!(s.next().await, Some(ChainEvent::Item(1)));
+            assert_eq!(s.next().await, Some(ChainEvent::SegmentDone(0)));
+            assert_eq!(s.next().await, Some(ChainEvent::Item(2)));
+            assert_eq!(s.next().await, Some(ChainEvent::SegmentDone(1)));
+            assert_eq!(s.next().await, None);
+        })
+    }
+
+    #[test]
+    fn does_not_repoll_finished_streams() {
+        use std::cell::Cell;
+
+        block_on(async {
+            let polls = Cell::new(0);
+            let first = stream::poll_fn(|cx| {
+                polls.set(polls.get() + 1);
+                match polls.get() {
+                    1 => Poll::Ready(Some(1)),
+                    2 => {
+                        cx.waker().wake_by_ref();
+                        Poll::Pending
+                    }
+                    3 => Poll::Ready(None),
+                    _ => panic!("polled after ending"),
+                }
+            });
+            let mut s = [
+                first.boxed_local(),
+                stream::empty().boxed_local(),
+                stream::once(2).boxed_local(),
+            ]
+            .chain();
+
+            // A pending stream holds up the ones after it.
+            assert_eq!(s.next().await, Some(1));
+            assert_eq!(s.next().await, Some(2));
+            assert_eq!(s.next().await, None);
+            assert_eq!(polls.get(), 3);
+        })
+    }
 }
@@ -1,6 +1,11 @@
+use core::pin::Pin;
+use core::task::{Context, Poll};
 use futures_core::Stream;
 
+use crate::utils;
+
 pub(crate) mod array;
+pub(crate) mod skip_errors;
 pub(crate) mod tuple;
 pub(crate) mod vec;
 
@@ -15,3 +20,69 @@ pub trait Chain {
     /// Combine multiple streams into a single stream.
     fn chain(self) -> Self::Stream;
 }
+
+/// An item of a chain which reports each time it moves on to its next
+/// stream.
+///
+/// This is the item type of the streams returned by the
+/// `chain_with_transitions` methods of the [vec][crate::vec::Chain] and
+/// [array][crate::array::Chain] chains.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChainEvent<T> {
+    /// An item yielded by the current stream.
+    Item(T),
+    /// The stream at this index has been exhausted.
+    SegmentDone(usize),
+}
+
+/// What a chain does after polling its current stream.
+pub(crate) enum Next<T> {
+    /// Yield an item, and keep polling the same stream next time.
+    Yield(T),
+    /// Move on to the next stream, and poll it straight away.
+    Advance,
+    /// Move on to the next stream, but yield an item first.
+    AdvanceWith(T),
+}
+
+/// Poll the chain of `streams`, starting from the one at `index`.
+///
+/// This is the poll loop shared by the array and vec chains and their
+/// adapters. `step` is handed the index and result of every poll which
+/// didn't return `Poll::Pending`, and decides what to do with it. The chain
+/// ends once it has advanced past the last stream.
+pub(crate) fn poll_chain<S, T, F>(
+    mut streams: Pin<&mut [S]>,
+    index: &mut usize,
+    done: &mut bool,
+    cx: &mut Context<'_>,
+    mut step: F,
+) -> Poll<Option<T>>
+where
+    S: Stream,
+    F: FnMut(usize, Option<S::Item>) -> Next<T>,
+{
+    assert!(!*done, "Stream should not be polled after completion");
+
+    loop {
+        let stream = match utils::get_pin_mut(streams.as_mut(), *index) {
+            Some(stream) => stream,
+            None => {
+                *done = true;
+                return Poll::Ready(None);
+            }
+        };
+        let item = match stream.poll_next(cx) {
+            Poll::Ready(item) => item,
+            Poll::Pending => return Poll::Pending,
+        };
+        match step(*index, item) {
+            Next::Yield(item) => return Poll::Ready(Some(item)),
+            Next::Advance => *index += 1,
+            Next::AdvanceWith(item) => {
+                *index += 1;
+                return Poll::Ready(Some(item));
+            }
+        }
+    }
+}
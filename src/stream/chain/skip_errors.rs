@@ -0,0 +1,107 @@
+use core::fmt;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use futures_core::Stream;
+use pin_project::pin_project;
+
+use super::{poll_chain, Next};
+use crate::utils;
+
+/// A stream that chains multiple streams one after another, skipping to the
+/// next stream when one yields an error.
+///
+/// This `struct` is created by the [`skip_errors`] method on [`Chain`]. See
+/// its documentation for more.
+///
+/// [`skip_errors`]: super::vec::Chain::skip_errors
+/// [`Chain`]: super::vec::Chain
+#[must_use = "streams do nothing unless you `.await` or poll them"]
+#[pin_project]
+pub struct ChainSkipErrors<S, E> {
+    #[pin]
+    streams: Vec<S>,
+    index: usize,
+    errors: Vec<(usize, E)>,
+    done: bool,
+}
+
+impl<S, E> ChainSkipErrors<S, E> {
+    pub(crate) fn new(streams: Vec<S>, index: usize, done: bool) -> Self {
+        Self {
+            streams,
+            index,
+            errors: Vec::new(),
+            done,
+        }
+    }
+
+    /// The errors which caused streams to be skipped so far, together with
+    /// the index of the stream each of them came from.
+    pub fn errors(&self) -> &[(usize, E)] {
+        &self.errors
+    }
+}
+
+impl<S, T, E> Stream for ChainSkipErrors<S, E>
+where
+    S: Stream<Item = Result<T, E>>,
+{
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        let errors = this.errors;
+        let streams = utils::pin_vec_as_slice(this.streams);
+        poll_chain(
+            streams,
+            this.index,
+            this.done,
+            cx,
+            |index, item| match item {
+                Some(Ok(item)) => Next::Yield(item),
+                Some(Err(err)) => {
+                    errors.push((index, err));
+                    Next::Advance
+                }
+                None => Next::Advance,
+            },
+        )
+    }
+}
+
+impl<S, E> fmt::Debug for ChainSkipErrors<S, E>
+where
+    S: fmt::Debug,
+    E: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChainSkipErrors")
+            .field("streams", &self.streams)
+            .field("errors", &self.errors)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::stream::Chain;
+    use futures_lite::future::block_on;
+    use futures_lite::prelude::*;
+    use futures_lite::stream;
+
+    #[test]
+    fn skips_failed_streams() {
+        block_on(async {
+            let a = stream::iter(vec![Ok(1), Err("a"), Ok(2)]);
+            let b = stream::iter(vec![Err("b")]);
+            let c = stream::iter(vec![Ok(3)]);
+            let mut s = vec![a, b, c].chain().skip_errors();
+
+            assert_eq!(s.next().await, Some(1));
+            assert_eq!(s.next().await, Some(3));
+            assert_eq!(s.next().await, None);
+            assert_eq!(s.errors(), &[(0, "a"), (1, "b")]);
+        })
+    }
+}
@@ -7,7 +7,8 @@ use pin_project::pin_project;
 
 use crate::utils;
 
-use super::Chain as ChainTrait;
+use super::skip_errors::ChainSkipErrors;
+use super::{poll_chain, Chain as ChainTrait, ChainEvent, Next};
 
 /// A stream that chains multiple streams one after another.
 ///
@@ -16,40 +17,115 @@ use super::Chain as ChainTrait;
 ///
 /// [`chain`]: trait.Chain.html#method.merge
 /// [`Chain`]: trait.Chain.html
+#[must_use = "streams do nothing unless you `.await` or poll them"]
 #[pin_project]
 pub struct Chain<S> {
     #[pin]
     streams: Vec<S>,
     index: usize,
-    len: usize,
     done: bool,
 }
 
+impl<S, T, E> Chain<S>
+where
+    S: Stream<Item = Result<T, E>>,
+{
+    /// Move on to the next stream when a stream yields an error, instead of
+    /// yielding the error.
+    ///
+    /// The rest of a stream which errors is skipped, and the error is stored
+    /// along with the index of the stream it came from. The stored errors
+    /// can be inspected with [`ChainSkipErrors::errors`], which is useful
+    /// for falling back through an ordered list of sources.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_concurrency::prelude::*;
+    /// use futures_lite::future::block_on;
+    /// use futures_lite::stream::{self, StreamExt};
+    ///
+    /// block_on(async {
+    ///     let mirrors = vec![
+    ///         stream::iter(vec![Err("unreachable"), Ok(0)]),
+    ///         stream::iter(vec![Ok(1), Ok(2)]),
+    ///     ];
+    ///     let mut s = mirrors.chain().skip_errors();
+    ///
+    ///     let mut items = vec![];
+    ///     while let Some(item) = s.next().await {
+    ///         items.push(item);
+    ///     }
+    ///     assert_eq!(items, vec![1, 2]);
+    ///     assert_eq!(s.errors(), &[(0, "unreachable")]);
+    /// })
+    /// ```
+    #[must_use = "streams do nothing unless you `.await` or poll them"]
+    pub fn skip_errors(self) -> ChainSkipErrors<S, E> {
+        ChainSkipErrors::new(self.streams, self.index, self.done)
+    }
+}
+
+impl<S> Chain<S> {
+    /// Report each time the chain moves on to its next stream.
+    ///
+    /// Items are yielded as [`ChainEvent::Item`], and once the stream at
+    /// index `i` is exhausted the chain yields [`ChainEvent::SegmentDone(i)`]
+    /// before polling the stream after it. This is useful for progress
+    /// tracking over concatenated sources, such as a chain of file reads.
+    /// The `SegmentDone` of the last stream is yielded right before the
+    /// chain ends with `None`.
+    ///
+    /// [`ChainEvent::SegmentDone(i)`]: ChainEvent::SegmentDone
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_concurrency::prelude::*;
+    /// use futures_concurrency::stream::ChainEvent;
+    /// use futures_lite::future::block_on;
+    /// use futures_lite::stream::{self, StreamExt};
+    ///
+    /// block_on(async {
+    ///     let s = vec![stream::iter(vec![1, 2]), stream::iter(vec![3])].chain();
+    ///     let events: Vec<_> = s.chain_with_transitions().collect().await;
+    ///     assert_eq!(
+    ///         events,
+    ///         vec![
+    ///             ChainEvent::Item(1),
+    ///             ChainEvent::Item(2),
+    ///             ChainEvent::SegmentDone(0),
+    ///             ChainEvent::Item(3),
+    ///             ChainEvent::SegmentDone(1),
+    ///         ]
+    ///     );
+    /// })
+    /// ```
+    #[must_use = "streams do nothing unless you `.await` or poll them"]
+    pub fn chain_with_transitions(self) -> ChainTransitions<S> {
+        ChainTransitions {
+            streams: self.streams,
+            index: self.index,
+            done: self.done,
+        }
+    }
+}
+
 impl<S: Stream> Stream for Chain<S> {
     type Item = S::Item;
 
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        let mut this = self.project();
-
-        assert!(!*this.done, "Stream should not be polled after completion");
-
-        loop {
-            if this.index == this.len {
-                *this.done = true;
-                return Poll::Ready(None);
-            }
-            let stream = utils::iter_pin_mut_vec(this.streams.as_mut())
-                .nth(*this.index)
-                .unwrap();
-            match stream.poll_next(cx) {
-                Poll::Ready(Some(item)) => return Poll::Ready(Some(item)),
-                Poll::Ready(None) => {
-                    *this.index += 1;
-                    continue;
-                }
-                Poll::Pending => return Poll::Pending,
-            }
-        }
+        let this = self.project();
+        poll_chain(
+            utils::pin_vec_as_slice(this.streams),
+            this.index,
+            this.done,
+            cx,
+            |_, item| match item {
+                Some(item) => Next::Yield(item),
+                None => Next::Advance,
+            },
+        )
     }
 }
 
@@ -62,6 +138,51 @@ where
     }
 }
 
+/// A stream that chains multiple streams one after another, reporting
+/// each time it moves on to the next one.
+///
+/// This `struct` is created by the [`chain_with_transitions`] method on
+/// [`Chain`]. See its documentation for more.
+///
+/// [`chain_with_transitions`]: Chain::chain_with_transitions
+#[must_use = "streams do nothing unless you `.await` or poll them"]
+#[pin_project]
+pub struct ChainTransitions<S> {
+    #[pin]
+    streams: Vec<S>,
+    index: usize,
+    done: bool,
+}
+
+impl<S: Stream> Stream for ChainTransitions<S> {
+    type Item = ChainEvent<S::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        poll_chain(
+            utils::pin_vec_as_slice(this.streams),
+            this.index,
+            this.done,
+            cx,
+            |index, item| match item {
+                Some(item) => Next::Yield(ChainEvent::Item(item)),
+                None => Next::AdvanceWith(ChainEvent::SegmentDone(index)),
+            },
+        )
+    }
+}
+
+impl<S> fmt::Debug for ChainTransitions<S>
+where
+    S: Stream + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.streams.iter()).finish()
+    }
+}
+
+utils::impl_boxed_stream!({S} Chain<S>);
+
 impl<S: Stream> ChainTrait for Vec<S> {
     type Item = S::Item;
 
@@ -69,7 +190,6 @@ impl<S: Stream> ChainTrait for Vec<S> {
 
     fn chain(self) -> Self::Stream {
         Chain {
-            len: self.len(),
             streams: self,
             index: 0,
             done: false,
@@ -98,4 +218,26 @@ mod tests {
             assert_eq!(s.next().await, None);
         })
     }
+
+    #[test]
+    fn transitions_over_empty_streams() {
+        block_on(async {
+            let s = vec![
+                stream::iter(vec![1]),
+                stream::iter(vec![]),
+                stream::iter(vec![2]),
+            ];
+            let events: Vec<_> = s.chain().chain_with_transitions().collect().await;
+            assert_eq!(
+                events,
+                vec![
+                    ChainEvent::Item(1),
+                    ChainEvent::SegmentDone(0),
+                    ChainEvent::SegmentDone(1),
+                    ChainEvent::Item(2),
+                    ChainEvent::SegmentDone(2),
+                ]
+            );
+        })
+    }
 }
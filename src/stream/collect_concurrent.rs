@@ -0,0 +1,200 @@
+use crate::utils::FutureSlots;
+
+use core::fmt;
+use core::future::Future;
+use core::iter;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures_core::Stream;
+
+use pin_project::pin_project;
+
+/// Run the futures yielded by a stream concurrently, collecting their outputs.
+pub trait CollectConcurrentExt: Stream {
+    /// Resolve the futures yielded by this stream with up to `limit` of them
+    /// running at once, collecting their outputs into `C`.
+    ///
+    /// Unlike collecting the stream and then joining the futures, futures
+    /// start running as soon as they're yielded, and the stream is only
+    /// polled for more futures while fewer than `limit` are running. Outputs
+    /// are added to the collection in the order the futures complete. Any
+    /// collection which implements `Default` and `Extend` can be used, such
+    /// as a `Vec`, a `String`, or a `HashMap` for futures which resolve to
+    /// key-value pairs.
+    ///
+    /// Room for the futures is only allocated as they're started, so
+    /// `usize::MAX` can be passed to not cap them at all.
+    ///
+    /// # Panics
+    ///
+    /// This panics if `limit` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_concurrency::prelude::*;
+    /// use futures_lite::future::block_on;
+    /// use futures_lite::stream;
+    /// use std::collections::HashMap;
+    /// use std::future::ready;
+    ///
+    /// block_on(async {
+    ///     let requests = stream::iter(vec![ready(("a", 1)), ready(("b", 2))]);
+    ///     let map: HashMap<_, _> = requests.collect_concurrent(8).await;
+    ///     assert_eq!(map["b"], 2);
+    /// })
+    /// ```
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    fn collect_concurrent<C>(self, limit: usize) -> CollectConcurrent<Self, C>
+    where
+        Self: Sized,
+        Self::Item: Future,
+        C: Default + Extend<<Self::Item as Future>::Output>,
+    {
+        CollectConcurrent::new(self, limit)
+    }
+}
+
+impl<S: Stream> CollectConcurrentExt for S {}
+
+/// A future which runs the futures yielded by a stream concurrently,
+/// collecting their outputs.
+///
+/// This `struct` is created by the [`collect_concurrent`] method on the
+/// [`CollectConcurrentExt`] trait. See its documentation for more.
+///
+/// [`collect_concurrent`]: CollectConcurrentExt::collect_concurrent
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+#[pin_project]
+pub struct CollectConcurrent<S, C>
+where
+    S: Stream,
+{
+    #[pin]
+    stream: S,
+    stream_done: bool,
+    slots: FutureSlots<S::Item>,
+    limit: usize,
+    collection: Option<C>,
+}
+
+impl<S, C> CollectConcurrent<S, C>
+where
+    S: Stream,
+    C: Default,
+{
+    fn new(stream: S, limit: usize) -> Self {
+        assert!(limit > 0, "collect_concurrent requires a non-zero limit");
+        Self {
+            stream,
+            stream_done: false,
+            slots: FutureSlots::new(),
+            limit,
+            collection: Some(C::default()),
+        }
+    }
+}
+
+impl<S, C> fmt::Debug for CollectConcurrent<S, C>
+where
+    S: Stream + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CollectConcurrent")
+            .field("stream", &self.stream)
+            .field("running", &self.slots.running())
+            .finish()
+    }
+}
+
+impl<S, C> Future for CollectConcurrent<S, C>
+where
+    S: Stream,
+    S::Item: Future,
+    C: Extend<<S::Item as Future>::Output>,
+{
+    type Output = C;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        assert!(
+            this.collection.is_some(),
+            "Futures must not be polled after completing"
+        );
+
+        let collection = this.collection;
+        let extend = collection.as_mut().unwrap();
+        let poll = this.slots.poll_stream(
+            this.stream,
+            this.stream_done,
+            *this.limit,
+            cx,
+            |fut| fut,
+            |output| extend.extend(iter::once(output)),
+        );
+        poll.map(|()| collection.take().unwrap())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use futures_lite::future::block_on;
+    use futures_lite::stream::{self, StreamExt};
+    use std::cell::Cell;
+
+    #[test]
+    fn respects_limit() {
+        block_on(async {
+            let running = Cell::new(0);
+            let peak = Cell::new(0);
+            let futures = stream::iter(0..10).map(|n| {
+                let (running, peak) = (&running, &peak);
+                async move {
+                    running.set(running.get() + 1);
+                    peak.set(peak.get().max(running.get()));
+                    futures_lite::future::yield_now().await;
+                    running.set(running.get() - 1);
+                    n
+                }
+            });
+            let mut outputs: Vec<_> = futures.collect_concurrent(3).await;
+            outputs.sort_unstable();
+            assert_eq!(outputs, (0..10).collect::<Vec<_>>());
+            assert_eq!(peak.get(), 3);
+        })
+    }
+
+    #[test]
+    fn collects_in_completion_order() {
+        block_on(async {
+            let futures: Vec<futures_lite::future::Boxed<char>> = vec![
+                Box::pin(async {
+                    futures_lite::future::yield_now().await;
+                    'b'
+                }),
+                Box::pin(async { 'a' }),
+            ];
+            let s: String = stream::iter(futures).collect_concurrent(2).await;
+            assert_eq!(s, "ab");
+        })
+    }
+
+    #[test]
+    fn unbounded_limit() {
+        block_on(async {
+            let futures = stream::iter(0..10).map(std::future::ready);
+            let outputs: Vec<_> = futures.collect_concurrent(usize::MAX).await;
+            assert_eq!(outputs, (0..10).collect::<Vec<_>>());
+        })
+    }
+
+    #[test]
+    fn empty() {
+        block_on(async {
+            let futures = stream::empty::<std::future::Ready<u8>>();
+            let outputs: Vec<u8> = futures.collect_concurrent(1).await;
+            assert!(outputs.is_empty());
+        })
+    }
+}
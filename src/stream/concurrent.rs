@@ -0,0 +1,245 @@
+use crate::utils::FutureSlots;
+
+use core::fmt;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures_core::Stream;
+
+use pin_project::pin_project;
+
+/// Process the items of a stream concurrently.
+pub trait ConcurrentStreamExt: Stream {
+    /// Turn this stream into a [`ConcurrentStream`], whose items are handed
+    /// to async closures which run concurrently.
+    ///
+    /// By default there's no limit on how many of them run at once, see
+    /// [`limit`][ConcurrentStream::limit].
+    fn co(self) -> Co<Self>
+    where
+        Self: Sized,
+    {
+        Co {
+            stream: self,
+            limit: None,
+        }
+    }
+}
+
+impl<S: Stream> ConcurrentStreamExt for S {}
+
+/// A stream whose items are processed concurrently.
+pub trait ConcurrentStream {
+    /// The items being processed.
+    type Item;
+
+    /// The stream the items are pulled from.
+    type Stream: Stream<Item = Self::Item>;
+
+    /// Process at most `limit` items at once.
+    ///
+    /// # Panics
+    ///
+    /// This panics if `limit` is zero.
+    fn limit(self, limit: usize) -> Self;
+
+    /// Run `f` on every item, driving the futures it returns concurrently.
+    ///
+    /// The resulting future completes once the stream has ended and every
+    /// future returned by `f` has completed. The stream is only pulled from
+    /// while fewer than the [`limit`][ConcurrentStream::limit] of futures are
+    /// running, so a slow consumer applies backpressure to the stream.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_concurrency::prelude::*;
+    /// use futures_lite::future::block_on;
+    /// use futures_lite::stream;
+    /// use std::cell::Cell;
+    ///
+    /// block_on(async {
+    ///     let sum = Cell::new(0);
+    ///     stream::iter(1..=4)
+    ///         .co()
+    ///         .limit(2)
+    ///         .for_each(|n| {
+    ///             let sum = &sum;
+    ///             async move { sum.set(sum.get() + n) }
+    ///         })
+    ///         .await;
+    ///     assert_eq!(sum.get(), 10);
+    /// })
+    /// ```
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    fn for_each<F, Fut>(self, f: F) -> ForEach<Self::Stream, F, Fut>
+    where
+        F: FnMut(Self::Item) -> Fut,
+        Fut: Future<Output = ()>;
+}
+
+/// A stream whose items are processed concurrently.
+///
+/// This `struct` is created by the [`co`] method on the
+/// [`ConcurrentStreamExt`] trait. See its documentation for more.
+///
+/// [`co`]: ConcurrentStreamExt::co
+#[derive(Debug)]
+pub struct Co<S> {
+    stream: S,
+    limit: Option<usize>,
+}
+
+impl<S: Stream> ConcurrentStream for Co<S> {
+    type Item = S::Item;
+    type Stream = S;
+
+    fn limit(mut self, limit: usize) -> Self {
+        assert!(limit > 0, "limit requires a non-zero limit");
+        self.limit = Some(limit);
+        self
+    }
+
+    fn for_each<F, Fut>(self, f: F) -> ForEach<S, F, Fut>
+    where
+        F: FnMut(S::Item) -> Fut,
+        Fut: Future<Output = ()>,
+    {
+        ForEach {
+            stream: self.stream,
+            stream_done: false,
+            f,
+            slots: FutureSlots::new(),
+            limit: self.limit.unwrap_or(usize::MAX),
+            done: false,
+        }
+    }
+}
+
+/// A future which runs an async closure on every item of a stream
+/// concurrently.
+///
+/// This `struct` is created by the [`for_each`] method on the
+/// [`ConcurrentStream`] trait. See its documentation for more.
+///
+/// [`for_each`]: ConcurrentStream::for_each
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+#[pin_project]
+pub struct ForEach<S, F, Fut> {
+    #[pin]
+    stream: S,
+    stream_done: bool,
+    f: F,
+    slots: FutureSlots<Fut>,
+    limit: usize,
+    done: bool,
+}
+
+impl<S, F, Fut> fmt::Debug for ForEach<S, F, Fut>
+where
+    S: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ForEach")
+            .field("stream", &self.stream)
+            .field("running", &self.slots.running())
+            .finish()
+    }
+}
+
+impl<S, F, Fut> Future for ForEach<S, F, Fut>
+where
+    S: Stream,
+    F: FnMut(S::Item) -> Fut,
+    Fut: Future<Output = ()>,
+{
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        assert!(!*this.done, "Futures must not be polled after completing");
+
+        let poll = this.slots.poll_stream(
+            this.stream,
+            this.stream_done,
+            *this.limit,
+            cx,
+            this.f,
+            |()| {},
+        );
+        *this.done = poll.is_ready();
+        poll
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use futures_lite::future::{block_on, yield_now};
+    use futures_lite::stream::{self, StreamExt};
+    use std::cell::Cell;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn respects_limit() {
+        block_on(async {
+            let running = AtomicUsize::new(0);
+            let peak = AtomicUsize::new(0);
+            let done = AtomicUsize::new(0);
+            stream::iter(0..20)
+                .co()
+                .limit(3)
+                .for_each(|n| {
+                    let (running, peak, done) = (&running, &peak, &done);
+                    async move {
+                        let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                        peak.fetch_max(now, Ordering::SeqCst);
+                        for _ in 0..n % 4 {
+                            yield_now().await;
+                        }
+                        running.fetch_sub(1, Ordering::SeqCst);
+                        done.fetch_add(1, Ordering::SeqCst);
+                    }
+                })
+                .await;
+            assert_eq!(peak.load(Ordering::SeqCst), 3);
+            assert_eq!(done.load(Ordering::SeqCst), 20);
+        })
+    }
+
+    #[test]
+    fn applies_backpressure() {
+        block_on(async {
+            let pulled = Cell::new(0);
+            let source = stream::iter(0..10).inspect(|_| pulled.set(pulled.get() + 1));
+            let mut fut = source.co().limit(2).for_each(|_| yield_now());
+            assert!(futures_lite::future::poll_once(&mut fut).await.is_none());
+            // Both slots are taken, so nothing else has been pulled yet.
+            assert_eq!(pulled.get(), 2);
+            fut.await;
+            assert_eq!(pulled.get(), 10);
+        })
+    }
+
+    #[test]
+    fn unlimited() {
+        block_on(async {
+            let pulled = Cell::new(0);
+            let source = stream::iter(0..10).inspect(|_| pulled.set(pulled.get() + 1));
+            let mut fut = source.co().for_each(|_| yield_now());
+            assert!(futures_lite::future::poll_once(&mut fut).await.is_none());
+            assert_eq!(pulled.get(), 10);
+            fut.await;
+        })
+    }
+
+    #[test]
+    fn empty() {
+        block_on(async {
+            stream::empty::<u8>()
+                .co()
+                .for_each(|_| async { unreachable!() })
+                .await;
+        })
+    }
+}
@@ -0,0 +1,71 @@
+use futures_core::Stream;
+
+use super::chain::array::Chain;
+use super::zip::array::Zip;
+use super::{Chain as _, Zip as _};
+
+/// Chain or zip a stream with one other stream of the same type, using
+/// method-chaining syntax.
+///
+/// These are shorthands for calling [`Chain`][super::Chain] and
+/// [`Zip`][super::Zip] on an array of two streams, and return the same
+/// combinators. To merge two streams, use
+/// [`MergeExt::merge`][super::MergeExt::merge].
+///
+/// This trait isn't part of the [prelude][crate::prelude]: its `chain` and
+/// `zip` would be ambiguous with the `StreamExt` traits of `futures` and
+/// `futures-lite`. Import it on its own where it's used.
+///
+/// # Examples
+///
+/// ```
+/// use futures_concurrency::stream::StreamCombineExt;
+/// use futures_lite::stream;
+///
+/// let a = stream::iter(vec![1, 2]);
+/// let b = stream::iter(vec![3, 4]);
+/// let items: Vec<_> = stream::block_on(a.chain(b)).collect();
+/// assert_eq!(items, vec![1, 2, 3, 4]);
+///
+/// let a = stream::iter(vec![1, 2]);
+/// let b = stream::iter(vec![3, 4]);
+/// let items: Vec<_> = stream::block_on(a.zip(b)).collect();
+/// assert_eq!(items, vec![[1, 3], [2, 4]]);
+/// ```
+pub trait StreamCombineExt: Stream {
+    /// Yield the items of this stream, then those of `other`, like
+    /// `[self, other].chain()`.
+    #[must_use = "streams do nothing unless you `.await` or poll them"]
+    fn chain(self, other: Self) -> Chain<Self, 2>
+    where
+        Self: Sized,
+    {
+        [self, other].chain()
+    }
+
+    /// Pair up the items of this stream with those of `other`, like
+    /// `[self, other].zip()`.
+    #[must_use = "streams do nothing unless you `.await` or poll them"]
+    fn zip(self, other: Self) -> Zip<Self, 2>
+    where
+        Self: Sized,
+    {
+        [self, other].zip()
+    }
+}
+
+impl<S: Stream> StreamCombineExt for S {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use futures_lite::stream;
+
+    #[test]
+    fn zip_ends_with_the_shorter_stream() {
+        let a = stream::iter(vec![1, 2, 3]);
+        let b = stream::iter(vec![4]);
+        let items: Vec<_> = stream::block_on(a.zip(b)).collect();
+        assert_eq!(items, vec![[1, 4]]);
+    }
+}
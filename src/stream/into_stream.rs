@@ -1,4 +1,8 @@
+use super::MapInto;
+
+use core::pin::Pin;
 use futures_core::Stream;
+use std::boxed::Box;
 
 /// Conversion into a [`Stream`].
 ///
@@ -14,6 +18,69 @@ pub trait IntoStream {
 
     /// Creates a stream from a value.
     fn into_stream(self) -> Self::IntoStream;
+
+    /// Creates a stream which converts each item into `T`.
+    ///
+    /// This is useful to bring streams with different item types to a
+    /// common type before merging them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_concurrency::prelude::*;
+    /// use futures_lite::future::block_on;
+    /// use futures_lite::stream::{self, StreamExt};
+    ///
+    /// block_on(async {
+    ///     let small = stream::iter(vec![1u8, 2]).map_into::<u32>();
+    ///     let large = stream::iter(vec![3u32]);
+    ///     let mut items: Vec<u32> = (small, large).merge().collect().await;
+    ///     items.sort_unstable();
+    ///     assert_eq!(items, vec![1, 2, 3]);
+    /// })
+    /// ```
+    #[must_use = "streams do nothing unless you `.await` or poll them"]
+    fn map_into<T>(self) -> MapInto<Self::IntoStream, T>
+    where
+        Self: Sized,
+        Self::Item: Into<T>,
+    {
+        MapInto::new(self.into_stream())
+    }
+
+    /// Creates a boxed stream which converts each item into `T`.
+    ///
+    /// The vec and array variants of [`Merge`][super::Merge] need all of
+    /// their streams to have the same type. Boxing erases the type of each
+    /// stream, so differently-typed streams can be merged as long as their
+    /// items convert into the same `T`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_concurrency::prelude::*;
+    /// use futures_lite::future::block_on;
+    /// use futures_lite::stream::{self, StreamExt};
+    ///
+    /// block_on(async {
+    ///     let a = stream::once(1u8);
+    ///     let b = stream::iter(vec![2u16, 3]);
+    ///     let s = vec![a.boxed_into::<u64>(), b.boxed_into()].merge();
+    ///     let mut items: Vec<u64> = s.collect().await;
+    ///     items.sort_unstable();
+    ///     assert_eq!(items, vec![1, 2, 3]);
+    /// })
+    /// ```
+    #[must_use = "streams do nothing unless you `.await` or poll them"]
+    fn boxed_into<'a, T>(self) -> Pin<Box<dyn Stream<Item = T> + 'a>>
+    where
+        Self: Sized,
+        Self::IntoStream: 'a,
+        Self::Item: Into<T>,
+        T: 'a,
+    {
+        Box::pin(self.map_into())
+    }
 }
 
 impl<S: Stream> IntoStream for S {
@@ -0,0 +1,59 @@
+use core::fmt;
+use core::marker::PhantomData;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures_core::Stream;
+
+use pin_project::pin_project;
+
+/// A stream which converts the items of another stream with [`Into`].
+///
+/// This `struct` is created by the [`map_into`] method on the [`IntoStream`]
+/// trait. See its documentation for more.
+///
+/// [`map_into`]: super::IntoStream::map_into
+/// [`IntoStream`]: super::IntoStream
+#[must_use = "streams do nothing unless you `.await` or poll them"]
+#[pin_project]
+pub struct MapInto<S, T> {
+    #[pin]
+    stream: S,
+    _item: PhantomData<fn() -> T>,
+}
+
+impl<S, T> MapInto<S, T> {
+    pub(crate) fn new(stream: S) -> Self {
+        Self {
+            stream,
+            _item: PhantomData,
+        }
+    }
+}
+
+impl<S, T> fmt::Debug for MapInto<S, T>
+where
+    S: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("MapInto").field(&self.stream).finish()
+    }
+}
+
+impl<S, T> Stream for MapInto<S, T>
+where
+    S: Stream,
+    S::Item: Into<T>,
+{
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.project()
+            .stream
+            .poll_next(cx)
+            .map(|item| item.map(Into::into))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.stream.size_hint()
+    }
+}
@@ -1,8 +1,9 @@
 use super::Merge as MergeTrait;
 use crate::stream::IntoStream;
-use crate::utils::{self, Indexer, PollArray, WakerArray};
+use crate::utils::{self, Indexer, LengthError, PollArray, WakerArray};
 
 use core::fmt;
+use core::mem;
 use futures_core::Stream;
 use std::pin::Pin;
 use std::task::{Context, Poll};
@@ -14,6 +15,17 @@ use std::task::{Context, Poll};
 ///
 /// [`merge`]: trait.Merge.html#method.merge
 /// [`Merge`]: trait.Merge.html
+///
+/// # Memory layout
+///
+/// The streams and the merge's bookkeeping are stored inline, so the size of
+/// a merge only depends on `S` and `N`, and is available at compile time as
+/// [`SIZE`][Self::SIZE] and [`ALIGN`][Self::ALIGN]. The per-stream wakers
+/// and the boxed ready bits they share are allocated once when the merge is
+/// created, and the merge never allocates after that. Like the array [`Join`][crate::array::Join], the
+/// struct isn't `repr(C)`, and its layout may change between versions of
+/// this crate or of the compiler.
+#[must_use = "streams do nothing unless you `.await` or poll them"]
 #[pin_project::pin_project]
 pub struct Merge<S, const N: usize>
 where
@@ -42,6 +54,93 @@ where
             done: false,
         }
     }
+
+    /// The size of the merge in bytes, as returned by [`mem::size_of`].
+    pub const SIZE: usize = mem::size_of::<Self>();
+
+    /// The alignment of the merge in bytes, as returned by [`mem::align_of`].
+    pub const ALIGN: usize = mem::align_of::<Self>();
+
+    /// Create a new `Merge` from exactly `N` streams yielded by an iterator.
+    ///
+    /// This returns an error if the iterator yields fewer or more than `N`
+    /// streams, in which case the streams it yielded are dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_concurrency::array::Merge;
+    /// use futures_lite::stream::{self, StreamExt};
+    ///
+    /// let streams = (0..2).map(stream::once);
+    /// let merge = Merge::<_, 2>::try_from_iter(streams).unwrap();
+    /// let mut items: Vec<_> = stream::block_on(merge).collect();
+    /// items.sort_unstable();
+    /// assert_eq!(items, vec![0, 1]);
+    /// ```
+    pub fn try_from_iter<I>(iter: I) -> Result<Self, LengthError>
+    where
+        I: IntoIterator<Item = S>,
+    {
+        Ok(Self::new(utils::collect_array(iter)?))
+    }
+
+    /// Returns `true` if every stream in the merge has been exhausted.
+    ///
+    /// This reads the merge's bookkeeping without polling any streams or
+    /// taking any locks. Once it returns `true`, polling the merge returns
+    /// `Poll::Ready(None)`. An empty merge is always complete.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_concurrency::prelude::*;
+    /// use futures_lite::future::block_on;
+    /// use futures_lite::stream::{self, StreamExt};
+    ///
+    /// block_on(async {
+    ///     let mut s = [stream::once(1), stream::once(2)].merge();
+    ///     assert!(!s.is_complete());
+    ///
+    ///     while s.next().await.is_some() {}
+    ///     assert!(s.is_complete());
+    /// })
+    /// ```
+    pub fn is_complete(&self) -> bool {
+        self.complete == N
+    }
+}
+
+impl<'a, S, const N: usize> Merge<Pin<&'a mut S>, N>
+where
+    S: Stream,
+{
+    /// Create a new `Merge` from an array of pinned streams.
+    ///
+    /// This makes it possible to merge `!Unpin` streams which have been pinned
+    /// on the stack using [`pin!`](core::pin::pin), without boxing them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_concurrency::array::Merge;
+    /// use futures_lite::future::block_on;
+    /// use futures_lite::stream::{self, StreamExt};
+    /// use std::pin::pin;
+    ///
+    /// block_on(async {
+    ///     let a = pin!(stream::once(1));
+    ///     let b = pin!(stream::once(2));
+    ///     let s = Merge::from_pinned([a, b]);
+    ///
+    ///     let mut counter = 0;
+    ///     s.for_each(|n| counter += n).await;
+    ///     assert_eq!(counter, 3);
+    /// })
+    /// ```
+    pub fn from_pinned(streams: [Pin<&'a mut S>; N]) -> Self {
+        Self::new(streams)
+    }
 }
 
 impl<S, const N: usize> fmt::Debug for Merge<S, N>
@@ -62,7 +161,12 @@ where
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         let mut this = self.project();
 
-        let mut readiness = this.wakers.readiness().lock().unwrap();
+        // An empty merge, or one where every stream is exhausted, is done.
+        if *this.complete == this.streams.len() {
+            return Poll::Ready(None);
+        }
+
+        let readiness = this.wakers.readiness();
         readiness.set_waker(cx.waker());
 
         // Iterate over our streams one-by-one. If a stream yields a value,
@@ -76,17 +180,15 @@ where
                 continue;
             }
 
-            // unlock readiness so we don't deadlock when polling
-            drop(readiness);
-
-            // Obtain the intermediate waker.
+            // Obtain the intermediate waker. Wakeups while the stream is
+            // being polled aren't lost, see `AtomicReadinessArray::wake`.
             let mut cx = Context::from_waker(this.wakers.get(index).unwrap());
 
             let stream = utils::get_pin_mut(this.streams.as_mut(), index).unwrap();
             match stream.poll_next(&mut cx) {
                 Poll::Ready(Some(item)) => {
                     // Mark ourselves as ready again because we need to poll for the next item.
-                    this.wakers.readiness().lock().unwrap().set_ready(index);
+                    readiness.set_ready(index);
                     return Poll::Ready(Some(item));
                 }
                 Poll::Ready(None) => {
@@ -98,15 +200,14 @@ where
                 }
                 Poll::Pending => {}
             }
-
-            // Lock readiness so we can use it again
-            readiness = this.wakers.readiness().lock().unwrap();
         }
 
         Poll::Pending
     }
 }
 
+utils::impl_boxed_stream!({S, const N: usize} Merge<S, N> where S: Stream);
+
 impl<S, const N: usize> MergeTrait for [S; N]
 where
     S: IntoStream,
@@ -126,6 +227,7 @@ mod tests {
 
     use super::*;
     use crate::utils::channel::local_channel;
+    use crate::utils::scripted::{trace, Scripted, Step};
     use futures::executor::LocalPool;
     use futures::task::LocalSpawnExt;
     use futures_lite::future::block_on;
@@ -213,4 +315,59 @@ mod tests {
             pool.run_until_stalled()
         }
     }
+
+    /// Every stream is woken from its own thread, so the ready bits are set
+    /// concurrently with the merge clearing them.
+    #[test]
+    fn merge_threaded_channels() {
+        use futures::channel::mpsc;
+        use std::convert::TryInto;
+        use std::thread;
+
+        let (senders, receivers): (Vec<_>, Vec<_>) = (0..4).map(|_| mpsc::unbounded()).unzip();
+        let receivers: [_; 4] = receivers.try_into().unwrap();
+        let threads: Vec<_> = senders
+            .into_iter()
+            .map(|send| {
+                thread::spawn(move || {
+                    for i in 1..=100 {
+                        send.unbounded_send(i).unwrap();
+                    }
+                })
+            })
+            .collect();
+
+        let sum = block_on(receivers.merge().fold(0, |a, b| a + b));
+        threads.into_iter().for_each(|t| t.join().unwrap());
+        assert_eq!(sum, 4 * 5050);
+    }
+
+    #[test]
+    fn merge_array_empty() {
+        block_on(async {
+            let streams: [stream::Once<u8>; 0] = [];
+            let mut s = streams.merge();
+            assert_eq!(s.next().await, None);
+        })
+    }
+
+    #[test]
+    fn output_is_reproducible() {
+        let streams = || {
+            std::array::from_fn::<_, 3, _>(|stream| {
+                Scripted::new((0..6).map(move |n| {
+                    if (n + stream) % 3 == 0 {
+                        Step::Pending
+                    } else {
+                        Step::Item((stream, n))
+                    }
+                }))
+            })
+        };
+        let expected = trace(streams().merge());
+        assert_eq!(expected.iter().filter(|poll| poll.is_ready()).count(), 12);
+        for _ in 0..10 {
+            assert_eq!(trace(streams().merge()), expected);
+        }
+    }
 }
@@ -0,0 +1,295 @@
+use crate::stream::IntoStream;
+use crate::utils::{self, PollVec, WakerVec};
+
+use core::fmt;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures_core::Stream;
+
+use pin_project::pin_project;
+
+/// Combine multiple sorted streams into a single sorted stream.
+///
+/// This is implemented for vectors of streams.
+pub trait MergeSorted {
+    /// The stream type being merged.
+    type Stream: Stream;
+
+    /// Merge streams which are each sorted by `key` into a single stream
+    /// sorted by `key`.
+    ///
+    /// This is meant for streams of events ordered by a timestamp. An item is
+    /// only emitted once every stream which hasn't ended yet has an item
+    /// waiting, because until then a stream could still yield an item with
+    /// an earlier key. The smallest waiting item is emitted first, and items
+    /// with equal keys are emitted in the order of their streams' indexes.
+    ///
+    /// This means that a single slow stream holds back the whole merge, even
+    /// when the other streams have items ready. Items are only emitted in
+    /// order if each stream is itself sorted by `key`.
+    ///
+    /// Like [`merge_drr`][super::MergeDeficitRoundRobin::merge_drr], this has
+    /// its own polling order, so it doesn't take the options of a
+    /// [`Merge`][super::vec::Merge].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_concurrency::prelude::*;
+    /// use futures_lite::future::block_on;
+    /// use futures_lite::stream::{self, StreamExt};
+    ///
+    /// block_on(async {
+    ///     let a = stream::iter(vec![(1, "login"), (5, "logout")]);
+    ///     let b = stream::iter(vec![(2, "click"), (3, "scroll")]);
+    ///     let s = vec![a, b].merge_by_key(|(time, _)| *time);
+    ///
+    ///     let events: Vec<_> = s.map(|(_, event)| event).collect().await;
+    ///     assert_eq!(events, vec!["login", "click", "scroll", "logout"]);
+    /// })
+    /// ```
+    #[must_use = "streams do nothing unless you `.await` or poll them"]
+    fn merge_by_key<F, K>(self, key: F) -> MergeByKey<Self::Stream, F>
+    where
+        F: FnMut(&<Self::Stream as Stream>::Item) -> K,
+        K: Ord;
+}
+
+impl<S> MergeSorted for Vec<S>
+where
+    S: IntoStream,
+{
+    type Stream = S::IntoStream;
+
+    fn merge_by_key<F, K>(self, key: F) -> MergeByKey<Self::Stream, F>
+    where
+        F: FnMut(&<Self::Stream as Stream>::Item) -> K,
+        K: Ord,
+    {
+        let streams = self.into_iter().map(IntoStream::into_stream).collect();
+        MergeByKey::new(streams, key)
+    }
+}
+
+/// A stream which merges sorted streams into a single sorted stream, ordered
+/// by a key extracted from each item.
+///
+/// This `struct` is created by the [`merge_by_key`] method on the
+/// [`MergeSorted`] trait. See its documentation for more.
+///
+/// [`merge_by_key`]: MergeSorted::merge_by_key
+#[must_use = "streams do nothing unless you `.await` or poll them"]
+#[pin_project]
+pub struct MergeByKey<S, F>
+where
+    S: Stream,
+{
+    #[pin]
+    streams: Vec<S>,
+    wakers: WakerVec,
+    state: PollVec,
+    complete: usize,
+    /// The next item of each stream, waiting to be emitted.
+    heads: Vec<Option<S::Item>>,
+    key: F,
+}
+
+impl<S, F> MergeByKey<S, F>
+where
+    S: Stream,
+{
+    pub(crate) fn new(streams: Vec<S>, key: F) -> Self {
+        let len = streams.len();
+        Self {
+            streams,
+            wakers: WakerVec::new(len),
+            state: PollVec::new(len),
+            complete: 0,
+            heads: (0..len).map(|_| None).collect(),
+            key,
+        }
+    }
+
+    /// Take out the items which have been pulled from the streams but not
+    /// yielded yet, in the order of the streams they came from.
+    ///
+    /// The merge holds on to the next item of each stream until it knows
+    /// which one sorts first, and dropping the merge discards them. This
+    /// doesn't poll any streams: it only returns items which have already
+    /// been buffered. If the merge is polled again afterwards, the streams
+    /// whose items were taken are asked for their next item.
+    pub fn drain(self: Pin<&mut Self>) -> Vec<S::Item> {
+        let this = self.project();
+        let mut readiness = this.wakers.readiness().lock().unwrap();
+        let mut items = Vec::new();
+        for (index, head) in this.heads.iter_mut().enumerate() {
+            if let Some(item) = head.take() {
+                readiness.set_ready(index);
+                items.push(item);
+            }
+        }
+        items
+    }
+}
+
+impl<S, F> fmt::Debug for MergeByKey<S, F>
+where
+    S: Stream + fmt::Debug,
+    S::Item: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MergeByKey")
+            .field("streams", &self.streams)
+            .field("heads", &self.heads)
+            .finish()
+    }
+}
+
+impl<S, F, K> Stream for MergeByKey<S, F>
+where
+    S: Stream,
+    F: FnMut(&S::Item) -> K,
+    K: Ord,
+{
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+        let len = this.streams.len();
+
+        let mut readiness = this.wakers.readiness().lock().unwrap();
+        readiness.set_waker(cx.waker());
+
+        // Fill in the heads of the streams which have been woken.
+        let mut waiting = false;
+        for index in 0..len {
+            if this.state[index].is_consumed() || this.heads[index].is_some() {
+                continue;
+            }
+            if !readiness.clear_ready(index) {
+                waiting = true;
+                continue;
+            }
+
+            // unlock readiness so we don't deadlock when polling
+            drop(readiness);
+
+            let mut cx = Context::from_waker(this.wakers.get(index).unwrap());
+            let stream = utils::get_pin_mut_from_vec(this.streams.as_mut(), index).unwrap();
+            match stream.poll_next(&mut cx) {
+                Poll::Ready(Some(item)) => this.heads[index] = Some(item),
+                Poll::Ready(None) => {
+                    this.state[index].set_consumed();
+                    *this.complete += 1;
+                }
+                Poll::Pending => waiting = true,
+            }
+
+            readiness = this.wakers.readiness().lock().unwrap();
+        }
+
+        // A live stream without a head may still yield an item which sorts
+        // before every head we have, so nothing is safe to emit yet.
+        if waiting {
+            return Poll::Pending;
+        }
+        if *this.complete == len {
+            return Poll::Ready(None);
+        }
+
+        // Every live stream has a head: emit the smallest one. Ties go to the
+        // stream with the lowest index.
+        let mut min: Option<(usize, K)> = None;
+        for (index, head) in this.heads.iter().enumerate() {
+            if let Some(item) = head {
+                let key = (this.key)(item);
+                let smaller = match &min {
+                    Some((_, min)) => key < *min,
+                    None => true,
+                };
+                if smaller {
+                    min = Some((index, key));
+                }
+            }
+        }
+        let (index, _) = min.expect("a live stream without a head should be waiting");
+
+        // Poll the stream for its next head the next time around.
+        readiness.set_ready(index);
+        Poll::Ready(this.heads[index].take())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::MergeSorted;
+    use crate::utils::channel::local_channel;
+    use futures_lite::future::{block_on, poll_once};
+    use futures_lite::stream::{self, StreamExt};
+
+    #[test]
+    fn orders_by_key() {
+        block_on(async {
+            let a = stream::iter(vec![(1, 'a'), (4, 'a'), (5, 'a')]);
+            let b = stream::iter(vec![(2, 'b'), (3, 'b'), (6, 'b')]);
+            let c = stream::iter(vec![(4, 'c')]);
+            let items: Vec<_> = vec![a.boxed(), b.boxed(), c.boxed()]
+                .merge_by_key(|(time, _)| *time)
+                .collect()
+                .await;
+
+            let expected = vec![
+                (1, 'a'),
+                (2, 'b'),
+                (3, 'b'),
+                (4, 'a'),
+                (4, 'c'),
+                (5, 'a'),
+                (6, 'b'),
+            ];
+            assert_eq!(items, expected);
+        })
+    }
+
+    #[test]
+    fn drain_takes_heads() {
+        block_on(async {
+            let (send_a, recv_a) = local_channel();
+            let (send_b, recv_b) = local_channel();
+            let mut s = std::pin::pin!(vec![recv_a, recv_b].merge_by_key(|time: &u32| *time));
+
+            send_a.send(2);
+            send_a.send(3);
+            assert!(poll_once(s.next()).await.is_none());
+            assert_eq!(s.as_mut().drain(), vec![2]);
+
+            // The drained stream is polled for its next item.
+            send_b.send(4);
+            assert_eq!(s.next().await, Some(3));
+            assert_eq!(s.as_mut().drain(), vec![4]);
+        })
+    }
+
+    #[test]
+    fn waits_for_slow_sources() {
+        block_on(async {
+            let (send_a, recv_a) = local_channel();
+            let (send_b, recv_b) = local_channel();
+            let mut s = vec![recv_a, recv_b].merge_by_key(|time: &u32| *time);
+
+            send_a.send(2);
+            // `b` could still yield something earlier than 2.
+            assert!(poll_once(s.next()).await.is_none());
+
+            send_b.send(1);
+            assert_eq!(s.next().await, Some(1));
+            // `b` is live again without a head, so 2 still has to wait.
+            assert!(poll_once(s.next()).await.is_none());
+
+            drop(send_b);
+            assert_eq!(s.next().await, Some(2));
+            drop(send_a);
+            assert_eq!(s.next().await, None);
+        })
+    }
+}
@@ -0,0 +1,129 @@
+use core::fmt;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures_core::Stream;
+use std::any::Any;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use pin_project::pin_project;
+
+/// A stream which turns a panic in the underlying stream into an error item.
+///
+/// Once the underlying stream has panicked this yields its panic payload as
+/// an `Err`, and then ends without polling the stream ever again. This is the
+/// type of the streams in the merge returned by the [`merge_catch_unwind`]
+/// method on [`Merge`]. See its documentation for more.
+///
+/// [`merge_catch_unwind`]: super::vec::Merge::merge_catch_unwind
+/// [`Merge`]: super::vec::Merge
+#[must_use = "streams do nothing unless you `.await` or poll them"]
+#[pin_project]
+pub struct CatchUnwind<S> {
+    #[pin]
+    stream: S,
+    done: bool,
+}
+
+impl<S> CatchUnwind<S> {
+    pub(crate) fn new(stream: S) -> Self {
+        Self {
+            stream,
+            done: false,
+        }
+    }
+}
+
+impl<S> fmt::Debug for CatchUnwind<S>
+where
+    S: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CatchUnwind")
+            .field("stream", &self.stream)
+            .field("done", &self.done)
+            .finish()
+    }
+}
+
+impl<S> Stream for CatchUnwind<S>
+where
+    S: Stream,
+{
+    type Item = Result<S::Item, Box<dyn Any + Send>>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        if *this.done {
+            return Poll::Ready(None);
+        }
+
+        let mut stream = this.stream;
+        match catch_unwind(AssertUnwindSafe(|| stream.as_mut().poll_next(cx))) {
+            Ok(Poll::Ready(Some(item))) => Poll::Ready(Some(Ok(item))),
+            // A restartable merge polls exhausted streams again, so only a
+            // panic closes the stream for good.
+            Ok(Poll::Ready(None)) => Poll::Ready(None),
+            Ok(Poll::Pending) => Poll::Pending,
+            // A stream which panicked may have been left in any state, so it
+            // must not be polled again.
+            Err(payload) => {
+                *this.done = true;
+                Poll::Ready(Some(Err(payload)))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::stream::Merge as _;
+    use futures_lite::future::block_on;
+    use futures_lite::stream::{self, StreamExt};
+    use std::cell::Cell;
+    use std::task::Poll;
+
+    #[test]
+    fn panicking_stream_is_closed() {
+        block_on(async {
+            let polls = Cell::new(0);
+            let panicking = stream::poll_fn(|_| {
+                polls.set(polls.get() + 1);
+                if polls.get() == 2 {
+                    panic!("boom");
+                }
+                Poll::Ready(Some(0))
+            })
+            .boxed_local();
+            let healthy = stream::iter(vec![1, 2, 3]).boxed_local();
+            let s = vec![panicking, healthy].merge().merge_catch_unwind();
+
+            let (mut oks, mut errs) = (vec![], vec![]);
+            s.for_each(|item| match item {
+                Ok(n) => oks.push(n),
+                Err(payload) => errs.push(*payload.downcast::<&str>().unwrap()),
+            })
+            .await;
+
+            oks.sort_unstable();
+            assert_eq!(oks, vec![0, 1, 2, 3]);
+            assert_eq!(errs, vec!["boom"]);
+            // The stream isn't polled again after panicking.
+            assert_eq!(polls.get(), 2);
+        })
+    }
+
+    #[test]
+    fn keeps_merge_options() {
+        block_on(async {
+            let a = stream::iter(vec![1, 1]);
+            let b = stream::iter(vec![2, 2]);
+            let mut merge = vec![a, b].merge();
+            merge.pause(0);
+            let s = merge.merge_catch_unwind();
+
+            // The first stream is still paused, so only the second one yields.
+            let items: Vec<_> = s.take(2).map(Result::unwrap).collect().await;
+            assert_eq!(items, vec![2, 2]);
+        })
+    }
+}
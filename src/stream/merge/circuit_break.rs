@@ -0,0 +1,243 @@
+use super::vec::Merge;
+use crate::future::Timer;
+
+use bitvec::{bitvec, vec::BitVec};
+use core::fmt;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use core::time::Duration;
+use futures_core::Stream;
+use std::collections::VecDeque;
+
+use pin_project::pin_project;
+
+/// A merged stream which drops streams that yield too many errors.
+///
+/// This `struct` is created by the [`circuit_break`] method on [`Merge`].
+/// See its documentation for more.
+///
+/// [`circuit_break`]: Merge::circuit_break
+#[must_use = "streams do nothing unless you `.await` or poll them"]
+#[pin_project]
+pub struct CircuitBreaker<S, T>
+where
+    S: Stream,
+    T: Timer,
+{
+    #[pin]
+    merge: Merge<S>,
+    max_errors: usize,
+    window: Duration,
+    /// One sleep per recent error of each stream, oldest first. Every sleep
+    /// lasts `window`, so they complete in the order they were created.
+    windows: Vec<VecDeque<Pin<Box<T::Sleep>>>>,
+    tripped: BitVec,
+    timer: T,
+}
+
+impl<S, T> CircuitBreaker<S, T>
+where
+    S: Stream,
+    T: Timer,
+{
+    pub(crate) fn new(
+        merge: Merge<S>,
+        len: usize,
+        max_errors: usize,
+        window: Duration,
+        timer: T,
+    ) -> Self {
+        Self {
+            merge,
+            max_errors,
+            window,
+            windows: (0..len).map(|_| VecDeque::new()).collect(),
+            tripped: bitvec![0; len],
+            timer,
+        }
+    }
+
+    /// Returns `true` if the stream at `index` has been dropped for yielding
+    /// too many errors.
+    ///
+    /// # Panics
+    ///
+    /// This panics if `index` is out of bounds.
+    pub fn is_tripped(&self, index: usize) -> bool {
+        self.tripped[index]
+    }
+}
+
+impl<S, T> fmt::Debug for CircuitBreaker<S, T>
+where
+    S: Stream + fmt::Debug,
+    T: Timer,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CircuitBreaker")
+            .field("merge", &self.merge)
+            .field("max_errors", &self.max_errors)
+            .field("window", &self.window)
+            .field("tripped", &self.tripped)
+            .finish()
+    }
+}
+
+impl<S, T, I, E> Stream for CircuitBreaker<S, T>
+where
+    S: Stream<Item = Result<I, E>>,
+    T: Timer,
+{
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        // Forget the errors whose window has elapsed.
+        for sleeps in this.windows.iter_mut() {
+            while let Some(sleep) = sleeps.front_mut() {
+                if sleep.as_mut().poll(cx).is_pending() {
+                    break;
+                }
+                sleeps.pop_front();
+            }
+        }
+
+        match this.merge.as_mut().poll_next_indexed(cx) {
+            Poll::Ready(Some((index, Err(err)))) => {
+                let sleeps = &mut this.windows[index];
+                sleeps.push_back(Box::pin(this.timer.sleep(*this.window)));
+                if sleeps.len() > *this.max_errors {
+                    // Trip the breaker: the stream is never polled again.
+                    sleeps.clear();
+                    this.tripped.set(index, true);
+                    this.merge.as_mut().close(index);
+                } else if sleeps.len() == 1 {
+                    // Register the sleep's waker straight away. Later sleeps
+                    // are polled once the ones before them have completed.
+                    if sleeps[0].as_mut().poll(cx).is_ready() {
+                        cx.waker().wake_by_ref();
+                    }
+                }
+                Poll::Ready(Some(Err(err)))
+            }
+            Poll::Ready(Some((_, item))) => Poll::Ready(Some(item)),
+            Poll::Ready(None) => Poll::Ready(None),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::stream::Merge as _;
+    use crate::utils::channel::local_channel;
+    use crate::utils::CountingWaker;
+    use futures_lite::future::{block_on, pending, poll_once};
+    use futures_lite::stream::{self, StreamExt};
+    use std::cell::RefCell;
+    use std::pin::Pin;
+    use std::rc::Rc;
+    use std::sync::Arc;
+    use std::task::{Context, Poll, Waker};
+    use std::time::Duration;
+
+    #[test]
+    fn drops_flapping_stream() {
+        block_on(async {
+            let flapping = stream::iter(vec![Err(1), Ok(1), Err(2), Err(3), Ok(2)]);
+            let healthy = stream::iter(vec![Ok(10), Err(10), Ok(11)]);
+            // The windows never elapse.
+            let timer = |_: Duration| pending::<()>();
+            let mut s =
+                vec![flapping, healthy]
+                    .merge()
+                    .circuit_break(2, Duration::from_secs(1), timer);
+
+            let mut items = vec![];
+            while let Some(item) = s.next().await {
+                items.push(item);
+            }
+            assert!(s.is_tripped(0));
+            assert!(!s.is_tripped(1));
+
+            // Every error is yielded, including the one which tripped the
+            // breaker, but nothing after it.
+            let flapping: Vec<_> = items
+                .iter()
+                .filter(|item| matches!(item, Ok(n) | Err(n) if *n < 10))
+                .collect();
+            assert_eq!(flapping, vec![&Err(1), &Ok(1), &Err(2), &Err(3)]);
+            assert_eq!(items.len(), 7);
+        })
+    }
+
+    #[test]
+    fn errors_expire_after_window() {
+        block_on(async {
+            let (send, recv) = local_channel::<Result<u8, u8>>();
+            // Each window stays open until its sender is dropped.
+            let windows = Rc::new(RefCell::new(vec![]));
+            let timer = {
+                let windows = windows.clone();
+                move |_: Duration| {
+                    let (send, recv) = local_channel::<()>();
+                    windows.borrow_mut().push(send);
+                    let mut recv = recv;
+                    async move {
+                        recv.next().await;
+                    }
+                }
+            };
+            let mut s = vec![recv]
+                .merge()
+                .circuit_break(1, Duration::from_secs(1), timer);
+
+            send.send(Err(0));
+            assert_eq!(s.next().await, Some(Err(0)));
+
+            // The first error's window elapses before the next one.
+            windows.borrow_mut().clear();
+            send.send(Err(1));
+            assert_eq!(s.next().await, Some(Err(1)));
+            assert!(!s.is_tripped(0));
+
+            // Two errors within one window trip the breaker.
+            send.send(Err(2));
+            assert_eq!(s.next().await, Some(Err(2)));
+            assert!(s.is_tripped(0));
+            send.send(Ok(3));
+            assert_eq!(poll_once(s.next()).await, Some(None));
+        })
+    }
+
+    /// A tripped stream leaves the rotation, so its ready bit mustn't keep a
+    /// real-time merge waking itself.
+    #[test]
+    fn tripped_stream_stays_quiet() {
+        let flapping = stream::iter(vec![Err::<u8, u8>(1)]).chain(stream::pending());
+        let healthy = stream::pending();
+        let timer = |_: Duration| pending::<()>();
+        let mut s = vec![flapping.boxed(), healthy.boxed()]
+            .merge()
+            .real_time()
+            .circuit_break(0, Duration::from_secs(1), timer);
+
+        let waker = Arc::new(CountingWaker::default());
+        let parent = Waker::from(waker.clone());
+        let mut cx = Context::from_waker(&parent);
+
+        assert_eq!(
+            Pin::new(&mut s).poll_next(&mut cx),
+            Poll::Ready(Some(Err(1)))
+        );
+        assert!(s.is_tripped(0));
+
+        let count = waker.count();
+        for _ in 0..3 {
+            assert_eq!(Pin::new(&mut s).poll_next(&mut cx), Poll::Pending);
+        }
+        assert_eq!(waker.count(), count);
+    }
+}
@@ -0,0 +1,287 @@
+use crate::stream::IntoStream;
+use crate::utils::{self, PollVec, WakerVec};
+
+use core::fmt;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures_core::Stream;
+
+use pin_project::pin_project;
+
+/// Combine multiple streams into a single stream, sharing the total weight
+/// of the yielded items fairly between streams.
+///
+/// This is implemented for vectors of streams.
+pub trait MergeDeficitRoundRobin {
+    /// The stream type being merged.
+    type Stream: Stream;
+
+    /// Merge the streams using deficit round-robin.
+    ///
+    /// Streams take turns in index order. Each turn grants a stream
+    /// `quantum` credit, and the stream may yield items for as long as their
+    /// `weight` fits within its accumulated credit. An item which doesn't fit
+    /// is held back until a later turn has granted enough credit, and a
+    /// stream which has nothing to yield when its turn comes loses its
+    /// credit.
+    ///
+    /// Plain round-robin is only fair in the number of items: a stream with
+    /// heavy items gets proportionally more of the consumer's time. With
+    /// deficit round-robin, after `k` rounds in which a stream always had an
+    /// item ready, the total weight it yielded is within the largest item
+    /// weight of `k * quantum`, no matter how the weights are distributed.
+    ///
+    /// This has its own polling order, so it doesn't take the options of a
+    /// [`Merge`][super::vec::Merge] such as
+    /// [`real_time`][super::vec::Merge::real_time]. Streams are polled in
+    /// turn rather than only when woken.
+    ///
+    /// # Panics
+    ///
+    /// This panics if `quantum` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_concurrency::prelude::*;
+    /// use futures_lite::future::block_on;
+    /// use futures_lite::stream::{self, StreamExt};
+    ///
+    /// block_on(async {
+    ///     let heavy = stream::iter(vec![2, 2]);
+    ///     let light = stream::iter(vec![1, 1, 1, 1]);
+    ///     let s = vec![heavy, light].merge_drr(2, |n| *n);
+    ///
+    ///     let items: Vec<u32> = s.collect().await;
+    ///     assert_eq!(items, vec![2, 1, 1, 2, 1, 1]);
+    /// })
+    /// ```
+    #[must_use = "streams do nothing unless you `.await` or poll them"]
+    fn merge_drr<W>(self, quantum: u32, weight: W) -> MergeDrr<Self::Stream, W>
+    where
+        W: FnMut(&<Self::Stream as Stream>::Item) -> u32;
+}
+
+impl<S> MergeDeficitRoundRobin for Vec<S>
+where
+    S: IntoStream,
+{
+    type Stream = S::IntoStream;
+
+    fn merge_drr<W>(self, quantum: u32, weight: W) -> MergeDrr<Self::Stream, W>
+    where
+        W: FnMut(&<Self::Stream as Stream>::Item) -> u32,
+    {
+        let streams = self.into_iter().map(IntoStream::into_stream).collect();
+        MergeDrr::new(streams, quantum, weight)
+    }
+}
+
+/// A stream which merges multiple streams using deficit round-robin.
+///
+/// This `struct` is created by the [`merge_drr`] method on the
+/// [`MergeDeficitRoundRobin`] trait. See its documentation for more.
+///
+/// [`merge_drr`]: MergeDeficitRoundRobin::merge_drr
+#[must_use = "streams do nothing unless you `.await` or poll them"]
+#[pin_project]
+pub struct MergeDrr<S, W>
+where
+    S: Stream,
+{
+    #[pin]
+    streams: Vec<S>,
+    wakers: WakerVec,
+    state: PollVec,
+    complete: usize,
+    /// Items which were too heavy for their stream's deficit.
+    peeked: Vec<Option<S::Item>>,
+    /// The weight each stream may still yield.
+    deficits: Vec<u64>,
+    quantum: u64,
+    weight: W,
+    /// The stream whose turn it is.
+    cursor: usize,
+    /// Whether the stream at `cursor` was granted its quantum this turn.
+    granted: bool,
+}
+
+impl<S, W> MergeDrr<S, W>
+where
+    S: Stream,
+{
+    pub(crate) fn new(streams: Vec<S>, quantum: u32, weight: W) -> Self {
+        assert!(quantum > 0, "merge_drr requires a non-zero quantum");
+        let len = streams.len();
+        Self {
+            streams,
+            wakers: WakerVec::new(len),
+            state: PollVec::new(len),
+            complete: 0,
+            peeked: (0..len).map(|_| None).collect(),
+            deficits: vec![0; len],
+            quantum: u64::from(quantum),
+            weight,
+            cursor: 0,
+            granted: false,
+        }
+    }
+}
+
+impl<S, W> fmt::Debug for MergeDrr<S, W>
+where
+    S: Stream + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MergeDrr")
+            .field("streams", &self.streams)
+            .field("deficits", &self.deficits)
+            .field("quantum", &self.quantum)
+            .finish()
+    }
+}
+
+impl<S, W> Stream for MergeDrr<S, W>
+where
+    S: Stream,
+    W: FnMut(&S::Item) -> u32,
+{
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+        let len = this.streams.len();
+
+        this.wakers
+            .readiness()
+            .lock()
+            .unwrap()
+            .set_waker(cx.waker());
+
+        // The number of turns in a row which ended without an item waiting
+        // for credit. Once every stream has had such a turn, nothing can make
+        // progress until a stream is woken.
+        let mut idle = 0;
+        while *this.complete < len && idle < len {
+            let index = *this.cursor;
+
+            if this.state[index].is_consumed() {
+                advance(this.cursor, this.granted, len);
+                idle += 1;
+                continue;
+            }
+
+            // Each turn grants the stream one quantum of credit, which it
+            // keeps until it has nothing left to yield.
+            if !*this.granted {
+                this.deficits[index] += *this.quantum;
+                *this.granted = true;
+            }
+
+            let item = match this.peeked[index].take() {
+                Some(item) => Some(item),
+                None => {
+                    let ready = this.wakers.readiness().lock().unwrap().clear_ready(index);
+                    if !ready {
+                        None
+                    } else {
+                        let mut cx = Context::from_waker(this.wakers.get(index).unwrap());
+                        let stream =
+                            utils::get_pin_mut_from_vec(this.streams.as_mut(), index).unwrap();
+                        match stream.poll_next(&mut cx) {
+                            Poll::Ready(Some(item)) => {
+                                // Poll for the next item during the next turn.
+                                this.wakers.readiness().lock().unwrap().set_ready(index);
+                                Some(item)
+                            }
+                            Poll::Ready(None) => {
+                                this.state[index].set_consumed();
+                                *this.complete += 1;
+                                None
+                            }
+                            Poll::Pending => None,
+                        }
+                    }
+                }
+            };
+
+            match item {
+                Some(item) => {
+                    let weight = u64::from((this.weight)(&item));
+                    if weight <= this.deficits[index] {
+                        this.deficits[index] -= weight;
+                        return Poll::Ready(Some(item));
+                    }
+                    // Not enough credit: keep the item for one of the
+                    // stream's next turns, which gives it more credit.
+                    this.peeked[index] = Some(item);
+                    idle = 0;
+                }
+                None => {
+                    // A stream with nothing to yield doesn't bank credit.
+                    this.deficits[index] = 0;
+                    idle += 1;
+                }
+            }
+            advance(this.cursor, this.granted, len);
+        }
+
+        if *this.complete == len {
+            Poll::Ready(None)
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+/// End the current turn, passing it to the next stream.
+fn advance(cursor: &mut usize, granted: &mut bool, len: usize) {
+    *cursor = (*cursor + 1) % len;
+    *granted = false;
+}
+
+#[cfg(test)]
+mod test {
+    use super::MergeDeficitRoundRobin;
+    use futures_lite::future::block_on;
+    use futures_lite::stream::{self, StreamExt};
+
+    #[test]
+    fn shares_weight_fairly() {
+        block_on(async {
+            // One stream yields heavy items, the other light ones. Plain
+            // round-robin would alternate them, giving the heavy stream 4
+            // times the bandwidth.
+            let heavy = stream::repeat(4).take(4);
+            let light = stream::repeat(1).take(16);
+            let items: Vec<u32> = vec![heavy, light].merge_drr(4, |n| *n).collect().await;
+
+            assert_eq!(items.len(), 20);
+            for prefix in [5, 10, 15] {
+                let heavy: u32 = items[..prefix].iter().filter(|n| **n == 4).sum();
+                let light: u32 = items[..prefix].iter().filter(|n| **n == 1).sum();
+                assert!(heavy.abs_diff(light) <= 8, "{:?}", &items[..prefix]);
+            }
+        })
+    }
+
+    #[test]
+    fn heavy_items_wait_for_credit() {
+        block_on(async {
+            let items: Vec<u32> = vec![stream::iter(vec![10, 1])]
+                .merge_drr(3, |n| *n)
+                .collect()
+                .await;
+            assert_eq!(items, vec![10, 1]);
+        })
+    }
+
+    #[test]
+    fn empty() {
+        block_on(async {
+            let streams: Vec<stream::Empty<u32>> = vec![];
+            let items: Vec<u32> = streams.merge_drr(1, |n| *n).collect().await;
+            assert!(items.is_empty());
+        })
+    }
+}
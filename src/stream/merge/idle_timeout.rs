@@ -0,0 +1,139 @@
+use super::vec::Merge;
+use crate::future::{Elapsed, Timer};
+
+use core::fmt;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use core::time::Duration;
+use futures_core::Stream;
+
+use pin_project::pin_project;
+
+/// A merged stream which ends once no stream has yielded an item for a
+/// while.
+///
+/// This `struct` is created by the [`idle_timeout`] method on [`Merge`]. See
+/// its documentation for more.
+///
+/// [`idle_timeout`]: Merge::idle_timeout
+#[must_use = "streams do nothing unless you `.await` or poll them"]
+#[pin_project]
+pub struct IdleTimeout<S, T>
+where
+    S: Stream,
+    T: Timer,
+{
+    #[pin]
+    merge: Merge<S>,
+    #[pin]
+    sleep: T::Sleep,
+    timer: T,
+    dur: Duration,
+    done: bool,
+}
+
+impl<S, T> IdleTimeout<S, T>
+where
+    S: Stream,
+    T: Timer,
+{
+    pub(crate) fn new(merge: Merge<S>, dur: Duration, mut timer: T) -> Self {
+        Self {
+            sleep: timer.sleep(dur),
+            merge,
+            timer,
+            dur,
+            done: false,
+        }
+    }
+}
+
+impl<S, T> fmt::Debug for IdleTimeout<S, T>
+where
+    S: Stream + fmt::Debug,
+    T: Timer,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IdleTimeout")
+            .field("merge", &self.merge)
+            .field("dur", &self.dur)
+            .finish()
+    }
+}
+
+impl<S, T> Stream for IdleTimeout<S, T>
+where
+    S: Stream,
+    T: Timer,
+{
+    type Item = Result<S::Item, Elapsed>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+        if *this.done {
+            return Poll::Ready(None);
+        }
+
+        // Always poll the streams first, so that an item which is ready at
+        // the same time as the deadline isn't discarded.
+        match this.merge.poll_next(cx) {
+            Poll::Ready(Some(item)) => {
+                this.sleep.set(this.timer.sleep(*this.dur));
+                return Poll::Ready(Some(Ok(item)));
+            }
+            Poll::Ready(None) => {
+                *this.done = true;
+                return Poll::Ready(None);
+            }
+            Poll::Pending => {}
+        }
+
+        if this.sleep.as_mut().poll(cx).is_ready() {
+            *this.done = true;
+            return Poll::Ready(Some(Err(Elapsed::new())));
+        }
+
+        Poll::Pending
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::stream::Merge as _;
+    use futures_lite::future::{block_on, pending};
+    use futures_lite::stream::{self, StreamExt};
+
+    #[test]
+    fn ends_after_idle_period() {
+        block_on(async {
+            let a = stream::once(1).chain(stream::pending());
+            let b = stream::once(2).chain(stream::pending());
+            let timer = |_| futures_lite::future::yield_now();
+            let items: Vec<_> = vec![a, b]
+                .merge()
+                .idle_timeout(Duration::from_secs(1), timer)
+                .collect()
+                .await;
+
+            assert_eq!(items.len(), 3);
+            assert!(items[..2].iter().all(Result::is_ok));
+            assert_eq!(items[2], Err(Elapsed::new()));
+        })
+    }
+
+    #[test]
+    fn ends_with_streams() {
+        block_on(async {
+            let a = stream::iter(vec![1, 2]);
+            let timer = |_| pending::<()>();
+            let items: Vec<_> = vec![a]
+                .merge()
+                .idle_timeout(Duration::from_secs(1), timer)
+                .collect()
+                .await;
+            assert_eq!(items, vec![Ok(1), Ok(2)]);
+        })
+    }
+}
@@ -0,0 +1,172 @@
+use super::vec::Merge;
+use crate::stream::IntoStream;
+
+use core::fmt;
+use core::pin::Pin;
+use core::task::{ready, Context, Poll};
+use futures_core::Stream;
+
+use pin_project::pin_project;
+
+/// Combine multiple streams into a single stream, labeling each item with
+/// the key of the stream it came from.
+///
+/// This is implemented for vectors of `(key, stream)` pairs.
+pub trait MergeKeyed {
+    /// The key each stream is labeled with.
+    type Key;
+
+    /// The stream type being merged.
+    type Stream: Stream;
+
+    /// Combine multiple streams into a single stream of `(key, item)` pairs.
+    ///
+    /// Each item is yielded together with a clone of the key that its
+    /// stream was paired with. Unlike the index of a stream, a key stays
+    /// meaningful as streams are exhausted or added with
+    /// [`KeyedMerge::push`]. Items are yielded as soon as they're ready,
+    /// like in a regular [`Merge`][crate::stream::Merge].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_concurrency::prelude::*;
+    /// use futures_lite::future::block_on;
+    /// use futures_lite::stream::{self, StreamExt};
+    ///
+    /// block_on(async {
+    ///     let feeds = vec![("news", stream::once(1)), ("sports", stream::once(2))];
+    ///     let mut items: Vec<_> = feeds.merge_keyed().collect().await;
+    ///     items.sort_unstable();
+    ///     assert_eq!(items, vec![("news", 1), ("sports", 2)]);
+    /// })
+    /// ```
+    #[must_use = "streams do nothing unless you `.await` or poll them"]
+    fn merge_keyed(self) -> KeyedMerge<Self::Key, Self::Stream>;
+}
+
+/// A stream which merges multiple streams, labeling each item with the key
+/// of the stream it came from.
+///
+/// This `struct` is created by the [`merge_keyed`] method on the
+/// [`MergeKeyed`] trait. See its documentation for more.
+///
+/// [`merge_keyed`]: MergeKeyed::merge_keyed
+#[must_use = "streams do nothing unless you `.await` or poll them"]
+#[pin_project]
+pub struct KeyedMerge<K, S>
+where
+    S: Stream,
+{
+    /// The key of each stream, by index in the merge.
+    keys: Vec<K>,
+    #[pin]
+    merge: Merge<S>,
+}
+
+impl<K, S> KeyedMerge<K, S>
+where
+    S: Stream + Unpin,
+{
+    /// Add a stream to the merge, labeled with `key`.
+    ///
+    /// This works on a merge which has already been polled, like
+    /// [`Merge::push`]. Keys don't have to be unique, but items from streams
+    /// sharing a key can't be told apart.
+    pub fn push(&mut self, key: K, stream: S) {
+        self.merge.push(stream);
+        self.keys.push(key);
+    }
+}
+
+impl<K, S> fmt::Debug for KeyedMerge<K, S>
+where
+    K: fmt::Debug,
+    S: Stream + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeyedMerge")
+            .field("keys", &self.keys)
+            .field("merge", &self.merge)
+            .finish()
+    }
+}
+
+impl<K, S> Stream for KeyedMerge<K, S>
+where
+    K: Clone,
+    S: Stream,
+{
+    type Item = (K, S::Item);
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        let keys = this.keys;
+        // Streams never move within the merge, so their index maps to the
+        // same key for as long as the merge lives.
+        let item = ready!(this.merge.poll_next_indexed(cx));
+        Poll::Ready(item.map(|(index, item)| (keys[index].clone(), item)))
+    }
+}
+
+impl<K, S> MergeKeyed for Vec<(K, S)>
+where
+    S: IntoStream,
+{
+    type Key = K;
+    type Stream = S::IntoStream;
+
+    fn merge_keyed(self) -> KeyedMerge<K, Self::Stream> {
+        let (keys, streams): (Vec<_>, Vec<_>) = self
+            .into_iter()
+            .map(|(key, stream)| (key, stream.into_stream()))
+            .unzip();
+        KeyedMerge {
+            keys,
+            merge: Merge::new(streams),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use futures_lite::future::block_on;
+    use futures_lite::stream::{self, StreamExt};
+
+    #[test]
+    fn keys_survive_completion_and_push() {
+        block_on(async {
+            let feeds = vec![
+                ("a", stream::iter(vec![1]).boxed()),
+                ("b", stream::iter(vec![2, 3, 4]).boxed()),
+                ("c", stream::iter(vec![5, 6]).boxed()),
+            ];
+            let mut s = feeds.merge_keyed();
+
+            // Once `a` has yielded its only item it's exhausted, and dropped
+            // from the merge's rotation while the others keep going.
+            let mut items = vec![];
+            while !items.contains(&("a", 1)) {
+                items.push(s.next().await.unwrap());
+            }
+
+            s.push("d", stream::iter(vec![7]).boxed());
+            while let Some(item) = s.next().await {
+                items.push(item);
+            }
+
+            items.sort_unstable();
+            let expected = vec![
+                ("a", 1),
+                ("b", 2),
+                ("b", 3),
+                ("b", 4),
+                ("c", 5),
+                ("c", 6),
+                ("d", 7),
+            ];
+            assert_eq!(items, expected);
+        })
+    }
+}
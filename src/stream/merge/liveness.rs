@@ -0,0 +1,82 @@
+use super::vec::Merge;
+
+use core::fmt;
+use core::pin::Pin;
+use core::task::{ready, Context, Poll};
+use futures_core::Stream;
+
+use pin_project::pin_project;
+
+/// A merged stream which pairs each item with the number of streams which
+/// are still live.
+///
+/// This `struct` is created by the [`merge_with_liveness`] method on
+/// [`Merge`]. See its documentation for more.
+///
+/// [`merge_with_liveness`]: Merge::merge_with_liveness
+#[must_use = "streams do nothing unless you `.await` or poll them"]
+#[pin_project]
+pub struct WithLiveness<S>
+where
+    S: Stream,
+{
+    #[pin]
+    merge: Merge<S>,
+}
+
+impl<S> WithLiveness<S>
+where
+    S: Stream,
+{
+    pub(crate) fn new(merge: Merge<S>) -> Self {
+        Self { merge }
+    }
+}
+
+impl<S> fmt::Debug for WithLiveness<S>
+where
+    S: Stream + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WithLiveness")
+            .field("merge", &self.merge)
+            .finish()
+    }
+}
+
+impl<S> Stream for WithLiveness<S>
+where
+    S: Stream,
+{
+    type Item = (S::Item, usize);
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+        let item = ready!(this.merge.as_mut().poll_next(cx));
+        Poll::Ready(item.map(|item| (item, this.merge.as_ref().get_ref().live())))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::stream::Merge as _;
+    use futures_lite::future::block_on;
+    use futures_lite::stream::{self, StreamExt};
+
+    #[test]
+    fn counts_live_streams() {
+        block_on(async {
+            let a = stream::once(1).boxed();
+            let b = stream::iter(vec![2, 3]).chain(stream::pending()).boxed();
+            let mut s = vec![a, b].merge().merge_with_liveness();
+
+            let mut items = vec![];
+            for _ in 0..3 {
+                items.push(s.next().await.unwrap());
+            }
+            // `a` is live until it reports its end, while `b` never ends.
+            assert_eq!(items[0], (1, 2));
+            assert_eq!(items.last(), Some(&(3, 1)));
+        })
+    }
+}
@@ -1,9 +1,31 @@
+use crate::stream::IntoStream;
 use futures_core::Stream;
 
 pub(crate) mod array;
+pub(crate) mod by_key;
+pub(crate) mod catch_unwind;
+pub(crate) mod circuit_break;
+pub(crate) mod drr;
+pub(crate) mod idle_timeout;
+pub(crate) mod keyed;
+pub(crate) mod liveness;
+pub(crate) mod pair;
+mod policy;
+pub(crate) mod rate_limit;
+pub(crate) mod round_robin;
+mod sequenced;
+pub(crate) mod summary;
+pub(crate) mod then;
 pub(crate) mod tuple;
 pub(crate) mod vec;
 
+pub use by_key::MergeSorted;
+pub use drr::MergeDeficitRoundRobin;
+pub use keyed::MergeKeyed;
+pub use pair::Merge2;
+pub use round_robin::MergeRoundRobin;
+pub use sequenced::Sequenced;
+
 /// Combines multiple streams into a single stream of all their outputs.
 ///
 /// Items are yielded as soon as they're received, and the stream continues
@@ -29,6 +51,31 @@ pub(crate) mod vec;
 ///     assert_eq!(&buf, &[1, 2, 3]);
 /// })
 /// ```
+///
+/// # Merging by reference
+///
+/// When all streams being merged are `Unpin`, the merged stream is `Unpin` as
+/// well. This means `&mut` references to it implement `Stream` too, which
+/// makes it possible to consume part of a merged stream while keeping
+/// ownership of it for later:
+///
+/// ```
+/// use futures_concurrency::prelude::*;
+/// use futures_lite::stream::{self, StreamExt};
+/// use futures_lite::future::block_on;
+///
+/// block_on(async {
+///     let a = stream::iter([1, 2]);
+///     let b = stream::iter([3, 4]);
+///     let mut s = [a, b].merge();
+///
+///     let first: Vec<_> = (&mut s).take(3).collect().await;
+///     assert_eq!(first.len(), 3);
+///
+///     let rest: Vec<_> = s.collect().await;
+///     assert_eq!(rest.len(), 1);
+/// })
+/// ```
 pub trait Merge {
     /// The resulting output type.
     type Item;
@@ -38,4 +85,110 @@ pub trait Merge {
 
     /// Combine multiple streams into a single stream.
     fn merge(self) -> Self::Stream;
+
+    /// Combine multiple streams into a single stream, pairing each item with
+    /// a sequence number.
+    ///
+    /// Sequence numbers are assigned as items are yielded, starting at `0`
+    /// and increasing by one for each item. They reflect the order in which
+    /// items left this merge, not which stream they came from, and are only
+    /// meaningful within a single merge.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_concurrency::prelude::*;
+    /// use futures_lite::stream::{self, StreamExt};
+    /// use futures_lite::future::block_on;
+    ///
+    /// block_on(async {
+    ///     let a = stream::once("a");
+    ///     let b = stream::once("b");
+    ///     let seqs: Vec<_> = vec![a, b]
+    ///         .merge_sequenced()
+    ///         .map(|(seq, _)| seq)
+    ///         .collect()
+    ///         .await;
+    ///     assert_eq!(seqs, vec![0, 1]);
+    /// })
+    /// ```
+    #[must_use = "streams do nothing unless you `.await` or poll them"]
+    fn merge_sequenced(self) -> Sequenced<Self::Stream>
+    where
+        Self: Sized,
+    {
+        Sequenced::new(self.merge())
+    }
+}
+
+/// Merge a stream with one other stream.
+///
+/// This is a faster path for the common case of merging exactly two streams,
+/// such as an event stream and a control stream. The streams are stored
+/// inline and polled directly with the caller's waker, so no per-stream
+/// wakers need to be allocated. The trade-off is that both streams are polled
+/// on every wakeup, which only pays off for small numbers of streams; use
+/// [`Merge`] for more.
+pub trait MergeExt: Stream {
+    /// Combine this stream with `other` into a single stream.
+    ///
+    /// Items are yielded as soon as either stream has one ready, and the
+    /// stream which gets polled first alternates between calls. The merged
+    /// stream ends once both streams have ended.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_concurrency::prelude::*;
+    /// use futures_lite::stream::{self, StreamExt};
+    /// use futures_lite::future::block_on;
+    ///
+    /// block_on(async {
+    ///     let events = stream::iter(vec![1, 2]);
+    ///     let control = stream::once(3);
+    ///
+    ///     let mut items: Vec<_> = events.merge(control).collect().await;
+    ///     items.sort_unstable();
+    ///     assert_eq!(items, vec![1, 2, 3]);
+    /// })
+    /// ```
+    #[must_use = "streams do nothing unless you `.await` or poll them"]
+    fn merge<S>(self, other: S) -> Merge2<Self, S::IntoStream>
+    where
+        Self: Sized,
+        S: IntoStream<Item = Self::Item>,
+    {
+        Merge2::new(self, other.into_stream())
+    }
+}
+
+impl<S: Stream> MergeExt for S {}
+
+#[cfg(test)]
+mod test {
+    use super::Merge;
+    use futures_lite::future::block_on;
+    use futures_lite::stream::{self, StreamExt};
+
+    /// Consume part of `s` by reference, then the rest by value.
+    async fn consume_by_ref<S>(mut s: S)
+    where
+        S: futures_core::Stream<Item = i32> + Unpin,
+    {
+        let first: Vec<_> = (&mut s).take(3).collect().await;
+        let rest: Vec<_> = s.collect().await;
+        assert_eq!(first.len(), 3);
+        assert_eq!(first.iter().chain(&rest).sum::<i32>(), 6);
+    }
+
+    #[test]
+    fn merge_by_ref() {
+        block_on(async {
+            let a = || stream::repeat(1).take(2);
+            let b = || stream::repeat(2).take(2);
+            consume_by_ref([a(), b()].merge()).await;
+            consume_by_ref((a(), b()).merge()).await;
+            consume_by_ref(vec![a(), b()].merge()).await;
+        })
+    }
 }
@@ -0,0 +1,145 @@
+use core::fmt;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures_core::Stream;
+
+use pin_project::pin_project;
+
+/// A stream which merges two streams into a single stream.
+///
+/// This `struct` is created by the [`merge`] method on the [`MergeExt`]
+/// trait. See its documentation for more.
+///
+/// [`merge`]: super::MergeExt::merge
+/// [`MergeExt`]: super::MergeExt
+#[must_use = "streams do nothing unless you `.await` or poll them"]
+#[pin_project]
+pub struct Merge2<A, B> {
+    #[pin]
+    a: A,
+    #[pin]
+    b: B,
+    a_done: bool,
+    b_done: bool,
+    /// Whether `b` gets polled first on the next call.
+    b_first: bool,
+}
+
+impl<A, B> Merge2<A, B> {
+    pub(crate) fn new(a: A, b: B) -> Self {
+        Self {
+            a,
+            b,
+            a_done: false,
+            b_done: false,
+            b_first: false,
+        }
+    }
+}
+
+impl<A, B> fmt::Debug for Merge2<A, B>
+where
+    A: fmt::Debug,
+    B: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Merge2")
+            .field(&self.a)
+            .field(&self.b)
+            .finish()
+    }
+}
+
+impl<A, B> Stream for Merge2<A, B>
+where
+    A: Stream,
+    B: Stream<Item = A::Item>,
+{
+    type Item = A::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        // Alternate which stream goes first, so neither can starve the other.
+        let b_first = *this.b_first && !cfg!(feature = "test-determinism");
+        *this.b_first = !*this.b_first;
+
+        for &poll_b in &[b_first, !b_first] {
+            let item = if poll_b {
+                poll_side(this.b.as_mut(), this.b_done, cx)
+            } else {
+                poll_side(this.a.as_mut(), this.a_done, cx)
+            };
+            if let Some(item) = item {
+                return Poll::Ready(Some(item));
+            }
+        }
+
+        if *this.a_done && *this.b_done {
+            Poll::Ready(None)
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+crate::utils::impl_boxed_stream!({A, B} Merge2<A, B>);
+
+/// Poll one of the streams unless it's exhausted, returning its next item if
+/// it has one ready.
+///
+/// Both streams are polled with the merge's own context, so a wakeup from
+/// either one polls both. Per-stream wakers can't be stored inline in the
+/// merge, since a waker may outlive it: they'd need a shared allocation like
+/// the other merges use. With only two streams, one spurious poll is cheaper
+/// than that allocation and the bookkeeping to find out which stream was
+/// woken.
+fn poll_side<S: Stream>(
+    stream: Pin<&mut S>,
+    done: &mut bool,
+    cx: &mut Context<'_>,
+) -> Option<S::Item> {
+    if *done {
+        return None;
+    }
+    match stream.poll_next(cx) {
+        Poll::Ready(Some(item)) => Some(item),
+        Poll::Ready(None) => {
+            *done = true;
+            None
+        }
+        Poll::Pending => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::stream::MergeExt as _;
+    use futures_lite::future::block_on;
+    use futures_lite::stream::{self, StreamExt};
+
+    #[test]
+    fn alternates() {
+        block_on(async {
+            let a = stream::repeat(1).take(3);
+            let b = stream::repeat(2).take(3);
+            let items: Vec<_> = a.merge(b).collect().await;
+            if cfg!(feature = "test-determinism") {
+                assert_eq!(items, vec![1, 1, 1, 2, 2, 2]);
+            } else {
+                assert_eq!(items, vec![1, 2, 1, 2, 1, 2]);
+            }
+        })
+    }
+
+    #[test]
+    fn continues_after_one_ends() {
+        block_on(async {
+            let a = stream::once(1);
+            let b = stream::iter(vec![2, 3, 4]);
+            let mut items: Vec<_> = a.merge(b).collect().await;
+            items.sort_unstable();
+            assert_eq!(items, vec![1, 2, 3, 4]);
+        })
+    }
+}
@@ -0,0 +1,249 @@
+//! The state behind the options of the vec [`Merge`][super::vec::Merge].
+//!
+//! Each option keeps its state in its own type here, so the merge's poll
+//! loop only asks them what to do with a stream.
+
+use crate::utils::{IndexIter, Indexer};
+
+use bitvec::{bitvec, vec::BitVec};
+use std::collections::VecDeque;
+
+/// The order a merge polls its streams in.
+pub(crate) enum Order {
+    /// Rotate the starting point by one on every poll.
+    Rotating,
+    /// Resume from the stream after the one which yielded last.
+    RoundRobin {
+        /// The index of the stream to resume from. It's a stream index
+        /// rather than a position, so it doesn't go stale as exhausted
+        /// streams are compacted away.
+        next: usize,
+    },
+    /// Poll every stream at most once per cycle.
+    RealTime {
+        /// The position to resume polling at.
+        cursor: usize,
+        /// The streams which have been polled during the current cycle.
+        visited: BitVec,
+    },
+}
+
+impl Order {
+    pub(crate) fn real_time(len: usize) -> Self {
+        Order::RealTime {
+            cursor: 0,
+            visited: bitvec![0; len],
+        }
+    }
+
+    pub(crate) fn is_real_time(&self) -> bool {
+        matches!(self, Order::RealTime { .. })
+    }
+
+    /// The positions in `active` to poll, in order.
+    pub(crate) fn positions(&self, indexer: &mut Indexer, active: &[usize]) -> IndexIter {
+        match self {
+            Order::Rotating => indexer.iter(),
+            Order::RoundRobin { next } => {
+                let pos = active.partition_point(|&index| index < *next);
+                indexer.iter_from(pos.checked_rem(active.len()).unwrap_or(0))
+            }
+            Order::RealTime { cursor, .. } => indexer.iter_from(*cursor),
+        }
+    }
+
+    /// Keep the cursor in range after exhausted streams were dropped from the
+    /// `len` active ones.
+    pub(crate) fn compacted(&mut self, len: usize) {
+        if let Order::RealTime { cursor, .. } = self {
+            *cursor = cursor.checked_rem(len).unwrap_or(0);
+        }
+    }
+
+    /// Returns `true` if the stream at `index` already had its turn during
+    /// this cycle.
+    pub(crate) fn visited(&self, index: usize) -> bool {
+        match self {
+            Order::RealTime { visited, .. } => visited[index],
+            _ => false,
+        }
+    }
+
+    /// Record that the stream at `index` is being polled.
+    pub(crate) fn visit(&mut self, index: usize) {
+        if let Order::RealTime { visited, .. } = self {
+            visited.set(index, true);
+        }
+    }
+
+    /// Record that the stream at `index`, found at position `pos` out of
+    /// `len` active streams, yielded an item.
+    pub(crate) fn yielded(&mut self, pos: usize, index: usize, len: usize) {
+        match self {
+            Order::Rotating => {}
+            Order::RoundRobin { next } => *next = index + 1,
+            Order::RealTime { cursor, .. } => *cursor = (pos + 1) % len,
+        }
+    }
+
+    /// End the current cycle, so every stream may be polled again.
+    pub(crate) fn end_cycle(&mut self) {
+        if let Order::RealTime { visited, .. } = self {
+            visited.fill(false);
+        }
+    }
+
+    pub(crate) fn push(&mut self) {
+        if let Order::RealTime { visited, .. } = self {
+            visited.push(false);
+        }
+    }
+}
+
+/// The streams which are polled before any of the others.
+pub(crate) struct Priority {
+    /// The priority streams, in the order to poll them.
+    order: Vec<usize>,
+    streams: BitVec,
+}
+
+impl Priority {
+    pub(crate) fn new(len: usize) -> Self {
+        Self {
+            order: Vec::new(),
+            streams: bitvec![0; len],
+        }
+    }
+
+    /// Add the stream at `index` after the current priority streams.
+    pub(crate) fn add(&mut self, index: usize) {
+        if !self.streams.replace(index, true) {
+            self.order.push(index);
+        }
+    }
+
+    pub(crate) fn contains(&self, index: usize) -> bool {
+        self.streams[index]
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.order.iter().copied()
+    }
+
+    pub(crate) fn push(&mut self) {
+        self.streams.push(false);
+    }
+}
+
+/// The streams which only keep their newest item, along with that item.
+pub(crate) struct Latest<T> {
+    streams: BitVec,
+    /// One slot per stream, only allocated once a stream is added.
+    slots: Vec<Option<T>>,
+}
+
+impl<T> Latest<T> {
+    pub(crate) fn new(len: usize) -> Self {
+        Self {
+            streams: bitvec![0; len],
+            slots: Vec::new(),
+        }
+    }
+
+    /// Only keep the newest item of the stream at `index`, out of `len`.
+    pub(crate) fn add(&mut self, index: usize, len: usize) {
+        self.streams.set(index, true);
+        if self.slots.is_empty() {
+            self.slots.resize_with(len, || None);
+        }
+    }
+
+    pub(crate) fn contains(&self, index: usize) -> bool {
+        self.streams[index]
+    }
+
+    /// Hold on to `item`, replacing any older item of the same stream.
+    pub(crate) fn hold(&mut self, index: usize, item: T) {
+        self.slots[index] = Some(item);
+    }
+
+    /// Take out the item held for the stream at `index`.
+    pub(crate) fn take(&mut self, index: usize) -> Option<T> {
+        self.slots.get_mut(index).and_then(Option::take)
+    }
+
+    /// Take out every held item, in index order.
+    pub(crate) fn drain(&mut self) -> impl Iterator<Item = T> + '_ {
+        self.slots.iter_mut().filter_map(Option::take)
+    }
+
+    pub(crate) fn map<U>(self, mut f: impl FnMut(T) -> U) -> Latest<U> {
+        Latest {
+            streams: self.streams,
+            slots: self
+                .slots
+                .into_iter()
+                .map(|slot| slot.map(&mut f))
+                .collect(),
+        }
+    }
+
+    pub(crate) fn push(&mut self) {
+        self.streams.push(false);
+        if !self.slots.is_empty() {
+            self.slots.push(None);
+        }
+    }
+}
+
+/// The items queued up by a batched merge, along with their stream's index.
+pub(crate) struct Batch<T> {
+    enabled: bool,
+    queue: VecDeque<(usize, T)>,
+}
+
+impl<T> Batch<T> {
+    pub(crate) fn new() -> Self {
+        Self {
+            enabled: false,
+            queue: VecDeque::new(),
+        }
+    }
+
+    pub(crate) fn enable(&mut self) {
+        self.enabled = true;
+    }
+
+    /// Queue up `item` if batching is enabled, or hand it back otherwise.
+    pub(crate) fn push(&mut self, index: usize, item: T) -> Option<(usize, T)> {
+        if !self.enabled {
+            return Some((index, item));
+        }
+        self.queue.push_back((index, item));
+        None
+    }
+
+    pub(crate) fn pop(&mut self) -> Option<(usize, T)> {
+        self.queue.pop_front()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// Take out every queued item, oldest first.
+    pub(crate) fn drain(&mut self) -> impl Iterator<Item = T> + '_ {
+        self.queue.drain(..).map(|(_, item)| item)
+    }
+
+    pub(crate) fn map<U>(self, mut f: impl FnMut(T) -> U) -> Batch<U> {
+        Batch {
+            enabled: self.enabled,
+            queue: self
+                .queue
+                .into_iter()
+                .map(|(index, item)| (index, f(item)))
+                .collect(),
+        }
+    }
+}
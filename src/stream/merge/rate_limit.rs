@@ -0,0 +1,163 @@
+use super::vec::Merge;
+use crate::future::Timer;
+use crate::utils;
+
+use core::fmt;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use core::time::Duration;
+use futures_core::Stream;
+
+use pin_project::pin_project;
+
+/// A merged stream which limits how often some of its streams are polled.
+///
+/// This `struct` is created by the [`rate_limit`] method on [`Merge`]. See
+/// its documentation for more.
+///
+/// [`rate_limit`]: Merge::rate_limit
+#[must_use = "streams do nothing unless you `.await` or poll them"]
+#[pin_project]
+pub struct RateLimited<S, T>
+where
+    S: Stream,
+    T: Timer,
+{
+    #[pin]
+    merge: Merge<S>,
+    /// The minimum interval between items of each stream, if it's limited.
+    intervals: Vec<Option<Duration>>,
+    /// The sleeps holding back the streams which have yielded too recently.
+    /// This has one slot per stream and is never resized, so the sleeps
+    /// stay in place.
+    #[pin]
+    sleeps: Vec<Option<T::Sleep>>,
+    timer: T,
+}
+
+impl<S, T> RateLimited<S, T>
+where
+    S: Stream,
+    T: Timer,
+{
+    pub(crate) fn new(merge: Merge<S>, len: usize, timer: T) -> Self {
+        Self {
+            merge,
+            intervals: vec![None; len],
+            sleeps: (0..len).map(|_| None).collect(),
+            timer,
+        }
+    }
+
+    /// Also limit the stream at `index` to one item per `min_interval`.
+    ///
+    /// This uses the same timer as the other rate-limited streams. Setting
+    /// the limit of a stream which is already limited replaces it.
+    ///
+    /// # Panics
+    ///
+    /// This panics if `index` is out of bounds.
+    pub fn rate_limit(mut self, index: usize, min_interval: Duration) -> Self {
+        self.intervals[index] = Some(min_interval);
+        self
+    }
+}
+
+impl<S, T> fmt::Debug for RateLimited<S, T>
+where
+    S: Stream + fmt::Debug,
+    T: Timer,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RateLimited")
+            .field("merge", &self.merge)
+            .field("intervals", &self.intervals)
+            .finish()
+    }
+}
+
+impl<S, T> Stream for RateLimited<S, T>
+where
+    S: Stream,
+    T: Timer,
+{
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        // Let the streams whose interval has elapsed be polled again.
+        for index in 0..this.intervals.len() {
+            let mut slot = utils::get_pin_mut_from_vec(this.sleeps.as_mut(), index).unwrap();
+            let elapsed = match slot.as_mut().as_pin_mut() {
+                Some(sleep) => sleep.poll(cx).is_ready(),
+                None => false,
+            };
+            if elapsed {
+                slot.set(None);
+                this.merge.as_mut().set_paused(index, false);
+            }
+        }
+
+        match this.merge.as_mut().poll_next_indexed(cx) {
+            Poll::Ready(Some((index, item))) => {
+                if let Some(interval) = this.intervals[index] {
+                    // Hold the stream back until its interval has elapsed.
+                    let sleep = this.timer.sleep(interval);
+                    let mut slot =
+                        utils::get_pin_mut_from_vec(this.sleeps.as_mut(), index).unwrap();
+                    slot.set(Some(sleep));
+                    this.merge.as_mut().set_paused(index, true);
+                    // Register the sleep's waker straight away.
+                    if slot.as_pin_mut().unwrap().poll(cx).is_ready() {
+                        cx.waker().wake_by_ref();
+                    }
+                }
+                Poll::Ready(Some(item))
+            }
+            Poll::Ready(None) => Poll::Ready(None),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::stream::Merge as _;
+    use futures_lite::future::{block_on, yield_now};
+    use futures_lite::stream::{self, StreamExt};
+    use std::cell::Cell;
+    use std::time::Duration;
+
+    #[test]
+    fn limited_stream_waits_for_its_interval() {
+        block_on(async {
+            let slow = stream::iter(vec![1, 1, 1]);
+            let fast = stream::iter(vec![2, 2, 2, 2, 2, 2]);
+            let sleeps = Cell::new(0);
+            // Each interval lasts a few polls of the merge.
+            let timer = |_: Duration| {
+                sleeps.set(sleeps.get() + 1);
+                async {
+                    for _ in 0..3 {
+                        yield_now().await;
+                    }
+                }
+            };
+            let items: Vec<_> = vec![slow, fast]
+                .merge()
+                .rate_limit(0, Duration::from_secs(1), timer)
+                .collect()
+                .await;
+
+            assert_eq!(items.iter().filter(|&&n| n == 1).count(), 3);
+            assert_eq!(items.iter().filter(|&&n| n == 2).count(), 6);
+            // The unlimited stream ran ahead while the limited one waited.
+            let first = items.iter().position(|&n| n == 1).unwrap();
+            let second = items[first + 1..].iter().position(|&n| n == 1).unwrap();
+            assert!(second > 0, "{:?}", items);
+            assert_eq!(sleeps.get(), 3);
+        })
+    }
+}
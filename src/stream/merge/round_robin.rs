@@ -0,0 +1,99 @@
+use super::vec::Merge;
+use super::Merge as _;
+use crate::stream::IntoStream;
+
+/// Combine multiple streams into a single stream, taking turns in a fixed
+/// order.
+///
+/// This is implemented for vectors of streams.
+pub trait MergeRoundRobin {
+    /// The stream type being merged.
+    type Stream: futures_core::Stream;
+
+    /// Combine multiple streams into a single stream, polling them in strict
+    /// index order.
+    ///
+    /// A regular [`merge`][crate::stream::Merge::merge] rotates the stream it
+    /// starts from by one on every poll. This merge instead remembers which
+    /// stream yielded last, and resumes from the stream after it on the next
+    /// poll, wrapping around after the last one. Streams which aren't ready
+    /// or have ended are skipped. Given the same readiness, the items are
+    /// always interleaved the same way, which keeps tests reproducible.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_concurrency::prelude::*;
+    /// use futures_lite::future::block_on;
+    /// use futures_lite::stream::{self, StreamExt};
+    ///
+    /// block_on(async {
+    ///     let a = stream::iter([1, 2]);
+    ///     let b = stream::iter([3, 4]);
+    ///     let items: Vec<_> = vec![a, b].merge_round_robin().collect().await;
+    ///     assert_eq!(items, vec![1, 3, 2, 4]);
+    /// })
+    /// ```
+    #[must_use = "streams do nothing unless you `.await` or poll them"]
+    fn merge_round_robin(self) -> Merge<Self::Stream>;
+}
+
+impl<S> MergeRoundRobin for Vec<S>
+where
+    S: IntoStream,
+{
+    type Stream = S::IntoStream;
+
+    fn merge_round_robin(self) -> Merge<Self::Stream> {
+        self.merge().round_robin()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::channel::{local_channel, LocalReceiver};
+    use crate::utils::CountingWaker;
+    use futures_core::Stream;
+    use std::pin::Pin;
+    use std::sync::Arc;
+    use std::task::{Context, Poll, Waker};
+
+    /// Poll `s` until it returns `Poll::Pending`, collecting its items.
+    fn burst(s: &mut Merge<LocalReceiver<u8>>, cx: &mut Context<'_>) -> Vec<u8> {
+        let mut items = vec![];
+        while let Poll::Ready(Some(item)) = Pin::new(&mut *s).poll_next(cx) {
+            items.push(item);
+        }
+        items
+    }
+
+    #[test]
+    fn interleaves_bursts_predictably() {
+        let (send_a, recv_a) = local_channel();
+        let (send_b, recv_b) = local_channel();
+        let (send_c, recv_c) = local_channel();
+        let mut s = vec![recv_a, recv_b, recv_c].merge_round_robin();
+
+        let waker = Waker::from(Arc::new(CountingWaker::default()));
+        let mut cx = Context::from_waker(&waker);
+
+        send_b.send(10);
+        send_b.send(11);
+        send_c.send(20);
+        // `a` has nothing, so `b` goes first, then `c`, then back to `b`.
+        assert_eq!(burst(&mut s, &mut cx), vec![10, 20, 11]);
+
+        send_a.send(0);
+        send_a.send(1);
+        send_c.send(21);
+        // Resume after `b`, which yielded last.
+        assert_eq!(burst(&mut s, &mut cx), vec![21, 0, 1]);
+
+        drop(send_a);
+        drop(send_b);
+        assert_eq!(Pin::new(&mut s).poll_next(&mut cx), Poll::Pending);
+        drop(send_c);
+        assert_eq!(Pin::new(&mut s).poll_next(&mut cx), Poll::Ready(None));
+    }
+}
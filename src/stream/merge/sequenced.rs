@@ -0,0 +1,96 @@
+use core::fmt;
+use core::pin::Pin;
+use core::task::{ready, Context, Poll};
+use futures_core::Stream;
+
+use pin_project::pin_project;
+
+/// A merged stream which tags each item with a sequence number.
+///
+/// This `struct` is created by the [`merge_sequenced`] method on the [`Merge`]
+/// trait. See its documentation for more.
+///
+/// [`merge_sequenced`]: crate::stream::Merge::merge_sequenced
+/// [`Merge`]: crate::stream::Merge
+#[must_use = "streams do nothing unless you `.await` or poll them"]
+#[pin_project]
+pub struct Sequenced<S> {
+    #[pin]
+    stream: S,
+    next: u64,
+}
+
+impl<S> Sequenced<S> {
+    pub(crate) fn new(stream: S) -> Self {
+        Self { stream, next: 0 }
+    }
+}
+
+impl<S> fmt::Debug for Sequenced<S>
+where
+    S: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sequenced")
+            .field("stream", &self.stream)
+            .field("next", &self.next)
+            .finish()
+    }
+}
+
+impl<S> Stream for Sequenced<S>
+where
+    S: Stream,
+{
+    type Item = (u64, S::Item);
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        let next = this.next;
+        let item = ready!(this.stream.poll_next(cx));
+        Poll::Ready(item.map(|item| {
+            let seq = *next;
+            *next += 1;
+            (seq, item)
+        }))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.stream.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::stream::Merge;
+    use futures_lite::future::block_on;
+    use futures_lite::stream::{self, StreamExt};
+
+    #[test]
+    fn sequence_numbers_follow_emission_order() {
+        block_on(async {
+            let a = stream::iter([1, 2]);
+            let b = stream::iter([3, 4]);
+            let items: Vec<_> = vec![a, b].merge_sequenced().collect().await;
+
+            let seqs: Vec<_> = items.iter().map(|(seq, _)| *seq).collect();
+            assert_eq!(seqs, vec![0, 1, 2, 3]);
+
+            let mut values: Vec<_> = items.into_iter().map(|(_, n)| n).collect();
+            values.sort_unstable();
+            assert_eq!(values, vec![1, 2, 3, 4]);
+        })
+    }
+
+    #[test]
+    fn sequenced_array() {
+        block_on(async {
+            let items: Vec<_> = [stream::once(1), stream::once(2)]
+                .merge_sequenced()
+                .map(|(seq, _)| seq)
+                .collect()
+                .await;
+            assert_eq!(items, vec![0, 1]);
+        })
+    }
+}
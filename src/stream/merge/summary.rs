@@ -0,0 +1,107 @@
+use super::vec::Merge;
+
+use core::fmt;
+use core::pin::Pin;
+use core::task::{ready, Context, Poll};
+use futures_core::Stream;
+
+use pin_project::pin_project;
+
+/// An item yielded by [`WithSummary`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Summarized<T> {
+    /// An item yielded by one of the merged streams.
+    Value(T),
+    /// The number of items yielded by each stream, in the order the streams
+    /// were passed to the merge. This is yielded once, after every stream has
+    /// been exhausted.
+    Summary(Vec<usize>),
+}
+
+/// A merged stream which ends with a summary of how many items each stream
+/// yielded.
+///
+/// This `struct` is created by the [`with_summary`] method on [`Merge`]. See
+/// its documentation for more.
+///
+/// [`with_summary`]: Merge::with_summary
+#[must_use = "streams do nothing unless you `.await` or poll them"]
+#[pin_project]
+pub struct WithSummary<S>
+where
+    S: Stream,
+{
+    #[pin]
+    merge: Merge<S>,
+    counts: Option<Vec<usize>>,
+}
+
+impl<S> WithSummary<S>
+where
+    S: Stream,
+{
+    pub(crate) fn new(merge: Merge<S>, len: usize) -> Self {
+        Self {
+            merge,
+            counts: Some(vec![0; len]),
+        }
+    }
+}
+
+impl<S> fmt::Debug for WithSummary<S>
+where
+    S: Stream + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WithSummary")
+            .field("merge", &self.merge)
+            .field("counts", &self.counts)
+            .finish()
+    }
+}
+
+impl<S> Stream for WithSummary<S>
+where
+    S: Stream,
+{
+    type Item = Summarized<S::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        let Some(counts) = this.counts.as_mut() else {
+            return Poll::Ready(None);
+        };
+
+        match ready!(this.merge.poll_next_indexed(cx)) {
+            Some((index, item)) => {
+                counts[index] += 1;
+                Poll::Ready(Some(Summarized::Value(item)))
+            }
+            None => Poll::Ready(this.counts.take().map(Summarized::Summary)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::stream::Merge as _;
+    use futures_lite::future::block_on;
+    use futures_lite::stream::{self, StreamExt};
+
+    #[test]
+    fn summary_is_last() {
+        block_on(async {
+            let a = stream::iter(vec![1, 2, 3]);
+            let b = stream::iter(vec![4]);
+            let c = stream::iter(vec![]);
+            let mut items: Vec<_> = vec![a, b, c].merge().with_summary().collect().await;
+
+            assert_eq!(items.pop(), Some(Summarized::Summary(vec![3, 1, 0])));
+            assert_eq!(items.len(), 4);
+            assert!(items
+                .iter()
+                .all(|item| matches!(item, Summarized::Value(_))));
+        })
+    }
+}
@@ -0,0 +1,271 @@
+use super::vec::Merge;
+use crate::utils::{Indexer, WakerVec};
+
+use core::fmt;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures_core::Stream;
+
+use pin_project::pin_project;
+
+/// A running future, along with the index of the stream which yielded it.
+type Task<Fut> = (usize, Pin<Box<Fut>>);
+
+/// A merged stream which resolves the futures yielded by each stream
+/// concurrently.
+///
+/// This `struct` is created by the [`merge_then`] method on [`Merge`]. See
+/// its documentation for more.
+///
+/// [`merge_then`]: Merge::merge_then
+#[must_use = "streams do nothing unless you `.await` or poll them"]
+#[pin_project]
+pub struct MergeThen<S>
+where
+    S: Stream,
+    S::Item: Future,
+{
+    #[pin]
+    merge: Merge<S>,
+    /// The running futures, along with the stream they came from. Slots are
+    /// only added as they're needed, and boxed so they stay in place as the
+    /// vector grows.
+    tasks: Vec<Option<Task<S::Item>>>,
+    /// The indexes of the empty slots.
+    free: Vec<usize>,
+    in_flight: Vec<usize>,
+    wakers: WakerVec,
+    indexer: Indexer,
+    limit: usize,
+    merge_done: bool,
+}
+
+impl<S> MergeThen<S>
+where
+    S: Stream,
+    S::Item: Future,
+{
+    pub(crate) fn new(merge: Merge<S>, streams: usize, limit: usize) -> Self {
+        assert!(limit > 0, "merge_then requires a non-zero limit");
+        Self {
+            in_flight: vec![0; streams],
+            tasks: Vec::new(),
+            free: Vec::new(),
+            wakers: WakerVec::new(0),
+            indexer: Indexer::new(0),
+            merge,
+            limit,
+            merge_done: false,
+        }
+    }
+}
+
+impl<S> fmt::Debug for MergeThen<S>
+where
+    S: Stream + fmt::Debug,
+    S::Item: Future,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MergeThen")
+            .field("merge", &self.merge)
+            .field("running", &(self.tasks.len() - self.free.len()))
+            .field("limit", &self.limit)
+            .finish()
+    }
+}
+
+impl<S> Stream for MergeThen<S>
+where
+    S: Stream,
+    S::Item: Future,
+{
+    type Item = <S::Item as Future>::Output;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+        let limit = *this.limit;
+
+        // Pull futures out of the merge until every stream is either pending,
+        // exhausted, or paused because all of its slots are in use. A stream
+        // which always has a future ready would never let us get to polling
+        // the futures, so we pull at most one future per stream before then.
+        let mut pulled = 0;
+        let mut more = false;
+        while !*this.merge_done {
+            if pulled == this.in_flight.len() {
+                more = true;
+                break;
+            }
+            match this.merge.as_mut().poll_next_indexed(cx) {
+                Poll::Ready(Some((stream, future))) => {
+                    let task = Some((stream, Box::pin(future)));
+                    match this.free.pop() {
+                        Some(slot) => {
+                            this.tasks[slot] = task;
+                            this.wakers.readiness().lock().unwrap().set_ready(slot);
+                        }
+                        None => {
+                            // New wakers start out ready.
+                            this.tasks.push(task);
+                            this.wakers.push();
+                            this.indexer.set_max(this.tasks.len());
+                        }
+                    }
+
+                    pulled += 1;
+                    this.in_flight[stream] += 1;
+                    if this.in_flight[stream] == limit {
+                        this.merge.as_mut().set_paused(stream, true);
+                    }
+                }
+                Poll::Ready(None) => *this.merge_done = true,
+                Poll::Pending => break,
+            }
+        }
+
+        let mut readiness = this.wakers.readiness().lock().unwrap();
+        readiness.set_waker(cx.waker());
+
+        for index in this.indexer.iter() {
+            if !readiness.any_ready() {
+                break;
+            } else if !readiness.clear_ready(index) || this.tasks[index].is_none() {
+                continue;
+            }
+
+            // unlock readiness so we don't deadlock when polling
+            drop(readiness);
+
+            // Obtain the intermediate waker.
+            let mut cx = Context::from_waker(this.wakers.get(index).unwrap());
+
+            let (stream, task) = this.tasks[index].as_mut().unwrap();
+            if let Poll::Ready(output) = task.as_mut().poll(&mut cx) {
+                let stream = *stream;
+                this.tasks[index] = None;
+                this.free.push(index);
+
+                // Free up a slot, so the stream may be polled again.
+                this.in_flight[stream] -= 1;
+                this.merge.as_mut().set_paused(stream, false);
+                return Poll::Ready(Some(output));
+            }
+
+            // Lock readiness so we can use it again
+            readiness = this.wakers.readiness().lock().unwrap();
+        }
+
+        if *this.merge_done && this.in_flight.iter().all(|&n| n == 0) {
+            Poll::Ready(None)
+        } else {
+            // The merge may still have futures for us, go around again.
+            if more {
+                cx.waker().wake_by_ref();
+            }
+            Poll::Pending
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::stream::Merge as _;
+    use futures_lite::future::{block_on, poll_fn, yield_now};
+    use futures_lite::stream::{self, StreamExt};
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use std::task::Poll;
+
+    #[test]
+    fn runs_futures_concurrently() {
+        block_on(async {
+            // The first future only completes once the second one has run,
+            // so this would hang if the futures were resolved one by one.
+            let flag = Rc::new(Cell::new(false));
+            let wait = {
+                let flag = flag.clone();
+                async move {
+                    poll_fn(|cx| {
+                        if flag.get() {
+                            Poll::Ready(())
+                        } else {
+                            cx.waker().wake_by_ref();
+                            Poll::Pending
+                        }
+                    })
+                    .await;
+                    1u8
+                }
+            };
+            let set = async move {
+                flag.set(true);
+                2
+            };
+
+            let a = stream::iter(vec![
+                Box::pin(wait) as std::pin::Pin<Box<dyn std::future::Future<Output = u8>>>,
+                Box::pin(set),
+            ]);
+            let items: Vec<_> = vec![a].merge().merge_then(2).collect().await;
+            assert_eq!(items, vec![2, 1]);
+        })
+    }
+
+    #[test]
+    fn respects_limit() {
+        block_on(async {
+            let running = Rc::new(Cell::new(0));
+            let max = Rc::new(Cell::new(0));
+            let task = |n: usize| {
+                let running = running.clone();
+                let max = max.clone();
+                async move {
+                    running.set(running.get() + 1);
+                    max.set(max.get().max(running.get()));
+                    yield_now().await;
+                    yield_now().await;
+                    running.set(running.get() - 1);
+                    n
+                }
+            };
+
+            let a = stream::iter((0..5).map(task).collect::<Vec<_>>());
+            let b = stream::iter((5..10).map(task).collect::<Vec<_>>());
+            let mut items: Vec<_> = vec![a, b].merge().merge_then(2).collect().await;
+            items.sort_unstable();
+            assert_eq!(items, (0..10).collect::<Vec<_>>());
+            assert!(max.get() <= 4);
+        })
+    }
+
+    #[test]
+    fn unbounded_limit() {
+        block_on(async {
+            let a = stream::iter((0..4).map(std::future::ready));
+            let b = stream::iter((4..8).map(std::future::ready));
+            let mut items: Vec<_> = vec![a, b].merge().merge_then(usize::MAX).collect().await;
+            items.sort_unstable();
+            assert_eq!(items, (0..8).collect::<Vec<_>>());
+        })
+    }
+
+    #[test]
+    fn unbounded_limit_with_endless_stream() {
+        block_on(async {
+            let a = stream::repeat_with(|| std::future::ready(1));
+            let s = vec![a].merge().merge_then(usize::MAX);
+            let items: Vec<_> = s.take(3).collect().await;
+            assert_eq!(items, vec![1, 1, 1]);
+        })
+    }
+
+    #[test]
+    fn empty() {
+        block_on(async {
+            let a = stream::iter(Vec::<std::future::Ready<u8>>::new());
+            let items: Vec<_> = vec![a].merge().merge_then(1).collect().await;
+            assert!(items.is_empty());
+        })
+    }
+}
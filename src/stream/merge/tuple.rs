@@ -13,12 +13,7 @@ macro_rules! poll_stream {
             match unsafe { Pin::new_unchecked(&mut $streams.$stream_member) }.poll_next(&mut $cx) {
                 Poll::Ready(Some(item)) => {
                     // Mark ourselves as ready again because we need to poll for the next item.
-                    $this
-                        .wakers
-                        .readiness()
-                        .lock()
-                        .unwrap()
-                        .set_ready($stream_idx);
+                    $this.wakers.readiness().set_ready($stream_idx);
                     return Poll::Ready(Some(item));
                 }
                 Poll::Ready(None) => {
@@ -43,6 +38,7 @@ macro_rules! impl_merge_tuple {
         ///
         /// [`merge`]: trait.Merge.html#method.merge
         /// [`Merge`]: trait.Merge.html
+        #[must_use = "streams do nothing unless you `.await` or poll them"]
         pub struct $StructName {}
 
         impl fmt::Debug for $StructName {
@@ -59,6 +55,8 @@ macro_rules! impl_merge_tuple {
             }
         }
 
+        utils::impl_boxed_stream!({} $StructName);
+
         impl MergeTrait for () {
             type Item = core::convert::Infallible; // TODO: convert to `never` type in the stdlib
             type Stream = $StructName;
@@ -86,6 +84,7 @@ macro_rules! impl_merge_tuple {
         ///
         /// [`merge`]: trait.Merge.html#method.merge
         /// [`Merge`]: trait.Merge.html
+        #[must_use = "streams do nothing unless you `.await` or poll them"]
         #[pin_project::pin_project]
         pub struct $StructName<T, $($F),*>
         where $(
@@ -119,7 +118,7 @@ macro_rules! impl_merge_tuple {
             fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
                 let this = self.project();
 
-                let mut readiness = this.wakers.readiness().lock().unwrap();
+                let readiness = this.wakers.readiness();
                 readiness.set_waker(cx.waker());
 
                 const LEN: u8 = $mod_name::LEN as u8;
@@ -137,9 +136,6 @@ macro_rules! impl_merge_tuple {
                         continue;
                     }
 
-                    // unlock readiness so we don't deadlock when polling
-                    drop(readiness);
-
                     // Obtain the intermediate waker.
                     let mut cx = Context::from_waker(this.wakers.get(index).unwrap());
 
@@ -154,15 +150,14 @@ macro_rules! impl_merge_tuple {
                             LEN
                         );
                     )+
-
-                    // Lock readiness so we can use it again
-                    readiness = this.wakers.readiness().lock().unwrap();
                 }
 
                 Poll::Pending
             }
         }
 
+        utils::impl_boxed_stream!({T, $($F),*} $StructName<T, $($F),*> where $($F: Stream<Item = T>),*);
+
         impl<T, $($F),*> MergeTrait for ($($F,)*)
         where $(
             $F: IntoStream<Item = T>,
@@ -1,8 +1,19 @@
+use super::catch_unwind::CatchUnwind;
+use super::circuit_break::CircuitBreaker;
+use super::idle_timeout::IdleTimeout;
+use super::liveness::WithLiveness;
+use super::policy::{Batch, Latest, Order, Priority};
+use super::rate_limit::RateLimited;
+use super::summary::WithSummary;
+use super::then::MergeThen;
 use super::Merge as MergeTrait;
+use crate::future::Timer;
 use crate::stream::IntoStream;
 use crate::utils::{self, Indexer, PollVec, WakerVec};
 
+use bitvec::{bitvec, vec::BitVec};
 use core::fmt;
+use core::time::Duration;
 use futures_core::Stream;
 use std::pin::Pin;
 use std::task::{Context, Poll};
@@ -14,6 +25,7 @@ use std::task::{Context, Poll};
 ///
 /// [`merge`]: trait.Merge.html#method.merge
 /// [`Merge`]: trait.Merge.html
+#[must_use = "streams do nothing unless you `.await` or poll them"]
 #[pin_project::pin_project]
 pub struct Merge<S>
 where
@@ -21,11 +33,26 @@ where
 {
     #[pin]
     streams: Vec<S>,
+    /// Rotates the starting point over the positions in `active`.
     indexer: Indexer,
+    /// The indexes of the streams which haven't been exhausted yet, so the
+    /// poll loop only has to scan the live streams.
+    active: Vec<usize>,
+    /// Whether a stream was exhausted since `active` was last compacted.
+    compact: bool,
     complete: usize,
     wakers: WakerVec,
     state: PollVec,
-    done: bool,
+    /// Set by [`Merge::real_time`] and round-robin merges.
+    order: Order,
+    /// Set by [`Merge::prioritize`].
+    priority: Priority,
+    paused: BitVec,
+    /// Set by [`Merge::latest_for`].
+    latest: Latest<S::Item>,
+    restartable: bool,
+    /// Set by [`Merge::batched_drain`].
+    batch: Batch<S::Item>,
 }
 
 impl<S> Merge<S>
@@ -38,11 +65,792 @@ where
             wakers: WakerVec::new(len),
             state: PollVec::new(len),
             indexer: Indexer::new(len),
+            active: (0..len).collect(),
+            compact: false,
             streams,
             complete: 0,
-            done: false,
+            order: Order::Rotating,
+            priority: Priority::new(len),
+            paused: bitvec![0; len],
+            latest: Latest::new(len),
+            restartable: false,
+            batch: Batch::new(),
         }
     }
+
+    /// Poll every stream at most once per cycle.
+    ///
+    /// By default the starting point of each call to `poll_next` is rotated by
+    /// one, which means a stream that just yielded an item can be polled again
+    /// straight away. In real-time mode the merge remembers where it stopped
+    /// and resumes from the stream after the one which yielded last. It also
+    /// tracks which streams have been polled during the current cycle, and
+    /// won't poll any of them again until every other ready stream has had its
+    /// turn.
+    ///
+    /// This bounds how long any one stream can wait to be polled. It costs
+    /// some latency: once a cycle completes while streams are still ready,
+    /// the merge wakes itself and returns `Poll::Pending` rather than
+    /// starting the next cycle right away, yielding to the executor in
+    /// between.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_concurrency::prelude::*;
+    /// use futures_lite::future::block_on;
+    /// use futures_lite::stream::{self, StreamExt};
+    ///
+    /// block_on(async {
+    ///     let a = stream::repeat(1).take(2);
+    ///     let b = stream::repeat(2).take(2);
+    ///     let s = vec![a, b].merge().real_time();
+    ///
+    ///     let items: Vec<_> = s.collect().await;
+    ///     assert_eq!(items, vec![1, 2, 1, 2]);
+    /// })
+    /// ```
+    #[must_use = "streams do nothing unless you `.await` or poll them"]
+    pub fn real_time(mut self) -> Self {
+        self.order = Order::real_time(self.streams.len());
+        self
+    }
+
+    /// Resume from the stream after the one which yielded last, see
+    /// [`merge_round_robin`][super::MergeRoundRobin::merge_round_robin].
+    pub(crate) fn round_robin(mut self) -> Self {
+        self.order = Order::RoundRobin { next: 0 };
+        self
+    }
+
+    /// Only keep the newest item of the stream at `index`.
+    ///
+    /// Items from this stream are held in a single slot rather than being
+    /// yielded straight away. The stream is polled until it returns
+    /// `Poll::Pending` or ends, with each new item overwriting the previous
+    /// one, and only then is the item in the slot yielded. This is useful for
+    /// streams of state updates, where stale values can be skipped.
+    ///
+    /// A stream which never returns `Poll::Pending` never settles, so none of
+    /// its items will be yielded until it ends.
+    ///
+    /// # Panics
+    ///
+    /// This panics if `index` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_concurrency::prelude::*;
+    /// use futures_lite::future::block_on;
+    /// use futures_lite::stream::{self, StreamExt};
+    ///
+    /// block_on(async {
+    ///     let updates = stream::iter([1, 2, 3]);
+    ///     let s = vec![updates].merge().latest_for(0);
+    ///
+    ///     let items: Vec<_> = s.collect().await;
+    ///     assert_eq!(items, vec![3]);
+    /// })
+    /// ```
+    #[must_use = "streams do nothing unless you `.await` or poll them"]
+    pub fn latest_for(mut self, index: usize) -> Self {
+        self.latest.add(index, self.streams.len());
+        self
+    }
+
+    /// Poll the streams at `indexes` before any of the others.
+    ///
+    /// Every time the merge is polled, the priority streams which are ready
+    /// are polled first, in the order they're listed in. Only then are the
+    /// remaining streams polled, starting from a rotating position as usual.
+    /// This suits merges of a few latency-critical streams and many bulk
+    /// ones, without having to weigh each stream.
+    ///
+    /// Priority streams which always have an item ready starve the others.
+    /// In [`real_time`][Merge::real_time] mode each stream is still polled at
+    /// most once per cycle, which bounds how long the other streams wait.
+    ///
+    /// # Panics
+    ///
+    /// This panics if any of the `indexes` are out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_concurrency::prelude::*;
+    /// use futures_lite::future::block_on;
+    /// use futures_lite::stream::{self, StreamExt};
+    ///
+    /// block_on(async {
+    ///     let bulk = stream::iter(vec!["bulk"; 2]);
+    ///     let critical = stream::iter(vec!["critical"; 2]);
+    ///     let s = vec![bulk, critical].merge().prioritize(&[1]);
+    ///
+    ///     let items: Vec<_> = s.collect().await;
+    ///     assert_eq!(items, vec!["critical", "critical", "bulk", "bulk"]);
+    /// })
+    /// ```
+    #[must_use = "streams do nothing unless you `.await` or poll them"]
+    pub fn prioritize(mut self, indexes: &[usize]) -> Self {
+        for &index in indexes {
+            self.priority.add(index);
+        }
+        self
+    }
+
+    /// Treat `None` from a stream as the end of a batch rather than the end
+    /// of the stream.
+    ///
+    /// This diverges from the `Stream` contract, where `None` means a stream
+    /// is exhausted and shouldn't be polled again. In restartable mode, a
+    /// stream which returns `None` is merely idle: it's polled again the next
+    /// time it's woken, and it never counts towards the merge's completion.
+    /// This means that a restartable merge never ends, and
+    /// [`is_complete`][Merge::is_complete] only returns `true` for an empty
+    /// merge.
+    ///
+    /// A stream returning `None` must arrange to be woken once it has more
+    /// items, the same as if it had returned `Poll::Pending`; otherwise it's
+    /// never polled again. Only use this with streams which are documented to
+    /// support being polled after returning `None`, since many streams panic
+    /// or misbehave when they are.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_concurrency::prelude::*;
+    /// use futures_lite::future::block_on;
+    /// use futures_lite::stream::{self, StreamExt};
+    /// use std::task::Poll;
+    ///
+    /// block_on(async {
+    ///     // Yields two batches of two items, separated by a `None`.
+    ///     let mut n = 0;
+    ///     let batches = stream::poll_fn(move |cx| {
+    ///         n += 1;
+    ///         if n == 3 {
+    ///             cx.waker().wake_by_ref();
+    ///             Poll::Ready(None)
+    ///         } else {
+    ///             Poll::Ready(Some(n))
+    ///         }
+    ///     });
+    ///     let s = vec![batches].merge().restartable();
+    ///
+    ///     let items: Vec<_> = s.take(4).collect().await;
+    ///     assert_eq!(items, vec![1, 2, 4, 5]);
+    /// })
+    /// ```
+    #[must_use = "streams do nothing unless you `.await` or poll them"]
+    pub fn restartable(mut self) -> Self {
+        self.restartable = true;
+        self
+    }
+
+    /// Poll every ready stream once per call to `poll_next`, queueing their
+    /// items, and serve the following calls from the queue.
+    ///
+    /// By default the merge returns as soon as a stream yields an item, so
+    /// the next call locks the readiness and scans the streams all over
+    /// again. When many streams tend to be ready at the same time, batching
+    /// drains all of them in a single pass instead, and the merge only polls
+    /// its streams again once the queue is empty. This trades latency for
+    /// throughput: a stream which becomes ready while the queue is being
+    /// served has to wait until the queue is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_concurrency::prelude::*;
+    /// use futures_lite::future::block_on;
+    /// use futures_lite::stream::{self, StreamExt};
+    ///
+    /// block_on(async {
+    ///     let a = stream::iter(vec![1, 2]);
+    ///     let b = stream::iter(vec![3, 4]);
+    ///     let mut items: Vec<_> = vec![a, b].merge().batched_drain().collect().await;
+    ///     items.sort_unstable();
+    ///     assert_eq!(items, vec![1, 2, 3, 4]);
+    /// })
+    /// ```
+    #[must_use = "streams do nothing unless you `.await` or poll them"]
+    pub fn batched_drain(mut self) -> Self {
+        self.batch.enable();
+        self
+    }
+
+    /// Yield a summary of how many items each stream produced once all
+    /// streams are exhausted.
+    ///
+    /// Items are wrapped in [`Summarized::Value`][crate::vec::Summarized::Value],
+    /// and the final item is a
+    /// [`Summarized::Summary`][crate::vec::Summarized::Summary] holding the per-stream counts. The stream
+    /// ends on the poll after the summary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_concurrency::prelude::*;
+    /// use futures_concurrency::vec::Summarized;
+    /// use futures_lite::future::block_on;
+    /// use futures_lite::stream::{self, StreamExt};
+    ///
+    /// block_on(async {
+    ///     let a = stream::iter(vec![1, 2]);
+    ///     let b = stream::iter(vec![3]);
+    ///     let items: Vec<_> = vec![a, b].merge().with_summary().collect().await;
+    ///     assert_eq!(items.last(), Some(&Summarized::Summary(vec![2, 1])));
+    /// })
+    /// ```
+    #[must_use = "streams do nothing unless you `.await` or poll them"]
+    pub fn with_summary(self) -> WithSummary<S> {
+        let len = self.streams.len();
+        WithSummary::new(self, len)
+    }
+
+    /// Pair each item with the number of streams which are still live.
+    ///
+    /// The count is read at the moment each item is yielded, so it can
+    /// change between items. A stream counts as live until it has returned
+    /// `None`, even if it has already yielded its last item; in
+    /// [`restartable`][Merge::restartable] mode every stream stays live.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_concurrency::prelude::*;
+    /// use futures_lite::future::block_on;
+    /// use futures_lite::stream::{self, StreamExt};
+    ///
+    /// block_on(async {
+    ///     let feeds = vec![stream::pending().boxed(), stream::once(1).boxed()];
+    ///     let mut s = feeds.merge().merge_with_liveness();
+    ///
+    ///     let (item, live) = s.next().await.unwrap();
+    ///     assert_eq!((item, live), (1, 2));
+    /// })
+    /// ```
+    #[must_use = "streams do nothing unless you `.await` or poll them"]
+    pub fn merge_with_liveness(self) -> WithLiveness<S> {
+        WithLiveness::new(self)
+    }
+
+    /// The number of streams which haven't been exhausted yet.
+    pub(crate) fn live(&self) -> usize {
+        self.streams.len() - self.complete
+    }
+
+    /// Resolve the futures yielded by each stream, running up to `limit` of
+    /// them concurrently per stream.
+    ///
+    /// The merged stream yields the outputs of the futures in the order they
+    /// complete. A stream which has `limit` futures in flight isn't polled
+    /// again until one of them completes.
+    ///
+    /// # Panics
+    ///
+    /// This panics if `limit` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_concurrency::prelude::*;
+    /// use futures_lite::future::block_on;
+    /// use futures_lite::stream::{self, StreamExt};
+    /// use std::future;
+    ///
+    /// block_on(async {
+    ///     let a = stream::iter(vec![future::ready(1), future::ready(2)]);
+    ///     let b = stream::iter(vec![future::ready(3)]);
+    ///     let mut items: Vec<_> = vec![a, b].merge().merge_then(2).collect().await;
+    ///     items.sort();
+    ///     assert_eq!(items, vec![1, 2, 3]);
+    /// })
+    /// ```
+    #[must_use = "streams do nothing unless you `.await` or poll them"]
+    pub fn merge_then(self, limit: usize) -> MergeThen<S>
+    where
+        S::Item: core::future::Future,
+    {
+        let len = self.streams.len();
+        MergeThen::new(self, len, limit)
+    }
+
+    /// End the merged stream if no stream yields an item for `dur`.
+    ///
+    /// A sleep future created by `timer` is polled alongside the streams, and
+    /// is replaced by a new one each time an item is yielded. Items are
+    /// wrapped in `Ok`; if the sleep completes first, a final
+    /// [`Elapsed`][crate::future::Elapsed] error is yielded and the stream
+    /// ends.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_concurrency::prelude::*;
+    /// use futures_lite::future::block_on;
+    /// use futures_lite::stream::{self, StreamExt};
+    /// use std::time::Duration;
+    ///
+    /// block_on(async {
+    ///     let a = stream::once(1).chain(stream::pending());
+    ///     let timer = |dur| async_std::task::sleep(dur);
+    ///     let s = vec![a].merge().idle_timeout(Duration::from_millis(10), timer);
+    ///     futures_lite::pin!(s);
+    ///
+    ///     assert_eq!(s.next().await, Some(Ok(1)));
+    ///     assert!(s.next().await.unwrap().is_err());
+    ///     assert_eq!(s.next().await, None);
+    /// })
+    /// ```
+    #[must_use = "streams do nothing unless you `.await` or poll them"]
+    pub fn idle_timeout<T>(self, dur: Duration, timer: T) -> IdleTimeout<S, T>
+    where
+        T: Timer,
+    {
+        IdleTimeout::new(self, dur, timer)
+    }
+
+    /// Limit the stream at `index` to one item per `min_interval`.
+    ///
+    /// After the stream yields an item, it's paused until a sleep created by
+    /// `timer` completes, and its wakeups are ignored in the meantime. The
+    /// other streams are polled as usual. Use the
+    /// [`rate_limit`][RateLimited::rate_limit] method on the returned stream
+    /// to limit further streams with the same timer. Pausing and resuming the
+    /// limited streams is up to the rate limiter from then on.
+    ///
+    /// # Panics
+    ///
+    /// This panics if `index` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_concurrency::prelude::*;
+    /// use futures_lite::future::block_on;
+    /// use futures_lite::stream::{self, StreamExt};
+    /// use std::time::Duration;
+    ///
+    /// block_on(async {
+    ///     let limited = stream::iter(vec![1, 2]);
+    ///     let unlimited = stream::iter(vec![3, 4]);
+    ///     let timer = |dur| async_std::task::sleep(dur);
+    ///     let mut items: Vec<_> = vec![limited, unlimited]
+    ///         .merge()
+    ///         .rate_limit(0, Duration::from_millis(5), timer)
+    ///         .collect()
+    ///         .await;
+    ///     items.sort_unstable();
+    ///     assert_eq!(items, vec![1, 2, 3, 4]);
+    /// })
+    /// ```
+    #[must_use = "streams do nothing unless you `.await` or poll them"]
+    pub fn rate_limit<T>(self, index: usize, min_interval: Duration, timer: T) -> RateLimited<S, T>
+    where
+        T: Timer,
+    {
+        let len = self.streams.len();
+        RateLimited::new(self, len, timer).rate_limit(index, min_interval)
+    }
+
+    /// Catch panics in the merged streams, yielding them as errors instead
+    /// of unwinding through the merge.
+    ///
+    /// Each stream is polled inside [`catch_unwind`][std::panic::catch_unwind].
+    /// When a stream panics, its panic payload is yielded as an `Err` and the
+    /// stream is treated as exhausted: it's never polled again, and the other
+    /// streams carry on as usual. Every other item is yielded as an `Ok`.
+    ///
+    /// Panicking streams are asserted to be unwind safe. This is sound
+    /// because a stream which panicked is only ever dropped afterwards, but
+    /// any state it shares with the outside, for example through an `Rc`,
+    /// may have been left inconsistent. The panic hook still runs as usual,
+    /// so the panic's message is still printed.
+    ///
+    /// The options set on this merge, such as [`real_time`][Merge::real_time]
+    /// or paused streams, carry over to the returned merge, and the items it
+    /// has already queued up are yielded as `Ok`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_concurrency::prelude::*;
+    /// use futures_lite::future::block_on;
+    /// use futures_lite::stream::{self, StreamExt};
+    ///
+    /// block_on(async {
+    ///     let healthy = stream::iter(vec![1, 2]).boxed();
+    ///     let panicking = stream::repeat_with(|| -> u8 { panic!("oh no") }).boxed();
+    ///     let s = vec![healthy, panicking].merge().merge_catch_unwind();
+    ///
+    ///     let items: Vec<_> = s.collect().await;
+    ///     assert_eq!(items.len(), 3);
+    ///     assert_eq!(items.iter().filter(|item| item.is_err()).count(), 1);
+    /// })
+    /// ```
+    #[must_use = "streams do nothing unless you `.await` or poll them"]
+    pub fn merge_catch_unwind(self) -> Merge<CatchUnwind<S>> {
+        let Self {
+            streams,
+            indexer,
+            active,
+            compact,
+            complete,
+            wakers,
+            state,
+            order,
+            priority,
+            paused,
+            latest,
+            restartable,
+            batch,
+        } = self;
+        Merge {
+            streams: streams.into_iter().map(CatchUnwind::new).collect(),
+            indexer,
+            active,
+            compact,
+            complete,
+            wakers,
+            state,
+            order,
+            priority,
+            paused,
+            latest: latest.map(Ok),
+            restartable,
+            batch: batch.map(Ok),
+        }
+    }
+
+    /// Returns `true` if every stream in the merge has been exhausted.
+    ///
+    /// This reads the merge's bookkeeping without polling any streams or
+    /// taking any locks. Once it returns `true`, polling the merge returns
+    /// `Poll::Ready(None)`. An empty merge is always complete.
+    pub fn is_complete(&self) -> bool {
+        self.complete == self.streams.len() && self.batch.is_empty()
+    }
+
+    /// Returns how often the merge was woken, and how many of its polls
+    /// were worth it.
+    ///
+    /// A poll is productive when it yields an item, and spurious otherwise,
+    /// whether or not a stream had woken the merge beforehand. A stream
+    /// which keeps waking the merge without yielding anything shows up as a
+    /// high number of spurious polls. This is only available with the
+    /// `waker-stats` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_concurrency::prelude::*;
+    /// use futures_lite::future::block_on;
+    /// use futures_lite::stream::{self, StreamExt};
+    ///
+    /// block_on(async {
+    ///     let mut s = vec![stream::iter(vec![1, 2])].merge();
+    ///     while s.next().await.is_some() {}
+    ///     assert_eq!(s.waker_stats().productive(), 2);
+    /// })
+    /// ```
+    #[cfg(feature = "waker-stats")]
+    pub fn waker_stats(&self) -> crate::utils::WakerStats {
+        self.wakers.readiness().lock().unwrap().stats
+    }
+
+    /// Stop polling the stream at `index` until it's resumed.
+    ///
+    /// The stream is kept alive, but is skipped when polling the merge and
+    /// its wakeups are ignored. Pausing a stream which is already paused does
+    /// nothing.
+    ///
+    /// # Panics
+    ///
+    /// This panics if `index` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_concurrency::prelude::*;
+    /// use futures_lite::future::block_on;
+    /// use futures_lite::stream::{self, StreamExt};
+    ///
+    /// block_on(async {
+    ///     let a = stream::once(1);
+    ///     let b = stream::once(2);
+    ///     let mut s = vec![a, b].merge();
+    ///
+    ///     s.pause(0);
+    ///     assert_eq!(s.next().await, Some(2));
+    ///
+    ///     s.resume(0);
+    ///     assert_eq!(s.next().await, Some(1));
+    ///     assert_eq!(s.next().await, None);
+    /// })
+    /// ```
+    pub fn pause(&mut self, index: usize) {
+        self.paused.set(index, true);
+    }
+
+    /// Resume polling the stream at `index`.
+    ///
+    /// The stream is polled again the next time the merge is polled, and the
+    /// task awaiting the merge is woken. Resuming a stream which isn't paused
+    /// does nothing.
+    ///
+    /// # Panics
+    ///
+    /// This panics if `index` is out of bounds.
+    pub fn resume(&mut self, index: usize) {
+        resume_stream(&mut self.paused, &self.wakers, index);
+    }
+
+    /// Returns `true` if the stream at `index` is paused.
+    ///
+    /// # Panics
+    ///
+    /// This panics if `index` is out of bounds.
+    pub fn is_paused(&self, index: usize) -> bool {
+        self.paused[index]
+    }
+
+    /// Take out the items which the merge has buffered but not yielded yet.
+    ///
+    /// Items are buffered by [`batched_drain`][Merge::batched_drain], and by
+    /// the streams set with [`latest_for`][Merge::latest_for] while they
+    /// settle. Dropping the merge discards them, so this makes it possible
+    /// to handle them on early teardown. The queued batch comes first,
+    /// followed by the latest item of each latest-only stream in index order.
+    ///
+    /// This doesn't poll any streams: it only returns items which have
+    /// already been buffered. It takes a pinned reference, so it can also be
+    /// called on a merge of streams which aren't `Unpin`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_concurrency::prelude::*;
+    /// use futures_lite::future::block_on;
+    /// use futures_lite::stream::{self, StreamExt};
+    /// use std::pin::pin;
+    ///
+    /// block_on(async {
+    ///     let a = stream::iter(vec![1, 2]);
+    ///     let b = stream::iter(vec![3, 4]);
+    ///     let mut s = pin!(vec![a, b].merge().batched_drain());
+    ///
+    ///     let first = s.next().await.unwrap();
+    ///     let rest = s.as_mut().drain();
+    ///     assert_eq!(rest.len(), 1);
+    ///     assert_eq!(first + rest[0], 4);
+    /// })
+    /// ```
+    pub fn drain(self: Pin<&mut Self>) -> Vec<S::Item> {
+        let this = self.project();
+        let batch = this.batch.drain();
+        let latest = this.latest.drain();
+        batch.chain(latest).collect()
+    }
+
+    /// Pause or resume the stream at `index` through a pinned reference.
+    pub(crate) fn set_paused(self: Pin<&mut Self>, index: usize, paused: bool) {
+        let this = self.project();
+        if paused {
+            this.paused.set(index, true);
+        } else {
+            resume_stream(this.paused, this.wakers, index);
+        }
+    }
+
+    /// Stop polling the stream at `index` for good, as if it was exhausted.
+    ///
+    /// The stream stays in place, since it's pinned, but it's dropped from
+    /// the rotation and counts as complete. Closing a stream which is
+    /// already exhausted does nothing.
+    pub(crate) fn close(self: Pin<&mut Self>, index: usize) {
+        let this = self.project();
+        if this.state[index].is_consumed() {
+            return;
+        }
+        this.state[index].set_consumed();
+        *this.complete += 1;
+        *this.compact = true;
+        // The stream leaves the rotation, so nothing else would clear its
+        // ready bit, and a real-time merge would keep waking itself for it.
+        this.wakers.readiness().lock().unwrap().clear_ready(index);
+        // An item held back by a latest-only stream would never be flushed.
+        this.latest.take(index);
+    }
+}
+
+impl<S, I, E> Merge<S>
+where
+    S: Stream<Item = Result<I, E>>,
+{
+    /// Drop streams which yield more than `max_errors` errors within
+    /// `window`.
+    ///
+    /// Every `Err` item starts a sleep created by `timer`, and the error
+    /// counts against its stream until that sleep completes. Once a stream
+    /// has more than `max_errors` errors counting against it, the circuit
+    /// breaker trips: the error which tripped it is still yielded, but the
+    /// stream is never polled again and the merge carries on without it.
+    /// Errors are yielded as usual otherwise.
+    ///
+    /// A tripped stream is dropped for good, there is no recovery after some
+    /// time has passed. Use [`is_tripped`][CircuitBreaker::is_tripped] on the
+    /// returned stream to find out which streams were dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_concurrency::prelude::*;
+    /// use futures_lite::future::block_on;
+    /// use futures_lite::stream::{self, StreamExt};
+    /// use std::time::Duration;
+    ///
+    /// block_on(async {
+    ///     let flapping = stream::iter(vec![Err("timeout"), Err("timeout"), Ok(1)]);
+    ///     let healthy = stream::iter(vec![Ok(2)]);
+    ///     let timer = |dur| async_std::task::sleep(dur);
+    ///     let s = vec![flapping, healthy]
+    ///         .merge()
+    ///         .circuit_break(1, Duration::from_secs(10), timer);
+    ///
+    ///     let ok: Vec<_> = s.filter_map(Result::ok).collect().await;
+    ///     assert_eq!(ok, vec![2]);
+    /// })
+    /// ```
+    #[must_use = "streams do nothing unless you `.await` or poll them"]
+    pub fn circuit_break<T>(
+        self,
+        max_errors: usize,
+        window: Duration,
+        timer: T,
+    ) -> CircuitBreaker<S, T>
+    where
+        T: Timer,
+    {
+        let len = self.streams.len();
+        CircuitBreaker::new(self, len, max_errors, window, timer)
+    }
+}
+
+impl<S> Merge<S>
+where
+    S: Stream + Unpin,
+{
+    /// Add a stream to the merge, returning its index.
+    ///
+    /// This works on a merge which has already been polled. The new stream
+    /// is polled the next time the merge is polled, and the task awaiting the
+    /// merge is woken. The merge doesn't end until the new stream has ended
+    /// too.
+    ///
+    /// A merge which has already returned `None` can be pushed onto as well.
+    /// Its exhausted streams stay exhausted, and it yields the items of the
+    /// new stream until that one ends, then returns `None` again. Wrappers
+    /// which assume a stream stays ended after `None`, such as `Fuse`, won't
+    /// see these items.
+    ///
+    /// This requires the streams to be `Unpin`, because adding a stream can
+    /// move the others in memory.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_concurrency::prelude::*;
+    /// use futures_lite::future::block_on;
+    /// use futures_lite::stream::{self, StreamExt};
+    ///
+    /// block_on(async {
+    ///     let mut s = vec![stream::iter(vec![1, 2])].merge();
+    ///     assert_eq!(s.next().await, Some(1));
+    ///
+    ///     s.push(stream::iter(vec![3]));
+    ///     let mut rest: Vec<_> = s.collect().await;
+    ///     rest.sort_unstable();
+    ///     assert_eq!(rest, vec![2, 3]);
+    /// })
+    /// ```
+    pub fn push(&mut self, stream: S) -> usize {
+        let index = self.streams.len();
+        self.streams.push(stream);
+        self.state.push(Default::default());
+        self.active.push(index);
+        self.indexer.set_max(self.active.len());
+        self.paused.push(false);
+        self.order.push();
+        self.priority.push();
+        self.latest.push();
+
+        self.wakers.push();
+        if let Some(waker) = self.wakers.readiness().lock().unwrap().parent_waker() {
+            waker.wake_by_ref();
+        }
+        index
+    }
+}
+
+/// Unpause the stream at `index`, marking it as ready so it's polled again.
+fn resume_stream(paused: &mut BitVec, wakers: &WakerVec, index: usize) {
+    if !paused.replace(index, false) {
+        return;
+    }
+    let mut readiness = wakers.readiness().lock().unwrap();
+    if !readiness.set_ready(index) {
+        if let Some(waker) = readiness.parent_waker() {
+            waker.wake_by_ref();
+        }
+    }
+}
+
+impl<'a, S> Merge<Pin<&'a mut S>>
+where
+    S: Stream,
+{
+    /// Create a new `Merge` from a vector of pinned streams.
+    ///
+    /// This makes it possible to merge `!Unpin` streams which have been pinned
+    /// on the stack using [`pin!`](core::pin::pin), without boxing them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_concurrency::vec::Merge;
+    /// use futures_lite::future::block_on;
+    /// use futures_lite::stream::{self, StreamExt};
+    /// use std::pin::pin;
+    ///
+    /// block_on(async {
+    ///     let a = pin!(stream::once(1));
+    ///     let b = pin!(stream::once(2));
+    ///     let s = Merge::from_pinned(vec![a, b]);
+    ///
+    ///     let mut counter = 0;
+    ///     s.for_each(|n| counter += n).await;
+    ///     assert_eq!(counter, 3);
+    /// })
+    /// ```
+    pub fn from_pinned(streams: Vec<Pin<&'a mut S>>) -> Self {
+        Self::new(streams)
+    }
+}
+
+/// Create an empty merge, which streams can be added to with
+/// [`push`][Merge::push].
+///
+/// Like any empty merge, it returns `Poll::Ready(None)` until a stream is
+/// added to it, rather than waiting for one.
+impl<S> Default for Merge<S>
+where
+    S: Stream,
+{
+    fn default() -> Self {
+        Self::new(Vec::new())
+    }
 }
 
 impl<S> fmt::Debug for Merge<S>
@@ -61,23 +869,103 @@ where
     type Item = S::Item;
 
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.poll_next_indexed(cx)
+            .map(|item| item.map(|(_, item)| item))
+    }
+}
+
+impl<S> Merge<S>
+where
+    S: Stream,
+{
+    /// Poll for the next item, along with the index of the stream it came from.
+    pub(crate) fn poll_next_indexed(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<(usize, S::Item)>> {
+        #[cfg(feature = "waker-stats")]
+        {
+            let mut this = self;
+            let poll = this.as_mut().poll_streams(cx);
+            let stats = &mut this.wakers.readiness().lock().unwrap().stats;
+            match poll {
+                Poll::Ready(Some(_)) => stats.productive += 1,
+                _ => stats.spurious += 1,
+            }
+            poll
+        }
+        #[cfg(not(feature = "waker-stats"))]
+        self.poll_streams(cx)
+    }
+
+    fn poll_streams(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<(usize, S::Item)>> {
         let mut this = self.project();
 
+        // Serve the items queued up by the last batch first.
+        if let Some(entry) = this.batch.pop() {
+            return Poll::Ready(Some(entry));
+        }
+
+        // An empty merge, or one where every stream is exhausted, is done.
+        if *this.complete == this.streams.len() {
+            return Poll::Ready(None);
+        }
+
+        // Streams are pinned, so exhausted ones can't be moved out of
+        // `streams`. Instead they're dropped from the set of positions we
+        // scan. This happens here rather than as they finish, so positions
+        // don't shift while iterating over them.
+        if *this.compact {
+            let state = &*this.state;
+            this.active.retain(|&index| !state[index].is_consumed());
+            this.indexer.set_max(this.active.len());
+            this.order.compacted(this.active.len());
+            *this.compact = false;
+        }
+
         let mut readiness = this.wakers.readiness().lock().unwrap();
         readiness.set_waker(cx.waker());
 
+        // Whether a latest-only stream has buffered an item without
+        // returning `Poll::Pending`, which means it needs polling again.
+        let mut buffered = false;
+
+        // Priority streams go first, and are skipped among the others. Only
+        // the others have a position in the rotation.
+        let active = &*this.active;
+        let priority = &*this.priority;
+        let rest = this
+            .order
+            .positions(this.indexer, active)
+            .map(|pos| (Some(pos), active[pos]))
+            .filter(|&(_, index)| !priority.contains(index));
+
         // Iterate over our streams one-by-one. If a stream yields a value,
         // we exit early. By default we'll return `Poll::Ready(None)`, but
         // this changes if we encounter a `Poll::Pending`.
-        for index in this.indexer.iter() {
+        for (pos, index) in priority.iter().map(|index| (None, index)).chain(rest) {
             if !readiness.any_ready() {
-                // Nothing is ready yet
-                return Poll::Pending;
+                // Nothing is ready yet, so every stream has had its turn.
+                this.order.end_cycle();
+                return match this.batch.pop() {
+                    Some(entry) => Poll::Ready(Some(entry)),
+                    None => Poll::Pending,
+                };
+            } else if this.paused[index] {
+                // Drop the wakeup, `resume` marks the stream as ready again.
+                readiness.clear_ready(index);
+                continue;
+            } else if this.order.visited(index) {
+                // Already polled during this cycle; leave the ready bit set
+                // so it's picked up by the next one.
+                continue;
             } else if !readiness.clear_ready(index) || this.state[index].is_consumed() {
                 continue;
             }
+            this.order.visit(index);
 
-            // unlock readiness so we don't deadlock when polling
+            // unlock readiness so we don't deadlock when polling, see
+            // `ReadinessVec::set_ready` for why no wakeups are lost
             drop(readiness);
 
             // Obtain the intermediate waker.
@@ -88,26 +976,67 @@ where
                 Poll::Ready(Some(item)) => {
                     // Mark ourselves as ready again because we need to poll for the next item.
                     this.wakers.readiness().lock().unwrap().set_ready(index);
-                    return Poll::Ready(Some(item));
+                    if this.latest.contains(index) {
+                        // Overwrite any older item, and hold on to this one
+                        // until the stream settles.
+                        this.latest.hold(index, item);
+                        buffered = true;
+                    } else {
+                        if let Some(pos) = pos {
+                            this.order.yielded(pos, index, active.len());
+                        }
+                        // Unless batching, return the item straight away.
+                        // Otherwise move on to the next stream; this one is
+                        // polled again once the batch has been served.
+                        if let Some(entry) = this.batch.push(index, item) {
+                            return Poll::Ready(Some(entry));
+                        }
+                    }
+                }
+                Poll::Ready(None) if *this.restartable => {
+                    // The stream is idle until it's woken again.
+                    if let Some(item) = this.latest.take(index) {
+                        return Poll::Ready(Some((index, item)));
+                    }
                 }
                 Poll::Ready(None) => {
                     *this.complete += 1;
+                    *this.compact = true;
                     this.state[index].set_consumed();
+                    if let Some(item) = this.latest.take(index) {
+                        return Poll::Ready(Some((index, item)));
+                    }
                     if *this.complete == this.streams.len() {
-                        return Poll::Ready(None);
+                        return Poll::Ready(this.batch.pop());
+                    }
+                }
+                Poll::Pending => {
+                    if let Some(item) = this.latest.take(index) {
+                        return Poll::Ready(Some((index, item)));
                     }
                 }
-                Poll::Pending => {}
             }
 
             // Lock readiness so we can use it again
             readiness = this.wakers.readiness().lock().unwrap();
         }
 
-        Poll::Pending
+        // Every unvisited stream has been polled, so the cycle is over. Any
+        // streams which are still ready get polled during the next one.
+        this.order.end_cycle();
+        if (this.order.is_real_time() && readiness.any_ready()) || buffered {
+            cx.waker().wake_by_ref();
+        }
+
+        match this.batch.pop() {
+            Some(entry) => Poll::Ready(Some(entry)),
+            None => Poll::Pending,
+        }
     }
 }
 
+utils::impl_boxed_stream!({S} Merge<S> where S: Stream);
+
 impl<S> MergeTrait for Vec<S>
 where
     S: IntoStream,
@@ -124,9 +1053,13 @@ where
 mod tests {
     use std::cell::RefCell;
     use std::rc::Rc;
+    use std::sync::Arc;
+    use std::task::Waker;
 
     use super::*;
     use crate::utils::channel::local_channel;
+    use crate::utils::scripted::{trace, Scripted, Step};
+    use crate::utils::CountingWaker;
     use futures::executor::LocalPool;
     use futures::task::LocalSpawnExt;
     use futures_lite::future::block_on;
@@ -152,6 +1085,24 @@ mod tests {
         })
     }
 
+    #[test]
+    fn boxed() {
+        fn erased() -> futures_core::stream::BoxStream<'static, i32> {
+            vec![stream::once(1), stream::once(2), stream::once(3)]
+                .merge()
+                .boxed()
+        }
+
+        block_on(async {
+            let mut s = erased();
+            let mut counter = 0;
+            while let Some(n) = s.next().await {
+                counter += n;
+            }
+            assert_eq!(counter, 6);
+        })
+    }
+
     #[test]
     fn merge_vec_2x2() {
         block_on(async {
@@ -214,4 +1165,627 @@ mod tests {
             pool.run_until_stalled()
         }
     }
+
+    /// A stream waking itself from inside `poll_next` happens while the merge
+    /// isn't holding the readiness lock. That wakeup must reach the parent.
+    #[test]
+    fn wake_during_own_poll() {
+        let mut polled = false;
+        let a = stream::poll_fn(move |cx| {
+            if polled {
+                Poll::Ready(Some(1))
+            } else {
+                polled = true;
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        });
+        let mut s = vec![a].merge();
+
+        let waker = Arc::new(CountingWaker::default());
+        let parent = Waker::from(waker.clone());
+        let mut cx = Context::from_waker(&parent);
+
+        assert_eq!(Pin::new(&mut s).poll_next(&mut cx), Poll::Pending);
+        assert_eq!(waker.count(), 1);
+        assert_eq!(Pin::new(&mut s).poll_next(&mut cx), Poll::Ready(Some(1)));
+    }
+
+    /// A stream woken by a sibling after it was polled in the same
+    /// `poll_next` call must be polled on the next call.
+    #[test]
+    fn wake_from_sibling_poll() {
+        let stored: Rc<RefCell<Option<Waker>>> = Rc::new(RefCell::new(None));
+
+        let store = stored.clone();
+        let a: Pin<Box<dyn Stream<Item = u8>>> = Box::pin(stream::poll_fn(move |cx| {
+            if store.borrow().is_none() {
+                *store.borrow_mut() = Some(cx.waker().clone());
+                Poll::Pending
+            } else {
+                Poll::Ready(Some(1))
+            }
+        }));
+        let wake = stored.clone();
+        let b: Pin<Box<dyn Stream<Item = u8>>> = Box::pin(stream::poll_fn(move |_| {
+            if let Some(waker) = wake.borrow().as_ref() {
+                waker.wake_by_ref();
+            }
+            Poll::Pending
+        }));
+        let mut s = vec![a, b].merge();
+
+        let waker = Arc::new(CountingWaker::default());
+        let parent = Waker::from(waker.clone());
+        let mut cx = Context::from_waker(&parent);
+
+        assert_eq!(Pin::new(&mut s).poll_next(&mut cx), Poll::Pending);
+        assert_eq!(waker.count(), 1);
+        assert_eq!(Pin::new(&mut s).poll_next(&mut cx), Poll::Ready(Some(1)));
+    }
+
+    /// A stream which yielded an item is polled again on the next call, so
+    /// it gets to register its waker before going quiet.
+    #[test]
+    fn reregisters_after_yield() {
+        let (send, recv) = local_channel();
+        let quiet: Pin<Box<dyn Stream<Item = u8>>> = Box::pin(stream::pending());
+        let recv: Pin<Box<dyn Stream<Item = u8>>> = Box::pin(recv);
+        let mut s = vec![quiet, recv].merge();
+
+        let waker = Arc::new(CountingWaker::default());
+        let parent = Waker::from(waker.clone());
+        let mut cx = Context::from_waker(&parent);
+
+        send.send(1);
+        assert_eq!(Pin::new(&mut s).poll_next(&mut cx), Poll::Ready(Some(1)));
+        assert_eq!(Pin::new(&mut s).poll_next(&mut cx), Poll::Pending);
+        assert_eq!(waker.count(), 0);
+
+        // The receiver registered its waker on the last poll, so sending
+        // wakes the merge and the item is picked up.
+        send.send(2);
+        assert_eq!(waker.count(), 1);
+        assert_eq!(Pin::new(&mut s).poll_next(&mut cx), Poll::Ready(Some(2)));
+        assert_eq!(Pin::new(&mut s).poll_next(&mut cx), Poll::Pending);
+
+        drop(send);
+        assert_eq!(waker.count(), 2);
+        assert_eq!(Pin::new(&mut s).poll_next(&mut cx), Poll::Pending);
+    }
+
+    #[test]
+    fn push_keeps_pinned_streams_in_place() {
+        use std::marker::PhantomPinned;
+
+        /// A stream which records its own address every time it's polled.
+        struct Tracked {
+            addresses: Rc<RefCell<Vec<usize>>>,
+            _pin: PhantomPinned,
+        }
+
+        impl Stream for Tracked {
+            type Item = ();
+
+            fn poll_next(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<()>> {
+                let address = &*self as *const Self as usize;
+                self.addresses.borrow_mut().push(address);
+                Poll::Pending
+            }
+        }
+
+        let addresses = Rc::new(RefCell::new(vec![]));
+        let tracked = || {
+            Box::pin(Tracked {
+                addresses: addresses.clone(),
+                _pin: PhantomPinned,
+            })
+        };
+
+        let waker = Arc::new(utils::DummyWaker()).into();
+        let mut cx = Context::from_waker(&waker);
+        let mut s = vec![tracked()].merge();
+        assert_eq!(Pin::new(&mut s).poll_next(&mut cx), Poll::Pending);
+        let first = addresses.borrow()[0];
+
+        // Grow the merge well past its initial capacity, reallocating its
+        // storage, then wake the first stream again.
+        for _ in 0..64 {
+            s.push(tracked());
+        }
+        s.wakers.readiness().lock().unwrap().set_ready(0);
+        assert_eq!(Pin::new(&mut s).poll_next(&mut cx), Poll::Pending);
+
+        // The boxed streams were moved along with the merge's storage, but
+        // the pinned streams behind them stayed where they are.
+        assert_eq!(
+            addresses.borrow().iter().filter(|&&a| a == first).count(),
+            2
+        );
+    }
+
+    #[test]
+    fn push_after_polling() {
+        let (send_a, recv_a) = local_channel();
+        let (send_b, recv_b) = local_channel();
+        let mut s = vec![recv_a].merge();
+
+        let waker = Arc::new(CountingWaker::default());
+        let parent = Waker::from(waker.clone());
+        let mut cx = Context::from_waker(&parent);
+
+        send_a.send(1);
+        assert_eq!(Pin::new(&mut s).poll_next(&mut cx), Poll::Ready(Some(1)));
+        assert_eq!(Pin::new(&mut s).poll_next(&mut cx), Poll::Pending);
+
+        // Adding a stream wakes the merge, and its items are picked up.
+        assert_eq!(s.push(recv_b), 1);
+        assert_eq!(waker.count(), 1);
+        send_b.send(2);
+        assert_eq!(Pin::new(&mut s).poll_next(&mut cx), Poll::Ready(Some(2)));
+        send_a.send(3);
+        send_b.send(4);
+        let mut items = vec![];
+        while let Poll::Ready(Some(item)) = Pin::new(&mut s).poll_next(&mut cx) {
+            items.push(item);
+        }
+        items.sort_unstable();
+        assert_eq!(items, vec![3, 4]);
+
+        // The merge only ends once the new stream has ended as well.
+        drop(send_a);
+        assert_eq!(Pin::new(&mut s).poll_next(&mut cx), Poll::Pending);
+        drop(send_b);
+        assert_eq!(Pin::new(&mut s).poll_next(&mut cx), Poll::Ready(None));
+    }
+
+    #[test]
+    fn push_after_end() {
+        let (send_a, recv_a) = local_channel();
+        let (send_b, recv_b) = local_channel();
+        let mut s = vec![recv_a].merge();
+
+        let waker = Arc::new(CountingWaker::default());
+        let parent = Waker::from(waker.clone());
+        let mut cx = Context::from_waker(&parent);
+
+        send_a.send(1);
+        drop(send_a);
+        assert_eq!(Pin::new(&mut s).poll_next(&mut cx), Poll::Ready(Some(1)));
+        assert_eq!(Pin::new(&mut s).poll_next(&mut cx), Poll::Ready(None));
+        assert!(s.is_complete());
+
+        assert_eq!(s.push(recv_b), 1);
+        assert!(!s.is_complete());
+        assert_eq!(waker.count(), 1);
+        assert_eq!(Pin::new(&mut s).poll_next(&mut cx), Poll::Pending);
+        send_b.send(2);
+        assert_eq!(Pin::new(&mut s).poll_next(&mut cx), Poll::Ready(Some(2)));
+        drop(send_b);
+        assert_eq!(Pin::new(&mut s).poll_next(&mut cx), Poll::Ready(None));
+        assert!(s.is_complete());
+    }
+
+    #[test]
+    fn push_in_real_time_mode() {
+        let a: Pin<Box<dyn Stream<Item = u8>>> = Box::pin(stream::repeat(0));
+        let mut s = vec![a].merge().real_time();
+
+        let waker = Arc::new(CountingWaker::default());
+        let parent = Waker::from(waker.clone());
+        let mut cx = Context::from_waker(&parent);
+
+        let mut poll = |s: &mut Merge<_>| loop {
+            if let Poll::Ready(item) = Pin::new(&mut *s).poll_next(&mut cx) {
+                break item.unwrap();
+            }
+        };
+        assert_eq!(poll(&mut s), 0);
+
+        // The new stream takes part in the cycles straight away, and the
+        // streams take turns.
+        s.push(Box::pin(stream::repeat(1)));
+        let items: Vec<_> = (0..4).map(|_| poll(&mut s)).collect();
+        assert_eq!(items, vec![1, 0, 1, 0]);
+    }
+
+    #[test]
+    fn push_while_batched() {
+        let a = stream::iter(vec![1, 2]);
+        let b = stream::iter(vec![3]);
+        let mut s = vec![a, b].merge().batched_drain();
+
+        let waker = Arc::new(CountingWaker::default());
+        let parent = Waker::from(waker.clone());
+        let mut cx = Context::from_waker(&parent);
+
+        // The first poll queues an item of each stream.
+        let first = match Pin::new(&mut s).poll_next(&mut cx) {
+            Poll::Ready(Some(item)) => item,
+            other => panic!("expected an item, got {:?}", other),
+        };
+
+        // Pushing doesn't disturb the queued batch, and the new stream's items
+        // follow it.
+        s.push(stream::iter(vec![4, 5]));
+        let mut items = vec![first];
+        loop {
+            match Pin::new(&mut s).poll_next(&mut cx) {
+                Poll::Ready(Some(item)) => items.push(item),
+                Poll::Ready(None) => break,
+                Poll::Pending => {}
+            }
+        }
+        items.sort_unstable();
+        assert_eq!(items, vec![1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn default_then_push() {
+        let (send, recv) = local_channel();
+        let mut s: Merge<_> = Default::default();
+        assert!(s.is_complete());
+
+        let waker = Arc::new(CountingWaker::default());
+        let parent = Waker::from(waker.clone());
+        let mut cx = Context::from_waker(&parent);
+        assert_eq!(Pin::new(&mut s).poll_next(&mut cx), Poll::Ready(None));
+
+        assert_eq!(s.push(recv), 0);
+        assert!(!s.is_complete());
+        send.send(1);
+        assert_eq!(Pin::new(&mut s).poll_next(&mut cx), Poll::Ready(Some(1)));
+        drop(send);
+        assert_eq!(Pin::new(&mut s).poll_next(&mut cx), Poll::Ready(None));
+
+        // An empty merge can be taken out of a struct, leaving another in
+        // its place.
+        let mut taken = std::mem::take(&mut s);
+        assert!(taken.is_complete());
+        assert_eq!(Pin::new(&mut taken).poll_next(&mut cx), Poll::Ready(None));
+    }
+
+    #[test]
+    fn prioritize_polls_priority_streams_first() {
+        let bulk = || -> Pin<Box<dyn Stream<Item = u8>>> { Box::pin(stream::repeat(0)) };
+        let critical: Pin<Box<dyn Stream<Item = u8>>> = Box::pin(stream::repeat(1));
+        let mut s = vec![bulk(), bulk(), critical].merge().prioritize(&[2]);
+
+        let waker = Arc::new(CountingWaker::default());
+        let parent = Waker::from(waker.clone());
+        let mut cx = Context::from_waker(&parent);
+
+        // A priority stream which is always ready starves the others.
+        for _ in 0..6 {
+            assert_eq!(Pin::new(&mut s).poll_next(&mut cx), Poll::Ready(Some(1)));
+        }
+
+        // Real-time mode polls it at most once per cycle.
+        let mut s = vec![bulk(), bulk(), Box::pin(stream::repeat(1))]
+            .merge()
+            .prioritize(&[2])
+            .real_time();
+        let mut items = vec![];
+        while items.len() < 6 {
+            if let Poll::Ready(item) = Pin::new(&mut s).poll_next(&mut cx) {
+                items.push(item.unwrap());
+            }
+        }
+        assert_eq!(items, vec![1, 0, 0, 1, 0, 0]);
+    }
+
+    #[test]
+    fn batched_drain_polls_each_ready_stream_once() {
+        let polls = Rc::new(RefCell::new(vec![0; 3]));
+        let counted = |index: usize| {
+            let polls = polls.clone();
+            stream::poll_fn(move |_| {
+                polls.borrow_mut()[index] += 1;
+                Poll::Ready(Some(index))
+            })
+        };
+        let mut s = vec![counted(0), counted(1), counted(2)]
+            .merge()
+            .batched_drain();
+
+        let waker = Arc::new(CountingWaker::default());
+        let parent = Waker::from(waker.clone());
+        let mut cx = Context::from_waker(&parent);
+
+        let mut items = vec![];
+        for _ in 0..3 {
+            match Pin::new(&mut s).poll_next(&mut cx) {
+                Poll::Ready(Some(item)) => items.push(item),
+                other => panic!("expected an item, got {:?}", other),
+            }
+            // The first call polled every stream, the others were served
+            // from the queue.
+            assert_eq!(*polls.borrow(), vec![1, 1, 1]);
+        }
+        items.sort_unstable();
+        assert_eq!(items, vec![0, 1, 2]);
+
+        // Once the queue is empty the streams are polled again.
+        assert!(Pin::new(&mut s).poll_next(&mut cx).is_ready());
+        assert_eq!(*polls.borrow(), vec![2, 2, 2]);
+    }
+
+    #[test]
+    fn drain_returns_latest_items() {
+        block_on(async {
+            let (send, recv) = local_channel();
+            let mut s = std::pin::pin!(vec![recv].merge().latest_for(0));
+
+            send.send(1);
+            send.send(2);
+            // The first item is held back until the stream settles.
+            assert!(futures_lite::future::poll_once(s.next()).await.is_none());
+            assert_eq!(s.as_mut().drain(), vec![1]);
+            assert_eq!(s.as_mut().drain(), vec![]);
+
+            // Draining didn't poll the stream, so the next item is still there.
+            assert_eq!(s.next().await, Some(2));
+        })
+    }
+
+    #[test]
+    fn batched_drain_flushes_before_ending() {
+        block_on(async {
+            let a = stream::iter(vec![1, 2, 3]);
+            let b = stream::iter(vec![4]);
+            let mut s = vec![a, b].merge().batched_drain();
+            let mut items = vec![];
+            while let Some(item) = s.next().await {
+                items.push(item);
+            }
+            assert!(s.is_complete());
+            items.sort_unstable();
+            assert_eq!(items, vec![1, 2, 3, 4]);
+        })
+    }
+
+    /// In real-time mode a stream which just yielded isn't polled again until
+    /// every other ready stream has had its turn.
+    #[test]
+    fn real_time_polls_once_per_cycle() {
+        let a: Pin<Box<dyn Stream<Item = u8>>> = Box::pin(stream::repeat(0));
+        let b: Pin<Box<dyn Stream<Item = u8>>> = Box::pin(stream::pending());
+        let c: Pin<Box<dyn Stream<Item = u8>>> = Box::pin(stream::repeat(2));
+        let mut s = vec![a, b, c].merge().real_time();
+
+        let waker = Arc::new(CountingWaker::default());
+        let parent = Waker::from(waker.clone());
+        let mut cx = Context::from_waker(&parent);
+
+        let mut items = vec![];
+        let mut pending = 0;
+        while items.len() < 6 {
+            match Pin::new(&mut s).poll_next(&mut cx) {
+                Poll::Ready(item) => items.push(item.unwrap()),
+                Poll::Pending => pending += 1,
+            }
+        }
+        assert_eq!(items, vec![0, 2, 0, 2, 0, 2]);
+        // Each completed cycle wakes the merge so the next one can start.
+        assert_eq!(waker.count(), pending);
+    }
+
+    #[test]
+    fn paused_stream_is_skipped() {
+        let a: Pin<Box<dyn Stream<Item = u8>>> = Box::pin(stream::once(1));
+        let b: Pin<Box<dyn Stream<Item = u8>>> = Box::pin(stream::pending());
+        let mut s = vec![a, b].merge();
+        s.pause(0);
+        assert!(s.is_paused(0));
+
+        let waker = Arc::new(CountingWaker::default());
+        let parent = Waker::from(waker.clone());
+        let mut cx = Context::from_waker(&parent);
+
+        assert_eq!(Pin::new(&mut s).poll_next(&mut cx), Poll::Pending);
+        assert_eq!(Pin::new(&mut s).poll_next(&mut cx), Poll::Pending);
+        assert_eq!(waker.count(), 0);
+
+        // Resuming wakes the task and polls the stream again.
+        s.resume(0);
+        assert!(!s.is_paused(0));
+        assert_eq!(waker.count(), 1);
+        assert_eq!(Pin::new(&mut s).poll_next(&mut cx), Poll::Ready(Some(1)));
+    }
+
+    #[test]
+    fn merge_vec_empty() {
+        block_on(async {
+            let streams: Vec<stream::Once<u8>> = vec![];
+            let mut s = streams.merge();
+            assert_eq!(s.next().await, None);
+        })
+    }
+
+    #[test]
+    fn is_complete() {
+        block_on(async {
+            let a = stream::once(1).boxed();
+            let b = stream::once(2).chain(stream::pending()).boxed();
+            let mut s = vec![a, b].merge();
+            assert!(!s.is_complete());
+
+            assert!(s.next().await.is_some());
+            assert!(s.next().await.is_some());
+            assert!(futures_lite::future::poll_once(s.next()).await.is_none());
+            assert!(!s.is_complete());
+
+            let empty: Vec<stream::Once<u8>> = vec![];
+            assert!(empty.merge().is_complete());
+        })
+    }
+
+    /// Exhausted streams stop being scanned, while items from the remaining
+    /// streams are still attributed to their original index.
+    #[test]
+    fn exhausted_streams_are_compacted() {
+        block_on(async {
+            let a = stream::once(1).boxed();
+            let b = stream::once(2).boxed();
+            let c = stream::once(3).chain(stream::pending()).boxed();
+            let mut s = vec![a, b, c].merge();
+            for _ in 0..3 {
+                assert!(s.next().await.is_some());
+            }
+            assert!(futures_lite::future::poll_once(s.next()).await.is_none());
+            assert!(futures_lite::future::poll_once(s.next()).await.is_none());
+            assert_eq!(s.active, vec![2]);
+
+            let streams: Vec<_> = vec![1, 3, 0, 2]
+                .into_iter()
+                .map(|len| stream::repeat(len).take(len))
+                .collect();
+            let items: Vec<_> = streams.merge().with_summary().collect().await;
+            assert_eq!(
+                items.last(),
+                Some(&crate::vec::Summarized::Summary(vec![1, 3, 0, 2]))
+            );
+        })
+    }
+
+    #[test]
+    fn restartable_stream_is_polled_after_none() {
+        let (send, receive) = local_channel::<u8>();
+        let mut n = 0;
+        let batches: Pin<Box<dyn Stream<Item = u8>>> = Box::pin(stream::poll_fn(move |cx| {
+            n += 1;
+            // End the first batch without scheduling a wakeup.
+            if n == 2 {
+                Poll::Ready(None)
+            } else {
+                cx.waker().wake_by_ref();
+                Poll::Ready(Some(n))
+            }
+        }));
+        let events: Pin<Box<dyn Stream<Item = u8>>> = Box::pin(receive);
+        let mut s = vec![batches, events].merge().restartable();
+
+        let waker = Arc::new(CountingWaker::default());
+        let parent = Waker::from(waker.clone());
+        let mut cx = Context::from_waker(&parent);
+
+        assert_eq!(Pin::new(&mut s).poll_next(&mut cx), Poll::Ready(Some(1)));
+        assert_eq!(Pin::new(&mut s).poll_next(&mut cx), Poll::Pending);
+        assert!(!s.is_complete());
+
+        // The channel closing is also just the end of a batch.
+        drop(send);
+        assert_eq!(Pin::new(&mut s).poll_next(&mut cx), Poll::Pending);
+        assert!(!s.is_complete());
+    }
+
+    #[test]
+    fn latest_only_stream_skips_stale_items() {
+        let mut sent = 0;
+        let updates = stream::poll_fn(move |cx| {
+            // Yield two items, then wait.
+            if sent < 2 {
+                sent += 1;
+                Poll::Ready(Some(sent))
+            } else {
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        });
+        let updates: Pin<Box<dyn Stream<Item = u8>>> = Box::pin(updates);
+        let events: Pin<Box<dyn Stream<Item = u8>>> = Box::pin(stream::iter([10]));
+        let mut s = vec![updates, events].merge().latest_for(0);
+
+        let waker = Arc::new(CountingWaker::default());
+        let parent = Waker::from(waker.clone());
+        let mut cx = Context::from_waker(&parent);
+
+        let mut items = vec![];
+        for _ in 0..8 {
+            if let Poll::Ready(Some(item)) = Pin::new(&mut s).poll_next(&mut cx) {
+                items.push(item);
+            }
+        }
+        items.sort_unstable();
+        assert_eq!(items, vec![2, 10]);
+    }
+
+    /// Three streams whose readiness interleaves differently on every poll.
+    fn scripted_streams() -> Vec<Scripted<(usize, usize)>> {
+        (0..3)
+            .map(|stream| {
+                Scripted::new((0..6).map(move |n| {
+                    if (n + stream) % 3 == 0 {
+                        Step::Pending
+                    } else {
+                        Step::Item((stream, n))
+                    }
+                }))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn output_is_reproducible() {
+        type Scripts = Merge<Scripted<(usize, usize)>>;
+        let configs: [fn(Scripts) -> Scripts; 5] = [
+            |merge| merge,
+            |merge| merge.real_time(),
+            |merge| merge.prioritize(&[2]),
+            |merge| merge.batched_drain(),
+            |merge| merge.round_robin(),
+        ];
+        for config in configs {
+            // Given the same readiness, every run polls the streams in the
+            // same order, and yields the same items on the same polls.
+            let expected = trace(config(scripted_streams().merge()));
+            assert_eq!(expected.iter().filter(|poll| poll.is_ready()).count(), 12);
+            for _ in 0..10 {
+                assert_eq!(trace(config(scripted_streams().merge())), expected);
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "waker-stats")]
+    fn counts_spurious_wakeups() {
+        let (send, recv) = local_channel::<u8>();
+        let mut s = vec![recv].merge();
+        let waker = Arc::new(CountingWaker::default()).into();
+        let mut cx = Context::from_waker(&waker);
+
+        // Every poll which yields nothing is spurious.
+        assert_eq!(Pin::new(&mut s).poll_next(&mut cx), Poll::Pending);
+        assert_eq!(s.waker_stats().spurious(), 1);
+        assert_eq!(Pin::new(&mut s).poll_next(&mut cx), Poll::Pending);
+        assert_eq!(s.waker_stats().spurious(), 2);
+
+        send.send(1);
+        assert_eq!(s.waker_stats().wakes(), 1);
+        assert_eq!(Pin::new(&mut s).poll_next(&mut cx), Poll::Ready(Some(1)));
+        let stats = s.waker_stats();
+        assert_eq!((stats.spurious(), stats.productive()), (2, 1));
+    }
+
+    #[test]
+    #[cfg(feature = "waker-stats")]
+    fn counts_self_waking_pending_polls_as_spurious() {
+        let mut wakeups = 0;
+        let source = stream::poll_fn(move |cx| {
+            if wakeups == 100 {
+                return Poll::Ready(None::<u8>);
+            }
+            wakeups += 1;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        });
+        let mut s = vec![source.boxed_local()].merge();
+        block_on(async { while s.next().await.is_some() {} });
+
+        let stats = s.waker_stats();
+        assert_eq!(stats.wakes(), 100);
+        assert_eq!(stats.productive(), 0);
+        // One spurious poll per wakeup, and the one which ends the merge.
+        assert_eq!(stats.spurious(), 101);
+    }
 }
@@ -47,12 +47,23 @@
 //!
 //! See the [future concurrency][crate::future#concurrency] documentation for
 //! more on futures concurrency.
-pub use chain::Chain;
+pub use chain::{Chain, ChainEvent};
+pub use collect_concurrent::{CollectConcurrent, CollectConcurrentExt};
+pub use concurrent::{Co, ConcurrentStream, ConcurrentStreamExt, ForEach};
+pub use ext::StreamCombineExt;
 pub use into_stream::IntoStream;
-pub use merge::Merge;
+pub use map_into::MapInto;
+pub use merge::{
+    Merge, Merge2, MergeDeficitRoundRobin, MergeExt, MergeKeyed, MergeRoundRobin, MergeSorted,
+    Sequenced,
+};
 pub use zip::Zip;
 
 pub(crate) mod chain;
+mod collect_concurrent;
+mod concurrent;
+mod ext;
 mod into_stream;
+mod map_into;
 pub(crate) mod merge;
 pub(crate) mod zip;
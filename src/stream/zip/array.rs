@@ -19,6 +19,7 @@ use pin_project::{pin_project, pinned_drop};
 ///
 /// [`zip`]: trait.Zip.html#method.zip
 /// [`Zip`]: trait.Zip.html
+#[must_use = "streams do nothing unless you `.await` or poll them"]
 #[pin_project(PinnedDrop)]
 pub struct Zip<S, const N: usize>
 where
@@ -67,7 +68,7 @@ where
 
         assert!(!*this.done, "Stream should not be polled after completion");
 
-        let mut readiness = this.wakers.readiness().lock().unwrap();
+        let readiness = this.wakers.readiness();
         readiness.set_waker(cx.waker());
         for index in 0..N {
             if !readiness.any_ready() {
@@ -79,9 +80,6 @@ where
                 continue;
             }
 
-            // unlock readiness so we don't deadlock when polling
-            drop(readiness);
-
             // Obtain the intermediate waker.
             let mut cx = Context::from_waker(this.wakers.get(index).unwrap());
 
@@ -94,7 +92,6 @@ where
                     let all_ready = this.state.iter().all(|state| state.is_ready());
                     if all_ready {
                         // Reset the future's state.
-                        readiness = this.wakers.readiness().lock().unwrap();
                         readiness.set_all_ready();
                         this.state.fill_with(PollState::default);
 
@@ -116,9 +113,6 @@ where
                 }
                 Poll::Pending => {}
             }
-
-            // Lock readiness so we can use it again
-            readiness = this.wakers.readiness().lock().unwrap();
         }
         Poll::Pending
     }
@@ -143,6 +137,8 @@ where
     }
 }
 
+utils::impl_boxed_stream!({S, const N: usize} Zip<S, N> where S: Stream);
+
 impl<S, const N: usize> ZipTrait for [S; N]
 where
     S: IntoStream,
@@ -175,6 +171,43 @@ mod tests {
             assert_eq!(s.next().await, None);
         })
     }
+
+    #[test]
+    fn zip_array_differing_lengths() {
+        block_on(async {
+            let a = stream::iter(vec![1, 2, 3, 4]);
+            let b = stream::iter(vec![10, 20]);
+            let c = stream::iter(vec![100, 200, 300]);
+            let s = Zip::zip([a, b, c]);
+
+            let items: Vec<_> = s.collect().await;
+            assert_eq!(items, vec![[1, 10, 100], [2, 20, 200]]);
+        })
+    }
+
+    #[test]
+    fn zip_array_does_not_repoll_ended_stream() {
+        use std::cell::Cell;
+        use std::task::Poll;
+
+        block_on(async {
+            let polls = Cell::new(0);
+            let short = stream::poll_fn(|_| {
+                polls.set(polls.get() + 1);
+                match polls.get() {
+                    1 => Poll::Ready(Some(2)),
+                    2 => Poll::Ready(None),
+                    _ => panic!("polled after ending"),
+                }
+            });
+            let long = stream::poll_fn(|_| Poll::Ready(Some(1)));
+            let mut s = Zip::zip([long.boxed_local(), short.boxed_local()]);
+
+            assert_eq!(s.next().await, Some([1, 2]));
+            assert_eq!(s.next().await, None);
+            assert_eq!(polls.get(), 2);
+        })
+    }
 }
 
 // Inlined version of the unstable `MaybeUninit::array_assume_init` feature.
@@ -1,6 +1,7 @@
 use futures_core::Stream;
 
 pub(crate) mod array;
+pub(crate) mod skew;
 pub(crate) mod tuple;
 pub(crate) mod vec;
 
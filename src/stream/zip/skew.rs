@@ -0,0 +1,178 @@
+use crate::utils::{self, WakerVec};
+
+use bitvec::{bitvec, vec::BitVec};
+use core::fmt;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures_core::Stream;
+use std::collections::VecDeque;
+
+use pin_project::pin_project;
+
+/// A stream which zips multiple streams, letting each of them run ahead of
+/// the others by a bounded number of items.
+///
+/// This `struct` is created by the [`max_skew`] method on [`Zip`]. See its
+/// documentation for more.
+///
+/// [`max_skew`]: super::vec::Zip::max_skew
+/// [`Zip`]: super::vec::Zip
+#[must_use = "streams do nothing unless you `.await` or poll them"]
+#[pin_project]
+pub struct SkewedZip<S>
+where
+    S: Stream,
+{
+    #[pin]
+    streams: Vec<S>,
+    /// The items each stream has yielded which haven't been zipped yet.
+    buffers: Vec<VecDeque<S::Item>>,
+    ended: BitVec,
+    wakers: WakerVec,
+    max_skew: usize,
+    done: bool,
+}
+
+impl<S> SkewedZip<S>
+where
+    S: Stream,
+{
+    pub(crate) fn new(streams: Vec<S>, max_skew: usize) -> Self {
+        assert!(max_skew > 0, "max_skew requires a non-zero skew");
+        let len = streams.len();
+        Self {
+            streams,
+            buffers: (0..len)
+                .map(|_| VecDeque::with_capacity(max_skew))
+                .collect(),
+            ended: bitvec![0; len],
+            wakers: WakerVec::new(len),
+            max_skew,
+            done: false,
+        }
+    }
+}
+
+impl<S> fmt::Debug for SkewedZip<S>
+where
+    S: Stream + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SkewedZip")
+            .field("streams", &self.streams)
+            .field("max_skew", &self.max_skew)
+            .finish()
+    }
+}
+
+impl<S> Stream for SkewedZip<S>
+where
+    S: Stream,
+{
+    type Item = Vec<S::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        assert!(!*this.done, "Stream should not be polled after completion");
+
+        let mut readiness = this.wakers.readiness().lock().unwrap();
+        readiness.set_waker(cx.waker());
+        for index in 0..this.buffers.len() {
+            if this.ended[index] {
+                continue;
+            }
+
+            // A stream whose buffer is full isn't polled, and keeps its
+            // readiness so it's polled again once a row has been taken.
+            while this.buffers[index].len() < *this.max_skew && readiness.clear_ready(index) {
+                // unlock readiness so we don't deadlock when polling
+                drop(readiness);
+
+                let mut cx = Context::from_waker(this.wakers.get(index).unwrap());
+                let stream = utils::get_pin_mut_from_vec(this.streams.as_mut(), index).unwrap();
+                let poll = stream.poll_next(&mut cx);
+
+                readiness = this.wakers.readiness().lock().unwrap();
+                match poll {
+                    Poll::Ready(Some(item)) => {
+                        this.buffers[index].push_back(item);
+                        // The stream won't wake us for its next item.
+                        readiness.set_ready(index);
+                    }
+                    Poll::Ready(None) => this.ended.set(index, true),
+                    Poll::Pending => {}
+                }
+            }
+        }
+        drop(readiness);
+
+        if !this.buffers.is_empty() && this.buffers.iter().all(|buffer| !buffer.is_empty()) {
+            let row = this
+                .buffers
+                .iter_mut()
+                .map(|buffer| buffer.pop_front().unwrap())
+                .collect();
+            return Poll::Ready(Some(row));
+        }
+
+        // Once a stream has ended and all of its items have been zipped, no
+        // more rows can be assembled.
+        let exhausted = this
+            .buffers
+            .iter()
+            .zip(this.ended.iter())
+            .any(|(buffer, ended)| *ended && buffer.is_empty());
+        if exhausted {
+            *this.done = true;
+            return Poll::Ready(None);
+        }
+        Poll::Pending
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::stream::Zip as _;
+    use crate::utils::channel::local_channel;
+    use futures_lite::future::{block_on, poll_once};
+    use futures_lite::stream::{self, StreamExt};
+    use std::cell::Cell;
+
+    #[test]
+    fn buffers_up_to_skew() {
+        block_on(async {
+            let pulled = Cell::new(0);
+            let (send, recv) = local_channel::<u8>();
+            let fast = stream::iter(0..10u8)
+                .inspect(|_| pulled.set(pulled.get() + 1))
+                .boxed_local();
+            let mut s = vec![fast, recv.boxed_local()].zip().max_skew(3);
+
+            // The fast stream stops being polled once it's 3 items ahead.
+            assert_eq!(poll_once(s.next()).await, None);
+            assert_eq!(pulled.get(), 3);
+            send.send(10);
+            send.send(11);
+            assert_eq!(s.next().await, Some(vec![0, 10]));
+            assert_eq!(s.next().await, Some(vec![1, 11]));
+            assert_eq!(poll_once(s.next()).await, None);
+
+            // Rows buffered before a stream ends are still yielded.
+            send.send(12);
+            drop(send);
+            assert_eq!(s.next().await, Some(vec![2, 12]));
+            assert_eq!(s.next().await, None);
+        })
+    }
+
+    #[test]
+    fn ends_with_shortest() {
+        block_on(async {
+            let a = stream::iter(vec![1, 2, 3]);
+            let b = stream::iter(vec![4, 5]);
+            let rows: Vec<_> = vec![a, b].zip().max_skew(2).collect().await;
+            assert_eq!(rows, vec![vec![1, 4], vec![2, 5]]);
+        })
+    }
+}
@@ -1,3 +1,4 @@
+use super::skew::SkewedZip;
 use super::Zip as ZipTrait;
 use crate::stream::IntoStream;
 use crate::utils::{self, PollState, WakerVec};
@@ -18,6 +19,7 @@ use pin_project::{pin_project, pinned_drop};
 ///
 /// [`zip`]: trait.Zip.html#method.zip
 /// [`Zip`]: trait.Zip.html
+#[must_use = "streams do nothing unless you `.await` or poll them"]
 #[pin_project(PinnedDrop)]
 pub struct Zip<S>
 where
@@ -47,6 +49,46 @@ where
             done: false,
         }
     }
+
+    /// Let each stream run up to `max_skew` items ahead of the others.
+    ///
+    /// A strict zip only polls a stream again once every other stream has
+    /// caught up, so one slow item holds up all of the streams. Here each
+    /// stream has its own buffer of up to `max_skew` items, which it keeps
+    /// filling while the others lag behind. A row is yielded as soon as every
+    /// buffer has an item, and a stream whose buffer is full isn't polled
+    /// until a row has been taken. This smooths out jitter between streams
+    /// which yield at roughly the same rate.
+    ///
+    /// At most `max_skew * N` items are buffered for `N` streams. A skew of
+    /// `1` behaves like a regular zip. The zip ends once a stream has ended
+    /// and every item it yielded has been zipped.
+    ///
+    /// # Panics
+    ///
+    /// This panics if `max_skew` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_concurrency::prelude::*;
+    /// use futures_lite::future::block_on;
+    /// use futures_lite::stream::{self, StreamExt};
+    ///
+    /// block_on(async {
+    ///     let a = stream::iter(vec![1, 2]);
+    ///     let b = stream::iter(vec![3, 4]);
+    ///     let rows: Vec<_> = vec![a, b].zip().max_skew(4).collect().await;
+    ///     assert_eq!(rows, vec![vec![1, 3], vec![2, 4]]);
+    /// })
+    /// ```
+    #[must_use = "streams do nothing unless you `.await` or poll them"]
+    pub fn max_skew(mut self, max_skew: usize) -> SkewedZip<S> {
+        // The zip can't be taken apart because it implements `Drop`. Any
+        // items it holds are dropped along with it.
+        let streams = mem::take(&mut self.streams);
+        SkewedZip::new(streams, max_skew)
+    }
 }
 
 impl<S> fmt::Debug for Zip<S>
@@ -145,6 +187,8 @@ where
     }
 }
 
+utils::impl_boxed_stream!({S} Zip<S> where S: Stream);
+
 impl<S> ZipTrait for Vec<S>
 where
     S: IntoStream,
@@ -0,0 +1,138 @@
+//! Helpers for testing combinators without an executor.
+//!
+//! This module is only available with the `test-util` feature.
+
+use crate::utils::CountingWaker;
+
+use core::future::Future;
+use core::task::{Context, Poll, Waker};
+use std::sync::Arc;
+
+/// Poll a future until it completes, returning its output and the number of
+/// times it was polled.
+///
+/// This is a shorthand for [`Driver::new().drive(future)`][Driver::drive].
+/// See [`Driver`] for more.
+///
+/// # Panics
+///
+/// This panics if the future returns `Poll::Pending` without waking its
+/// waker, or if it hasn't completed after the default budget of polls.
+///
+/// # Examples
+///
+/// ```
+/// use futures_concurrency::prelude::*;
+/// use futures_concurrency::test_util::drive_to_completion;
+/// use futures_lite::future::yield_now;
+///
+/// let a = async { 1 };
+/// let b = async {
+///     yield_now().await;
+///     2
+/// };
+/// let (output, polls) = drive_to_completion((a, b).join());
+/// assert_eq!(output, (1, 2));
+/// assert_eq!(polls, 2);
+/// ```
+pub fn drive_to_completion<F>(future: F) -> (F::Output, usize)
+where
+    F: Future,
+{
+    Driver::new().drive(future)
+}
+
+/// A deterministic driver which polls a future by hand.
+///
+/// The future is polled on the current thread with a waker which only
+/// counts how often it's woken, over and over until it completes. This
+/// isn't an executor: nothing waits for wakeups. A future which returns
+/// `Poll::Pending` without having woken its waker would never be polled
+/// again by a real executor, unless something outside of it is going to
+/// wake it, so the driver panics instead of spinning. Futures which are
+/// woken by other threads or by timers can't be driven this way.
+#[derive(Debug, Clone)]
+pub struct Driver {
+    budget: usize,
+}
+
+impl Driver {
+    /// Create a new driver, with a budget of 10,000 polls.
+    pub fn new() -> Self {
+        Self { budget: 10_000 }
+    }
+
+    /// Set the number of times the future can be polled before the driver
+    /// gives up and panics.
+    pub fn budget(mut self, budget: usize) -> Self {
+        self.budget = budget;
+        self
+    }
+
+    /// Poll `future` until it completes, returning its output and the number
+    /// of times it was polled.
+    ///
+    /// # Panics
+    ///
+    /// This panics if the future returns `Poll::Pending` without waking its
+    /// waker, or if it hasn't completed after the budget of polls.
+    pub fn drive<F>(&self, future: F) -> (F::Output, usize)
+    where
+        F: Future,
+    {
+        let counter = Arc::new(CountingWaker::default());
+        let waker = Waker::from(counter.clone());
+        let mut cx = Context::from_waker(&waker);
+        let mut future = Box::pin(future);
+
+        for polls in 1..=self.budget {
+            let wakes = counter.count();
+            if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                return (output, polls);
+            }
+            assert!(
+                counter.count() != wakes,
+                "future returned `Poll::Pending` on poll {} without waking its waker, so it would never complete",
+                polls
+            );
+        }
+        panic!("future didn't complete within {} polls", self.budget);
+    }
+}
+
+impl Default for Driver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::prelude::*;
+    use futures_lite::future::{pending, yield_now};
+
+    #[test]
+    fn counts_polls() {
+        let futures = vec![yield_now(), yield_now(), yield_now()];
+        let (_, polls) = drive_to_completion(futures.join());
+        assert_eq!(polls, 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "without waking its waker")]
+    fn panics_when_stuck() {
+        drive_to_completion(pending::<()>());
+    }
+
+    #[test]
+    #[should_panic(expected = "didn't complete within 3 polls")]
+    fn panics_over_budget() {
+        let future = async {
+            for _ in 0..3 {
+                yield_now().await;
+            }
+        };
+        Driver::new().budget(3).drive(future);
+    }
+}
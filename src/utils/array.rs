@@ -1,3 +1,5 @@
+use core::fmt;
+use std::error::Error;
 use std::mem::{self, MaybeUninit};
 
 /// Extracts the values from an array of `MaybeUninit` containers.
@@ -20,3 +22,75 @@ pub(crate) unsafe fn array_assume_init<T, const N: usize>(array: [MaybeUninit<T>
     mem::forget(array);
     ret
 }
+
+/// Collect exactly `N` items from an iterator into an array.
+pub(crate) fn collect_array<I, const N: usize>(iter: I) -> Result<[I::Item; N], LengthError>
+where
+    I: IntoIterator,
+{
+    let mut iter = iter.into_iter().fuse();
+    let mut items: [Option<I::Item>; N] = core::array::from_fn(|_| iter.next());
+    if let Some(found) = items.iter().position(Option::is_none) {
+        return Err(LengthError {
+            expected: N,
+            found: Some(found),
+        });
+    }
+    if iter.next().is_some() {
+        return Err(LengthError {
+            expected: N,
+            found: None,
+        });
+    }
+    Ok(core::array::from_fn(|i| items[i].take().unwrap()))
+}
+
+/// An error returned when an iterator doesn't yield the number of items an
+/// array needs.
+///
+/// This is returned by the `try_from_iter` constructors of the array
+/// combinators, such as [`Join::try_from_iter`][crate::array::Join::try_from_iter].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LengthError {
+    expected: usize,
+    found: Option<usize>,
+}
+
+impl LengthError {
+    /// The number of items the array needed.
+    pub fn expected(&self) -> usize {
+        self.expected
+    }
+
+    /// The number of items the iterator yielded, or `None` if it yielded
+    /// more than [`expected`][Self::expected].
+    pub fn found(&self) -> Option<usize> {
+        self.found
+    }
+}
+
+impl fmt::Display for LengthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.found {
+            Some(found) => write!(f, "expected {} items, found {}", self.expected, found),
+            None => write!(f, "expected {} items, found more", self.expected),
+        }
+    }
+}
+
+impl Error for LengthError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn collects_exactly_n() {
+        assert_eq!(collect_array::<_, 3>(0..3), Ok([0, 1, 2]));
+        let short = collect_array::<_, 3>(0..2).unwrap_err();
+        assert_eq!((short.expected(), short.found()), (3, Some(2)));
+        let long = collect_array::<_, 3>(0..4).unwrap_err();
+        assert_eq!((long.expected(), long.found()), (3, None));
+        assert_eq!(long.to_string(), "expected 3 items, found more");
+    }
+}
@@ -0,0 +1,35 @@
+/// Implement `boxed` and `boxed_local` on a stream combinator.
+///
+/// These are inherent methods rather than a trait, so that they take
+/// precedence over `StreamExt::boxed` from `futures` and `futures-lite`
+/// instead of making calls ambiguous when either is in scope.
+macro_rules! impl_boxed_stream {
+    ({$($generics:tt)*} $ty:ty $(where $($bounds:tt)*)?) => {
+        impl<$($generics)*> $ty $(where $($bounds)*)? {
+            /// Erase the type of this stream, boxing it up as a
+            /// [`BoxStream`][futures_core::stream::BoxStream].
+            ///
+            /// This makes it possible to return the stream from APIs which
+            /// expect a `Pin<Box<dyn Stream + Send>>`. Streams which aren't
+            /// `Send` can use [`boxed_local`][Self::boxed_local] instead.
+            pub fn boxed<'a>(self) -> futures_core::stream::BoxStream<'a, <Self as Stream>::Item>
+            where
+                Self: Stream + Send + Sized + 'a,
+            {
+                Box::pin(self)
+            }
+
+            /// Erase the type of this stream, boxing it up as a
+            /// [`LocalBoxStream`][futures_core::stream::LocalBoxStream].
+            pub fn boxed_local<'a>(
+                self,
+            ) -> futures_core::stream::LocalBoxStream<'a, <Self as Stream>::Item>
+            where
+                Self: Stream + Sized + 'a,
+            {
+                Box::pin(self)
+            }
+        }
+    };
+}
+pub(crate) use impl_boxed_stream;
@@ -0,0 +1,272 @@
+use core::fmt;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use pin_project::pin_project;
+
+use super::WakerVec;
+
+/// Build a combinator out of a closure, driving `futures` with this crate's
+/// readiness tracking.
+///
+/// Every time the returned future is polled, `f` is called with a
+/// [`ReadySet`] of the futures which have been woken since the last call.
+/// The closure decides which of them to poll and when the combinator is
+/// done: it returns `Poll::Ready` with the combinator's output, or
+/// `Poll::Pending` to wait for more futures to be woken. This makes it
+/// possible to write custom completion logic, like waiting for a quorum,
+/// without implementing `Future` by hand.
+///
+/// Futures are dropped as soon as they complete. Futures which were woken
+/// but which the closure didn't poll are handed to it again on the next
+/// call.
+///
+/// # Examples
+///
+/// Wait for two out of three futures to complete:
+///
+/// ```
+/// use futures_concurrency::primitives::combinator;
+/// use futures_lite::future::{block_on, pending, ready, Boxed, FutureExt};
+/// use std::mem;
+/// use std::task::Poll;
+///
+/// block_on(async {
+///     let futures: Vec<Boxed<u8>> = vec![ready(1).boxed(), pending().boxed(), ready(3).boxed()];
+///     let mut outputs = vec![];
+///     let quorum = combinator(futures, move |ready| {
+///         while let Some(index) = ready.next_ready() {
+///             if let Poll::Ready(output) = ready.poll(index) {
+///                 outputs.push(output);
+///             }
+///         }
+///         if outputs.len() >= 2 {
+///             Poll::Ready(mem::take(&mut outputs))
+///         } else {
+///             Poll::Pending
+///         }
+///     });
+///     assert_eq!(quorum.await, vec![1, 3]);
+/// })
+/// ```
+pub fn combinator<Fut, F, T>(futures: Vec<Fut>, f: F) -> Combinator<Fut, F>
+where
+    Fut: Future + Unpin,
+    F: FnMut(&mut ReadySet<'_, Fut>) -> Poll<T>,
+{
+    let wakers = WakerVec::new(futures.len());
+    Combinator {
+        futures: futures.into_iter().map(Some).collect(),
+        wakers,
+        ready: Vec::new(),
+        f,
+    }
+}
+
+/// A combinator driven by a closure.
+///
+/// This `struct` is created by the [`combinator`] function. See its
+/// documentation for more.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+#[pin_project]
+pub struct Combinator<Fut, F> {
+    futures: Vec<Option<Fut>>,
+    wakers: WakerVec,
+    /// The indexes which were woken, reused between polls.
+    ready: Vec<usize>,
+    f: F,
+}
+
+impl<Fut, F> fmt::Debug for Combinator<Fut, F>
+where
+    Fut: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.futures.iter()).finish()
+    }
+}
+
+impl<Fut, F, T> Future for Combinator<Fut, F>
+where
+    Fut: Future + Unpin,
+    F: FnMut(&mut ReadySet<'_, Fut>) -> Poll<T>,
+{
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+
+        let mut readiness = this.wakers.readiness().lock().unwrap();
+        readiness.set_waker(cx.waker());
+        this.ready.clear();
+        for (index, fut) in this.futures.iter().enumerate() {
+            if fut.is_some() && readiness.clear_ready(index) {
+                this.ready.push(index);
+            }
+        }
+        drop(readiness);
+
+        let mut set = ReadySet {
+            futures: this.futures,
+            wakers: this.wakers,
+            ready: this.ready,
+            next: 0,
+        };
+        let poll = (this.f)(&mut set);
+
+        // Hand the futures which weren't polled to the closure again next time.
+        let next = set.next;
+        let mut readiness = this.wakers.readiness().lock().unwrap();
+        for &index in &this.ready[next..] {
+            readiness.set_ready(index);
+        }
+        drop(readiness);
+        // Marking them as ready doesn't wake us, so make sure there is a next
+        // time.
+        if poll.is_pending() && next < this.ready.len() {
+            cx.waker().wake_by_ref();
+        }
+        poll
+    }
+}
+
+/// The futures of a [`Combinator`] which are ready to be polled.
+///
+/// This is passed to the closure given to [`combinator`].
+pub struct ReadySet<'a, Fut> {
+    futures: &'a mut [Option<Fut>],
+    wakers: &'a WakerVec,
+    ready: &'a [usize],
+    next: usize,
+}
+
+impl<Fut> ReadySet<'_, Fut> {
+    /// The indexes of the woken futures which haven't been taken with
+    /// [`next_ready`][Self::next_ready] yet.
+    pub fn ready(&self) -> &[usize] {
+        &self.ready[self.next..]
+    }
+
+    /// Take the index of the next woken future.
+    pub fn next_ready(&mut self) -> Option<usize> {
+        let index = *self.ready.get(self.next)?;
+        self.next += 1;
+        Some(index)
+    }
+
+    /// The total number of futures, including the completed ones.
+    pub fn len(&self) -> usize {
+        self.futures.len()
+    }
+
+    /// Returns `true` if there are no futures at all.
+    pub fn is_empty(&self) -> bool {
+        self.futures.is_empty()
+    }
+
+    /// Returns `true` if the future at `index` has completed.
+    pub fn is_done(&self, index: usize) -> bool {
+        self.futures[index].is_none()
+    }
+}
+
+impl<Fut> ReadySet<'_, Fut>
+where
+    Fut: Future + Unpin,
+{
+    /// Poll the future at `index`, dropping it if it completes.
+    ///
+    /// # Panics
+    ///
+    /// This panics if the future has already completed.
+    pub fn poll(&mut self, index: usize) -> Poll<Fut::Output> {
+        let slot = &mut self.futures[index];
+        let fut = slot.as_mut().expect("future polled after completing");
+        let mut cx = Context::from_waker(self.wakers.get(index).unwrap());
+        let poll = Pin::new(fut).poll(&mut cx);
+        if poll.is_ready() {
+            *slot = None;
+        }
+        poll
+    }
+}
+
+impl<Fut> fmt::Debug for ReadySet<'_, Fut> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReadySet")
+            .field("ready", &self.ready())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::{CountingWaker, DummyWaker};
+    use futures::channel::oneshot;
+    use futures_lite::future::{ready, Boxed, FutureExt};
+    use std::sync::Arc;
+
+    #[test]
+    fn only_hands_out_woken_futures() {
+        let waker = Arc::new(DummyWaker()).into();
+        let mut cx = Context::from_waker(&waker);
+
+        let (senders, receivers): (Vec<_>, Vec<_>) =
+            (0..3).map(|_| oneshot::channel::<u8>()).unzip();
+        let mut seen = vec![];
+        let mut fut = combinator(receivers, |ready| {
+            seen.push(ready.ready().to_vec());
+            while let Some(index) = ready.next_ready() {
+                let _ = ready.poll(index);
+            }
+            if (0..ready.len()).all(|i| ready.is_done(i)) {
+                Poll::Ready(())
+            } else {
+                Poll::Pending
+            }
+        });
+
+        assert!(Pin::new(&mut fut).poll(&mut cx).is_pending());
+        let mut senders = senders.into_iter();
+        let first = senders.next().unwrap();
+        first.send(0).unwrap();
+        assert!(Pin::new(&mut fut).poll(&mut cx).is_pending());
+        for sender in senders {
+            sender.send(0).unwrap();
+        }
+        assert!(Pin::new(&mut fut).poll(&mut cx).is_ready());
+        drop(fut);
+        assert_eq!(seen, vec![vec![0, 1, 2], vec![0], vec![1, 2]]);
+    }
+
+    #[test]
+    fn wakes_for_futures_left_unpolled() {
+        let counter = Arc::new(CountingWaker::default());
+        let waker = counter.clone().into();
+        let mut cx = Context::from_waker(&waker);
+
+        let futures: Vec<Boxed<u8>> = vec![ready(1).boxed(), ready(2).boxed()];
+        let mut outputs = vec![];
+        // Only poll one future per call.
+        let mut fut = combinator(futures, |ready| {
+            if let Some(index) = ready.next_ready() {
+                if let Poll::Ready(output) = ready.poll(index) {
+                    outputs.push(output);
+                }
+            }
+            if outputs.len() == 2 {
+                Poll::Ready(())
+            } else {
+                Poll::Pending
+            }
+        });
+
+        assert!(Pin::new(&mut fut).poll(&mut cx).is_pending());
+        assert_eq!(counter.count(), 1);
+        assert!(Pin::new(&mut fut).poll(&mut cx).is_ready());
+        drop(fut);
+        assert_eq!(outputs, vec![1, 2]);
+    }
+}
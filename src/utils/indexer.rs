@@ -5,25 +5,63 @@ use core::ops;
 pub(crate) struct Indexer {
     offset: usize,
     max: usize,
+    biased: bool,
 }
 
 impl Indexer {
     pub(crate) fn new(max: usize) -> Self {
-        Self { offset: 0, max }
+        Self {
+            offset: 0,
+            max,
+            biased: false,
+        }
+    }
+
+    /// Always start iterating from `0`, giving up on fairness so earlier
+    /// indexes win ties.
+    pub(crate) fn bias(&mut self) {
+        self.biased = true;
+        self.offset = 0;
     }
 
     /// Generate a range between `0..max`, incrementing the starting point
     /// for the next iteration.
+    ///
+    /// With the `test-determinism` feature enabled, or once the indexer is
+    /// biased, the starting point is always `0`.
     pub(crate) fn iter(&mut self) -> IndexIter {
+        if self.biased || cfg!(feature = "test-determinism") {
+            return self.iter_from(0);
+        }
+
         // Increment the starting point for next time.
         let offset = self.offset;
-        self.offset = (self.offset + 1).wrapping_rem(self.max);
+        self.offset = (self.offset + 1).checked_rem(self.max).unwrap_or(0);
 
         IndexIter {
             iter: (0..self.max),
             offset,
         }
     }
+
+    /// Change the end of the range, keeping the starting point within it.
+    pub(crate) fn set_max(&mut self, max: usize) {
+        self.max = max;
+        self.offset = self.offset.checked_rem(max).unwrap_or(0);
+    }
+
+    /// Generate a range between `0..max` which starts at `offset`, without
+    /// moving the starting point for the next iteration.
+    pub(crate) fn iter_from(&self, offset: usize) -> IndexIter {
+        debug_assert!(
+            offset < self.max || offset == 0,
+            "starting point out of range"
+        );
+        IndexIter {
+            iter: (0..self.max),
+            offset,
+        }
+    }
 }
 
 pub(crate) struct IndexIter {
@@ -35,8 +73,50 @@ impl Iterator for IndexIter {
     type Item = usize;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.iter
-            .next()
-            .map(|pos| (pos + self.offset).wrapping_rem(self.iter.end))
+        // `pos + offset` can overflow for ranges longer than half of
+        // `usize::MAX`, so wrap around without computing it directly.
+        let end = self.iter.end;
+        let offset = self.offset;
+        self.iter.next().map(|pos| {
+            if pos < end - offset {
+                pos + offset
+            } else {
+                pos - (end - offset)
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn starting_point() {
+        let mut indexer = Indexer::new(3);
+        assert_eq!(indexer.iter().collect::<Vec<_>>(), vec![0, 1, 2]);
+
+        let expected = if cfg!(feature = "test-determinism") {
+            vec![0, 1, 2]
+        } else {
+            vec![1, 2, 0]
+        };
+        assert_eq!(indexer.iter().collect::<Vec<_>>(), expected);
+    }
+
+    #[test]
+    fn biased_starting_point() {
+        let mut indexer = Indexer::new(3);
+        indexer.iter();
+        indexer.bias();
+        assert_eq!(indexer.iter().collect::<Vec<_>>(), vec![0, 1, 2]);
+        assert_eq!(indexer.iter().collect::<Vec<_>>(), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn wraps_without_overflow() {
+        let indexer = Indexer::new(usize::MAX);
+        let positions: Vec<_> = indexer.iter_from(usize::MAX - 1).take(3).collect();
+        assert_eq!(positions, vec![usize::MAX - 1, 0, 1]);
     }
 }
@@ -1,22 +1,41 @@
 //! Utilities to implement the different futures of this crate.
 
 mod array;
+mod boxed;
+mod combinator;
 mod indexer;
+mod overpoll;
 mod pin;
 mod poll_state;
+mod scheduler;
+mod slots;
 mod tuple;
 mod wakers;
 
-pub(crate) use array::array_assume_init;
-pub(crate) use indexer::Indexer;
-pub(crate) use pin::{get_pin_mut, get_pin_mut_from_vec, iter_pin_mut, iter_pin_mut_vec};
+pub use array::LengthError;
+pub(crate) use array::{array_assume_init, collect_array};
+pub(crate) use boxed::impl_boxed_stream;
+pub use combinator::{combinator, Combinator, ReadySet};
+pub(crate) use indexer::{IndexIter, Indexer};
+pub(crate) use overpoll::polled_after_completion;
+pub(crate) use pin::{
+    get_pin_mut, get_pin_mut_from_vec, iter_pin_mut, iter_pin_mut_vec, pin_vec_as_slice,
+};
 pub(crate) use poll_state::MaybeDone;
-pub(crate) use poll_state::{PollArray, PollState, PollVec};
+pub use poll_state::{PollArray, PollState, PollVec};
+pub use scheduler::RaceScheduler;
+pub(crate) use slots::FutureSlots;
 pub(crate) use tuple::{gen_conditions, tuple_len};
-pub(crate) use wakers::{WakerArray, WakerVec};
+#[cfg(feature = "waker-stats")]
+pub use wakers::WakerStats;
+pub use wakers::{AtomicReadinessArray, Readiness, ReadinessVec, WakerArray, WakerVec};
 
+#[cfg(any(test, feature = "test-util"))]
+pub(crate) use wakers::CountingWaker;
 #[cfg(test)]
 pub(crate) use wakers::DummyWaker;
 
 #[cfg(test)]
 pub(crate) mod channel;
+#[cfg(test)]
+pub(crate) mod scripted;
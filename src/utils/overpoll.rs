@@ -0,0 +1,19 @@
+/// Handle a join being polled after it already completed.
+///
+/// This panics by default, naming the number of futures in the join and
+/// where it was created, since the panic itself points into this crate. With
+/// the `permissive-polling` feature it returns `Poll::Pending` from the
+/// enclosing `poll` instead, which suits executors that poll futures again
+/// after they've completed.
+macro_rules! polled_after_completion {
+    ($len:expr, $location:expr) => {{
+        if cfg!(feature = "permissive-polling") {
+            return Poll::Pending;
+        }
+        panic!(
+            "Futures must not be polled after completing: join of {} futures created at {}",
+            $len, $location
+        )
+    }};
+}
+pub(crate) use polled_after_completion;
@@ -12,6 +12,9 @@ pub(crate) fn iter_pin_mut<T>(slice: Pin<&mut [T]>) -> impl Iterator<Item = Pin<
 }
 
 // From: `futures_rs::join_all!` -- https://github.com/rust-lang/futures-rs/blob/b48eb2e9a9485ef7388edc2f177094a27e08e28b/futures-util/src/future/join_all.rs#L18-L23
+//
+// NOTE: like `get_pin_mut_from_vec`, this relies on a pinned `Vec` never being
+// grown, shrunk or reordered, since reallocating would move its elements.
 pub(crate) fn iter_pin_mut_vec<T>(slice: Pin<&mut Vec<T>>) -> impl Iterator<Item = Pin<&mut T>> {
     // SAFETY: `std` _could_ make this unsound if it were to decide Pin's
     // invariants aren't required to transmit through slices. Otherwise this has
@@ -43,6 +46,12 @@ where
 // NOTE: If this is implemented through the trait, this will work on both vecs and
 // slices.
 //
+// NOTE: this treats every element of a pinned `Vec` as pinned, which is only
+// sound as long as the `Vec` is never grown, shrunk or reordered once it's been
+// pinned: reallocating would move the elements. Combinators which add
+// elements after being polled, like `Merge::push`, must require them to be
+// `Unpin`.
+//
 // From: https://github.com/rust-lang/rust/pull/78370/files
 pub(crate) fn get_pin_mut_from_vec<T, I>(
     slice: Pin<&mut Vec<T>>,
@@ -61,3 +70,12 @@ where
             .map(|x| Pin::new_unchecked(x))
     }
 }
+
+/// Returns a pinned mutable reference to the elements of a pinned `Vec`.
+//
+// NOTE: like `get_pin_mut_from_vec`, this relies on a pinned `Vec` never being
+// grown, shrunk or reordered, since reallocating would move its elements.
+pub(crate) fn pin_vec_as_slice<T>(vec: Pin<&mut Vec<T>>) -> Pin<&mut [T]> {
+    // SAFETY: the elements aren't moved, and stay pinned in the slice.
+    unsafe { vec.map_unchecked_mut(|vec| vec.as_mut_slice()) }
+}
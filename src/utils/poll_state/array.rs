@@ -2,12 +2,33 @@ use std::ops::{Deref, DerefMut};
 
 use super::PollState;
 
-pub(crate) struct PollArray<const N: usize> {
+/// The poll states of a fixed number of futures or streams.
+///
+/// This dereferences to a slice of [`PollState`], so individual slots can be
+/// queried and transitioned by index.
+///
+/// # Examples
+///
+/// ```
+/// use futures_concurrency::primitives::PollArray;
+///
+/// let mut states = PollArray::<2>::new();
+/// assert!(states.iter().all(|state| state.is_pending()));
+///
+/// states[1].set_ready();
+/// assert!(states[1].is_ready());
+/// states[1].set_consumed();
+/// assert!(states[1].is_consumed());
+/// ```
+#[derive(Debug)]
+pub struct PollArray<const N: usize> {
     state: [PollState; N],
 }
 
 impl<const N: usize> PollArray<N> {
-    pub(crate) fn new() -> Self {
+    /// Create a new `PollArray` with all slots set to
+    /// [`Pending`][PollState::Pending].
+    pub fn new() -> Self {
         Self {
             state: [PollState::default(); N],
         }
@@ -25,6 +46,12 @@ impl<const N: usize> PollArray<N> {
     }
 }
 
+impl<const N: usize> Default for PollArray<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<const N: usize> Deref for PollArray<N> {
     type Target = [PollState];
 
@@ -5,7 +5,7 @@ mod maybe_done;
 mod poll_state;
 mod vec;
 
-pub(crate) use array::PollArray;
+pub use array::PollArray;
 pub(crate) use maybe_done::MaybeDone;
-pub(crate) use poll_state::PollState;
-pub(crate) use vec::PollVec;
+pub use poll_state::PollState;
+pub use vec::PollVec;
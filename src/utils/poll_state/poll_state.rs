@@ -1,7 +1,14 @@
 /// Enumerate the current poll state.
-#[derive(Debug, Clone, Copy, Default)]
+///
+/// Every slot starts out [`Pending`][Self::Pending]. Once its future or stream
+/// has produced a value which has been stored, it moves to
+/// [`Ready`][Self::Ready]. After that value has been read out it moves to
+/// [`Consumed`][Self::Consumed], at which point the slot must not be polled
+/// again. A stream slot which yields an item and still has more to yield may
+/// move from `Ready` back to `Pending` instead.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 #[repr(u8)]
-pub(crate) enum PollState {
+pub enum PollState {
     /// Polling the underlying future or stream.
     #[default]
     Pending,
@@ -17,33 +24,33 @@ impl PollState {
     /// Returns `true` if the metadata is [`Pending`][Self::Pending].
     #[must_use]
     #[inline]
-    pub(crate) fn is_pending(&self) -> bool {
+    pub fn is_pending(&self) -> bool {
         matches!(self, Self::Pending)
     }
 
     /// Returns `true` if the poll state is [`Ready`][Self::Ready].
     #[must_use]
     #[inline]
-    pub(crate) fn is_ready(&self) -> bool {
+    pub fn is_ready(&self) -> bool {
         matches!(self, Self::Ready)
     }
 
     /// Sets the poll state to [`Ready`][Self::Ready].
     #[inline]
-    pub(crate) fn set_ready(&mut self) {
+    pub fn set_ready(&mut self) {
         *self = PollState::Ready;
     }
 
     /// Returns `true` if the poll state is [`Consumed`][Self::Consumed].
     #[must_use]
     #[inline]
-    pub(crate) fn is_consumed(&self) -> bool {
+    pub fn is_consumed(&self) -> bool {
         matches!(self, Self::Consumed)
     }
 
     /// Sets the poll state to [`Consumed`][Self::Consumed].
     #[inline]
-    pub(crate) fn set_consumed(&mut self) {
+    pub fn set_consumed(&mut self) {
         *self = PollState::Consumed;
     }
 }
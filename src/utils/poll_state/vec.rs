@@ -1,3 +1,4 @@
+use core::fmt;
 use std::ops::{Deref, DerefMut};
 
 use super::PollState;
@@ -25,17 +26,39 @@ use super::PollState;
 /// ```
 const MAX_INLINE_ENTRIES: usize = std::mem::size_of::<usize>() * 3 - 2;
 
-pub(crate) enum PollVec {
+/// The poll states of a dynamic number of futures or streams.
+///
+/// This dereferences to a slice of [`PollState`], so individual slots can be
+/// queried and transitioned by index. Small numbers of slots are stored
+/// inline without allocating.
+///
+/// # Examples
+///
+/// ```
+/// use futures_concurrency::primitives::PollVec;
+///
+/// let mut states = PollVec::new(3);
+/// assert_eq!(states.len(), 3);
+///
+/// states[0].set_ready();
+/// assert!(states[0].is_ready());
+/// assert!(states[1].is_pending());
+/// ```
+pub struct PollVec(Storage);
+
+enum Storage {
     Inline(u8, [PollState; MAX_INLINE_ENTRIES]),
     Boxed(Box<[PollState]>),
 }
 
 impl PollVec {
-    pub(crate) fn new(len: usize) -> Self {
+    /// Create a new `PollVec` with `len` slots, all set to
+    /// [`Pending`][PollState::Pending].
+    pub fn new(len: usize) -> Self {
         assert!(MAX_INLINE_ENTRIES <= u8::MAX as usize);
 
         if len <= MAX_INLINE_ENTRIES {
-            Self::Inline(len as u8, Default::default())
+            Self(Storage::Inline(len as u8, Default::default()))
         } else {
             // Make sure that we don't reallocate the vec's memory
             // during `Vec::into_boxed_slice()`.
@@ -45,27 +68,51 @@ impl PollVec {
             debug_assert_eq!(states.capacity(), len);
             states.resize(len, PollState::default());
             debug_assert_eq!(states.capacity(), len);
-            Self::Boxed(states.into_boxed_slice())
+            Self(Storage::Boxed(states.into_boxed_slice()))
         }
     }
 }
 
+impl PollVec {
+    /// Add one more slot, moving the slots to the heap once they no longer
+    /// fit inline.
+    pub(crate) fn push(&mut self, state: PollState) {
+        match &mut self.0 {
+            Storage::Inline(len, states) if (*len as usize) < MAX_INLINE_ENTRIES => {
+                states[*len as usize] = state;
+                *len += 1;
+            }
+            _ => {
+                let mut states = self.to_vec();
+                states.push(state);
+                self.0 = Storage::Boxed(states.into_boxed_slice());
+            }
+        }
+    }
+}
+
+impl fmt::Debug for PollVec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
 impl Deref for PollVec {
     type Target = [PollState];
 
     fn deref(&self) -> &Self::Target {
-        match self {
-            PollVec::Inline(len, states) => &states[..*len as usize],
-            Self::Boxed(states) => &states[..],
+        match &self.0 {
+            Storage::Inline(len, states) => &states[..*len as usize],
+            Storage::Boxed(states) => &states[..],
         }
     }
 }
 
 impl DerefMut for PollVec {
     fn deref_mut(&mut self) -> &mut Self::Target {
-        match self {
-            PollVec::Inline(len, states) => &mut states[..*len as usize],
-            Self::Boxed(states) => &mut states[..],
+        match &mut self.0 {
+            Storage::Inline(len, states) => &mut states[..*len as usize],
+            Storage::Boxed(states) => &mut states[..],
         }
     }
 }
@@ -82,6 +129,17 @@ mod tests {
         );
     }
 
+    #[test]
+    fn push_moves_to_the_heap() {
+        let mut states = PollVec::new(MAX_INLINE_ENTRIES);
+        states[0].set_ready();
+        states.push(Default::default());
+        states.push(Default::default());
+        assert_eq!(states.len(), MAX_INLINE_ENTRIES + 2);
+        assert!(states[0].is_ready());
+        assert!(states[MAX_INLINE_ENTRIES + 1].is_pending());
+    }
+
     #[test]
     fn boxed_does_not_allocate_twice() {
         // Make sure the debug_assertions in PollStates::new() don't fail.
@@ -0,0 +1,77 @@
+/// Remembers which futures won previous races, so later races can favor the
+/// others.
+///
+/// A race normally has no memory: every race starts polling from a fresh
+/// position. When the same set of sources is raced over and over, passing a
+/// `RaceScheduler` to [`Race::race_with`][crate::vec::Race::race_with] polls
+/// the futures which have won the fewest races first, spreading wins across
+/// all of them when several are ready at once.
+///
+/// # Examples
+///
+/// ```
+/// use futures_concurrency::prelude::*;
+/// use futures_concurrency::vec::RaceScheduler;
+/// use futures_lite::future::block_on;
+/// use std::future::ready;
+///
+/// block_on(async {
+///     let mut scheduler = RaceScheduler::new();
+///     for _ in 0..4 {
+///         let mirrors = vec![ready("a"), ready("b")];
+///         mirrors.race().race_with(&mut scheduler).await;
+///     }
+///     assert_eq!(scheduler.wins(), &[2, 2]);
+/// })
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RaceScheduler {
+    wins: Vec<usize>,
+}
+
+impl RaceScheduler {
+    /// Create a new scheduler with no recorded wins.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of races won by the future at each index.
+    ///
+    /// Indexes which haven't taken part in a race yet aren't included.
+    pub fn wins(&self) -> &[usize] {
+        &self.wins
+    }
+
+    /// The order to poll `len` futures in, starting with the ones which have
+    /// won the fewest races. Ties are broken by index.
+    pub(crate) fn order(&mut self, len: usize) -> Vec<usize> {
+        if self.wins.len() < len {
+            self.wins.resize(len, 0);
+        }
+        let mut order: Vec<usize> = (0..len).collect();
+        order.sort_by_key(|&index| self.wins[index]);
+        order
+    }
+
+    /// Record that the future at `index` won a race.
+    pub(crate) fn record_win(&mut self, index: usize) {
+        self.wins[index] += 1;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn least_wins_first() {
+        let mut scheduler = RaceScheduler::new();
+        assert_eq!(scheduler.order(3), vec![0, 1, 2]);
+
+        scheduler.record_win(0);
+        scheduler.record_win(0);
+        scheduler.record_win(2);
+        assert_eq!(scheduler.order(3), vec![1, 2, 0]);
+        assert_eq!(scheduler.order(4), vec![1, 3, 2, 0]);
+    }
+}
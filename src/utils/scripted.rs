@@ -0,0 +1,85 @@
+//! Streams with scripted readiness, to check that combinators order their
+//! items deterministically.
+
+use std::{
+    collections::VecDeque,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll, Waker},
+};
+
+use futures::Stream;
+
+use super::CountingWaker;
+
+/// One step of a [`Scripted`] stream.
+#[derive(Debug, Clone)]
+pub(crate) enum Step<T> {
+    /// Yield an item.
+    Item(T),
+    /// Return `Poll::Pending`, waking the waker straight away.
+    Pending,
+}
+
+/// A stream which follows a fixed script, then ends.
+///
+/// Its readiness only depends on how many times it has been polled, never on
+/// anything outside of it.
+#[derive(Debug)]
+pub(crate) struct Scripted<T> {
+    script: VecDeque<Step<T>>,
+}
+
+impl<T> Scripted<T> {
+    pub(crate) fn new(script: impl IntoIterator<Item = Step<T>>) -> Self {
+        Self {
+            script: script.into_iter().collect(),
+        }
+    }
+}
+
+impl<T> Unpin for Scripted<T> {}
+
+impl<T> Stream for Scripted<T> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        match self.script.pop_front() {
+            Some(Step::Item(item)) => Poll::Ready(Some(item)),
+            Some(Step::Pending) => {
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+            None => Poll::Ready(None),
+        }
+    }
+}
+
+/// Poll `stream` on the current thread until it ends, recording the outcome
+/// of every poll.
+///
+/// # Panics
+///
+/// This panics if the stream returns `Poll::Pending` without waking its
+/// waker, since it would never be polled again.
+pub(crate) fn trace<S>(mut stream: S) -> Vec<Poll<S::Item>>
+where
+    S: Stream + Unpin,
+{
+    let counter = Arc::new(CountingWaker::default());
+    let waker = Waker::from(counter.clone());
+    let mut cx = Context::from_waker(&waker);
+
+    let mut trace = vec![];
+    loop {
+        let wakes = counter.count();
+        match Pin::new(&mut stream).poll_next(&mut cx) {
+            Poll::Ready(Some(item)) => trace.push(Poll::Ready(item)),
+            Poll::Ready(None) => return trace,
+            Poll::Pending => {
+                assert!(counter.count() != wakes, "stream stalled");
+                trace.push(Poll::Pending);
+            }
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use core::fmt;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures_core::Stream;
+
+use super::{Indexer, WakerVec};
+
+/// A growable set of futures, started from the items of a stream and polled
+/// as they're woken.
+///
+/// Slots are only added as they're needed, and boxed so they stay in place
+/// as the set grows. A slot is reused once its future completes, so the set
+/// never holds more slots than the most futures which ran at once.
+pub(crate) struct FutureSlots<Fut> {
+    slots: Vec<Option<Pin<Box<Fut>>>>,
+    /// The indexes of the empty slots.
+    free: Vec<usize>,
+    wakers: WakerVec,
+    indexer: Indexer,
+}
+
+impl<Fut> FutureSlots<Fut> {
+    pub(crate) fn new() -> Self {
+        Self {
+            slots: Vec::new(),
+            free: Vec::new(),
+            wakers: WakerVec::new(0),
+            indexer: Indexer::new(0),
+        }
+    }
+
+    /// The number of futures which are still running.
+    pub(crate) fn running(&self) -> usize {
+        self.slots.len() - self.free.len()
+    }
+
+    fn insert(&mut self, fut: Fut) {
+        let fut = Box::pin(fut);
+        match self.free.pop() {
+            Some(index) => {
+                self.slots[index] = Some(fut);
+                self.wakers.readiness().lock().unwrap().set_ready(index);
+            }
+            None => {
+                // New wakers start out ready.
+                self.slots.push(Some(fut));
+                self.wakers.push();
+                self.indexer.set_max(self.slots.len());
+            }
+        }
+    }
+}
+
+impl<Fut: Future> FutureSlots<Fut> {
+    /// Start a future for every item of `stream` while fewer than `limit` are
+    /// running, and poll the ones which were woken, handing their outputs to
+    /// `output`.
+    ///
+    /// This returns `Poll::Ready` once the stream has ended and every future
+    /// has completed.
+    pub(crate) fn poll_stream<S, F, O>(
+        &mut self,
+        mut stream: Pin<&mut S>,
+        stream_done: &mut bool,
+        limit: usize,
+        cx: &mut Context<'_>,
+        mut start: F,
+        mut output: O,
+    ) -> Poll<()>
+    where
+        S: Stream,
+        F: FnMut(S::Item) -> Fut,
+        O: FnMut(Fut::Output),
+    {
+        loop {
+            // Start new futures while there's room for them.
+            while !*stream_done && self.running() < limit {
+                match stream.as_mut().poll_next(cx) {
+                    Poll::Ready(Some(item)) => self.insert(start(item)),
+                    Poll::Ready(None) => *stream_done = true,
+                    Poll::Pending => break,
+                }
+            }
+
+            let mut readiness = self.wakers.readiness().lock().unwrap();
+            readiness.set_waker(cx.waker());
+
+            let mut freed = false;
+            for index in self.indexer.iter() {
+                if !readiness.any_ready() {
+                    break;
+                } else if !readiness.clear_ready(index) {
+                    continue;
+                }
+
+                // unlock readiness so we don't deadlock when polling
+                drop(readiness);
+
+                if let Some(fut) = self.slots[index].as_mut() {
+                    let mut cx = Context::from_waker(self.wakers.get(index).unwrap());
+                    if let Poll::Ready(out) = fut.as_mut().poll(&mut cx) {
+                        self.slots[index] = None;
+                        self.free.push(index);
+                        output(out);
+                        freed = true;
+                    }
+                }
+
+                readiness = self.wakers.readiness().lock().unwrap();
+            }
+            drop(readiness);
+
+            if *stream_done && self.running() == 0 {
+                return Poll::Ready(());
+            }
+            // Only go around again if a slot opened up for the stream to
+            // fill; otherwise we're waiting on a wakeup.
+            if !freed || *stream_done {
+                return Poll::Pending;
+            }
+        }
+    }
+}
+
+impl<Fut> fmt::Debug for FutureSlots<Fut> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FutureSlots")
+            .field("running", &self.running())
+            .finish()
+    }
+}
@@ -2,6 +2,6 @@ mod readiness;
 mod waker;
 mod waker_array;
 
-pub(crate) use readiness::ReadinessArray;
+pub use readiness::AtomicReadinessArray;
 pub(crate) use waker::InlineWakerArray;
-pub(crate) use waker_array::WakerArray;
+pub use waker_array::WakerArray;
@@ -1,67 +1,165 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
 use std::task::Waker;
 
-/// Tracks which wakers are "ready" and should be polled.
+use crate::utils::wakers::Readiness;
+
+const WORD_BITS: usize = usize::BITS as usize;
+
+/// Tracks which wakers are "ready" and should be polled, without locking.
+///
+/// The ready markers are kept as a bitset of atomic words, so children can be
+/// marked and cleared through a shared reference, from any thread. Only the
+/// parent waker sits behind a mutex, which is taken once per poll to replace
+/// it, and by a child waker when it marks its child ready.
 #[derive(Debug)]
-pub(crate) struct ReadinessArray<const N: usize> {
-    count: usize,
-    ready: [bool; N],
-    parent_waker: Option<Waker>,
+pub struct AtomicReadinessArray<const N: usize> {
+    /// The ready markers, `WORD_BITS` children per word. The length can't be
+    /// computed from `N` in a type on stable Rust, so the words are boxed.
+    words: Box<[AtomicUsize]>,
+    parent_waker: Mutex<Option<Waker>>,
 }
 
-impl<const N: usize> ReadinessArray<N> {
-    /// Create a new instance of readiness.
+impl<const N: usize> AtomicReadinessArray<N> {
+    /// Create a new instance of readiness, with every child marked as ready.
     pub(crate) fn new() -> Self {
-        Self {
-            count: N,
-            ready: [true; N], // TODO: use a bitarray instead
-            parent_waker: None,
-        }
+        let words = (0..N.div_ceil(WORD_BITS))
+            .map(|_| AtomicUsize::new(0))
+            .collect();
+        let this = Self {
+            words,
+            parent_waker: Mutex::new(None),
+        };
+        this.set_all_ready();
+        this
     }
 
     /// Returns the old ready state for this id
-    pub(crate) fn set_ready(&mut self, id: usize) -> bool {
-        if !self.ready[id] {
-            self.count += 1;
-            self.ready[id] = true;
-
-            false
-        } else {
-            true
-        }
+    pub(crate) fn set_ready(&self, id: usize) -> bool {
+        let (word, mask) = locate(id);
+        self.words[word].fetch_or(mask, Ordering::AcqRel) & mask != 0
     }
 
     /// Set all markers to ready.
-    pub(crate) fn set_all_ready(&mut self) {
-        self.ready.fill(true);
-        self.count = N;
+    pub(crate) fn set_all_ready(&self) {
+        let full = N / WORD_BITS;
+        for word in &self.words[..full] {
+            word.store(usize::MAX, Ordering::Release);
+        }
+        if let Some(last) = self.words.get(full) {
+            last.store((1 << (N % WORD_BITS)) - 1, Ordering::Release);
+        }
     }
 
     /// Returns whether the task id was previously ready
-    pub(crate) fn clear_ready(&mut self, id: usize) -> bool {
-        if self.ready[id] {
-            self.count -= 1;
-            self.ready[id] = false;
-
-            true
-        } else {
-            false
-        }
+    pub(crate) fn clear_ready(&self, id: usize) -> bool {
+        let (word, mask) = locate(id);
+        self.words[word].fetch_and(!mask, Ordering::AcqRel) & mask != 0
     }
 
     /// Returns `true` if any of the wakers are ready.
     pub(crate) fn any_ready(&self) -> bool {
-        self.count > 0
-    }
-
-    /// Access the parent waker.
-    #[inline]
-    pub(crate) fn parent_waker(&self) -> Option<&Waker> {
-        self.parent_waker.as_ref()
+        self.words
+            .iter()
+            .any(|word| word.load(Ordering::Acquire) != 0)
     }
 
     /// Set the parent `Waker`. This needs to be called at the start of every
     /// `poll` function.
-    pub(crate) fn set_waker(&mut self, parent_waker: &Waker) {
-        self.parent_waker = Some(parent_waker.clone());
+    pub(crate) fn set_waker(&self, parent_waker: &Waker) {
+        let mut waker = self.parent_waker.lock().unwrap();
+        match &*waker {
+            Some(waker) if waker.will_wake(parent_waker) => {}
+            _ => *waker = Some(parent_waker.clone()),
+        }
+    }
+
+    /// Mark child `id` as ready, waking the parent if it wasn't ready
+    /// already.
+    ///
+    /// Poll loops clear a child's bit before polling it, so a wakeup which
+    /// arrives while the child is being polled sees the transition and wakes
+    /// the parent. No wakeups are lost in that window.
+    pub(crate) fn wake(&self, id: usize) {
+        if !self.set_ready(id) {
+            self.parent_waker
+                .lock()
+                .unwrap()
+                .as_ref()
+                .expect("`parent_waker` not available from `Readiness`. Did you forget to call `Readiness::set_waker`?")
+                .wake_by_ref()
+        }
+    }
+}
+
+/// The word holding the marker for `id`, and the marker's mask within it.
+fn locate(id: usize) -> (usize, usize) {
+    (id / WORD_BITS, 1 << (id % WORD_BITS))
+}
+
+impl<const N: usize> Default for AtomicReadinessArray<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Readiness for AtomicReadinessArray<N> {
+    fn set_waker(&mut self, parent_waker: &Waker) {
+        AtomicReadinessArray::set_waker(self, parent_waker)
+    }
+
+    fn any_ready(&self) -> bool {
+        AtomicReadinessArray::any_ready(self)
+    }
+
+    fn set_ready(&mut self, id: usize) -> bool {
+        AtomicReadinessArray::set_ready(self, id)
+    }
+
+    fn clear_ready(&mut self, id: usize) -> bool {
+        AtomicReadinessArray::clear_ready(self, id)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::CountingWaker;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn starts_out_ready() {
+        let readiness = AtomicReadinessArray::<70>::new();
+        assert!((0..70).all(|id| readiness.clear_ready(id)));
+        assert!(!readiness.any_ready());
+        assert!(!readiness.clear_ready(69));
+    }
+
+    #[test]
+    fn concurrent_wakes_wake_the_parent_once_per_child() {
+        let readiness = Arc::new(AtomicReadinessArray::<130>::new());
+        let counter = Arc::new(CountingWaker::default());
+        readiness.set_waker(&Waker::from(counter.clone()));
+        for id in 0..130 {
+            readiness.clear_ready(id);
+        }
+
+        let threads: Vec<_> = (0..4)
+            .map(|_| {
+                let readiness = readiness.clone();
+                thread::spawn(move || (0..130).for_each(|id| readiness.wake(id)))
+            })
+            .collect();
+        threads.into_iter().for_each(|t| t.join().unwrap());
+
+        assert_eq!(counter.count(), 130);
+        assert!((0..130).all(|id| readiness.clear_ready(id)));
+    }
+
+    #[test]
+    fn empty() {
+        let readiness = AtomicReadinessArray::<0>::new();
+        assert!(!readiness.any_ready());
     }
 }
@@ -1,20 +1,20 @@
 use core::array;
 use std::sync::Arc;
-use std::sync::Mutex;
 use std::task::Waker;
 
-use super::{InlineWakerArray, ReadinessArray};
+use super::{AtomicReadinessArray, InlineWakerArray};
 
 /// A collection of wakers which delegate to an in-line waker.
-pub(crate) struct WakerArray<const N: usize> {
+#[derive(Debug)]
+pub struct WakerArray<const N: usize> {
     wakers: [Waker; N],
-    readiness: Arc<Mutex<ReadinessArray<N>>>,
+    readiness: Arc<AtomicReadinessArray<N>>,
 }
 
 impl<const N: usize> WakerArray<N> {
     /// Create a new instance of `WakerArray`.
-    pub(crate) fn new() -> Self {
-        let readiness = Arc::new(Mutex::new(ReadinessArray::new()));
+    pub fn new() -> Self {
+        let readiness = Arc::new(AtomicReadinessArray::new());
         Self {
             wakers: array::from_fn(|i| {
                 Arc::new(InlineWakerArray::new(i, readiness.clone())).into()
@@ -23,12 +23,23 @@ impl<const N: usize> WakerArray<N> {
         }
     }
 
-    pub(crate) fn get(&self, index: usize) -> Option<&Waker> {
+    /// The waker for child `index`, if it exists.
+    pub fn get(&self, index: usize) -> Option<&Waker> {
         self.wakers.get(index)
     }
 
     /// Access the `Readiness`.
-    pub(crate) fn readiness(&self) -> &Mutex<ReadinessArray<N>> {
+    ///
+    /// Unlike [`WakerVec::readiness`][crate::primitives::WakerVec::readiness]
+    /// this doesn't need to be locked, so it can be held on to while polling
+    /// the children.
+    pub fn readiness(&self) -> &AtomicReadinessArray<N> {
         self.readiness.as_ref()
     }
 }
+
+impl<const N: usize> Default for WakerArray<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,6 +1,30 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::{sync::Arc, task::Wake};
 
+#[cfg(test)]
 pub(crate) struct DummyWaker();
+#[cfg(test)]
 impl Wake for DummyWaker {
     fn wake(self: Arc<Self>) {}
 }
+
+/// A waker which counts the number of times it has been woken.
+#[derive(Default)]
+pub(crate) struct CountingWaker(AtomicUsize);
+
+impl CountingWaker {
+    /// The number of times the waker has been woken so far.
+    pub(crate) fn count(&self) -> usize {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+impl Wake for CountingWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref()
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
@@ -1,10 +1,18 @@
 mod array;
-#[cfg(test)]
+#[cfg(any(test, feature = "test-util"))]
 mod dummy;
+mod readiness;
+#[cfg(feature = "waker-stats")]
+mod stats;
 mod vec;
 
+#[cfg(any(test, feature = "test-util"))]
+pub(crate) use dummy::CountingWaker;
 #[cfg(test)]
 pub(crate) use dummy::DummyWaker;
 
-pub(crate) use array::*;
-pub(crate) use vec::*;
+pub use array::{AtomicReadinessArray, WakerArray};
+pub use readiness::Readiness;
+#[cfg(feature = "waker-stats")]
+pub use stats::WakerStats;
+pub use vec::{ReadinessVec, WakerVec};
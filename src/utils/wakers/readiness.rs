@@ -0,0 +1,32 @@
+use std::task::Waker;
+
+/// Tracks which child futures or streams have been woken.
+///
+/// Each child is identified by its index. Children start out ready so that
+/// they are all polled at least once. A child waker marks its own index as
+/// ready, waking the parent [`Waker`] if it wasn't ready already. The parent
+/// clears the mark before polling the child.
+///
+/// This is implemented by [`ReadinessVec`][super::ReadinessVec], which is
+/// shared behind a mutex by [`WakerVec`][super::WakerVec], and by the
+/// lock-free [`AtomicReadinessArray`][super::AtomicReadinessArray] shared by
+/// [`WakerArray`][super::WakerArray]. This API is semi-stable: it's relied on by every combinator
+/// in this crate, but may still change between minor releases.
+pub trait Readiness {
+    /// Set the parent `Waker`. This needs to be called at the start of every
+    /// `poll` function, before any child is polled.
+    fn set_waker(&mut self, parent_waker: &Waker);
+
+    /// Returns `true` if any of the children are ready.
+    fn any_ready(&self) -> bool;
+
+    /// Mark child `id` as ready, returning whether it was ready already.
+    ///
+    /// This doesn't wake the parent waker.
+    fn set_ready(&mut self, id: usize) -> bool;
+
+    /// Mark child `id` as not ready, returning whether it was ready before.
+    ///
+    /// A child should only be polled after this returns `true`.
+    fn clear_ready(&mut self, id: usize) -> bool;
+}
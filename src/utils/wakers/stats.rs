@@ -0,0 +1,34 @@
+/// Counts of how often a combinator was woken, and whether waking it was
+/// worth it.
+///
+/// This is only available with the `waker-stats` feature. A poll is
+/// productive when it yields an item, and spurious otherwise, so a source
+/// which wakes the combinator much more often than it yields items shows up
+/// as a high number of spurious polls.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WakerStats {
+    pub(crate) wakes: u64,
+    pub(crate) spurious: u64,
+    pub(crate) productive: u64,
+}
+
+impl WakerStats {
+    /// The number of times a source woke the task polling the combinator.
+    ///
+    /// Wakeups for a source which was already marked as ready don't wake the
+    /// task again, and aren't counted.
+    pub fn wakes(&self) -> u64 {
+        self.wakes
+    }
+
+    /// The number of polls which didn't yield an item, including the ones
+    /// where a source had woken the task.
+    pub fn spurious(&self) -> u64 {
+        self.spurious
+    }
+
+    /// The number of polls which yielded an item.
+    pub fn productive(&self) -> u64 {
+        self.productive
+    }
+}
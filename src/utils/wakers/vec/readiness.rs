@@ -1,13 +1,22 @@
 use bitvec::{bitvec, vec::BitVec};
 use std::task::Waker;
 
+use crate::utils::wakers::Readiness;
+#[cfg(feature = "waker-stats")]
+use crate::utils::wakers::WakerStats;
+
 /// Tracks which wakers are "ready" and should be polled.
 #[derive(Debug)]
-pub(crate) struct ReadinessVec {
+pub struct ReadinessVec {
     count: usize,
     max_count: usize,
     ready: BitVec,
+    /// The markers which haven't been retired. Wakeups for the others are
+    /// ignored.
+    live: BitVec,
     parent_waker: Option<Waker>,
+    #[cfg(feature = "waker-stats")]
+    pub(crate) stats: WakerStats,
 }
 
 impl ReadinessVec {
@@ -17,12 +26,25 @@ impl ReadinessVec {
             count,
             max_count: count,
             ready: bitvec![true as usize; count],
+            live: bitvec![true as usize; count],
             parent_waker: None,
+            #[cfg(feature = "waker-stats")]
+            stats: WakerStats::default(),
         }
     }
 
     /// Returns the old ready state for this id
+    ///
+    /// This only sets the bit: a child waker wakes the parent when it
+    /// returns `false`. Poll loops clear a child's bit before unlocking the
+    /// readiness to poll it, so a wakeup which arrives while the lock is
+    /// released sees the transition and wakes the parent. No wakeups are
+    /// lost in that window.
     pub(crate) fn set_ready(&mut self, id: usize) -> bool {
+        if !self.live.get(id).is_some_and(|live| *live) {
+            // A retired waker may still be woken through a clone of it.
+            return true;
+        }
         if !self.ready[id] {
             self.count += 1;
             self.ready.set(id, true);
@@ -33,9 +55,40 @@ impl ReadinessVec {
         }
     }
 
+    /// Add a marker for one more waker, which starts out ready.
+    pub(crate) fn push(&mut self) {
+        self.ready.push(true);
+        self.live.push(true);
+        self.count += 1;
+        self.max_count += 1;
+    }
+
+    /// Stop tracking the marker for `id`, ignoring any wakeups for it from
+    /// now on.
+    pub(crate) fn retire(&mut self, id: usize) {
+        if self.live.replace(id, false) {
+            self.clear_ready(id);
+            self.max_count -= 1;
+        }
+    }
+
+    /// Returns `true` if the marker for `id` hasn't been retired.
+    pub(crate) fn is_live(&self, id: usize) -> bool {
+        self.live[id]
+    }
+
+    /// Drop the markers from `len` onwards, which must all be retired.
+    pub(crate) fn truncate(&mut self, len: usize) {
+        debug_assert!(self.live[len..].not_any());
+        self.ready.truncate(len);
+        self.live.truncate(len);
+        self.ready.shrink_to_fit();
+        self.live.shrink_to_fit();
+    }
+
     /// Set all markers to ready.
     pub(crate) fn set_all_ready(&mut self) {
-        self.ready.fill(true);
+        self.ready.copy_from_bitslice(&self.live);
         self.count = self.max_count;
     }
 
@@ -68,3 +121,21 @@ impl ReadinessVec {
         self.parent_waker = Some(parent_waker.clone());
     }
 }
+
+impl Readiness for ReadinessVec {
+    fn set_waker(&mut self, parent_waker: &Waker) {
+        ReadinessVec::set_waker(self, parent_waker)
+    }
+
+    fn any_ready(&self) -> bool {
+        ReadinessVec::any_ready(self)
+    }
+
+    fn set_ready(&mut self, id: usize) -> bool {
+        ReadinessVec::set_ready(self, id)
+    }
+
+    fn clear_ready(&mut self, id: usize) -> bool {
+        ReadinessVec::clear_ready(self, id)
+    }
+}
@@ -21,6 +21,10 @@ impl Wake for InlineWakerVec {
     fn wake(self: std::sync::Arc<Self>) {
         let mut readiness = self.readiness.lock().unwrap();
         if !readiness.set_ready(self.id) {
+            #[cfg(feature = "waker-stats")]
+            {
+                readiness.stats.wakes += 1;
+            }
             readiness
                 .parent_waker()
                 .as_mut()
@@ -5,27 +5,158 @@ use std::task::Waker;
 use super::{InlineWakerVec, ReadinessVec};
 
 /// A collection of wakers which delegate to an in-line waker.
-pub(crate) struct WakerVec {
-    wakers: Vec<Waker>,
+#[derive(Debug)]
+pub struct WakerVec {
+    /// `None` for the wakers which were retired and then dropped by
+    /// `shrink_to_fit`.
+    wakers: Vec<Option<Waker>>,
     readiness: Arc<Mutex<ReadinessVec>>,
 }
 
 impl WakerVec {
     /// Create a new instance of `WakerVec`.
-    pub(crate) fn new(len: usize) -> Self {
+    pub fn new(len: usize) -> Self {
         let readiness = Arc::new(Mutex::new(ReadinessVec::new(len)));
         let wakers = (0..len)
-            .map(|i| Arc::new(InlineWakerVec::new(i, readiness.clone())).into())
+            .map(|i| Some(Arc::new(InlineWakerVec::new(i, readiness.clone())).into()))
             .collect();
         Self { wakers, readiness }
     }
 
-    pub(crate) fn get(&self, index: usize) -> Option<&Waker> {
-        self.wakers.get(index)
+    /// Add a waker for one more child, which starts out ready.
+    pub fn push(&mut self) {
+        let index = self.wakers.len();
+        self.readiness.lock().unwrap().push();
+        let waker = Arc::new(InlineWakerVec::new(index, self.readiness.clone()));
+        self.wakers.push(Some(waker.into()));
+    }
+
+    /// The waker for child `index`, if it exists.
+    ///
+    /// This returns `None` for wakers which have been retired and dropped.
+    pub fn get(&self, index: usize) -> Option<&Waker> {
+        self.wakers.get(index)?.as_ref()
+    }
+
+    /// The number of children, including the retired ones which haven't
+    /// been dropped by [`shrink_to_fit`][WakerVec::shrink_to_fit].
+    pub fn len(&self) -> usize {
+        self.wakers.len()
+    }
+
+    /// Returns `true` if there are no children.
+    pub fn is_empty(&self) -> bool {
+        self.wakers.is_empty()
+    }
+
+    /// The number of children there is room for without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.wakers.capacity()
+    }
+
+    /// Stop tracking child `index`, because it has been removed.
+    ///
+    /// The child isn't ready from now on, and wakeups through clones of its
+    /// waker are ignored. Its waker is kept until
+    /// [`shrink_to_fit`][WakerVec::shrink_to_fit] is called, and indexes
+    /// of the other children don't change.
+    ///
+    /// # Panics
+    ///
+    /// This panics if `index` is out of bounds.
+    pub fn retire(&mut self, index: usize) {
+        self.readiness.lock().unwrap().retire(index);
+    }
+
+    /// Returns `true` if child `index` hasn't been retired.
+    ///
+    /// # Panics
+    ///
+    /// This panics if `index` is out of bounds.
+    pub fn is_live(&self, index: usize) -> bool {
+        self.readiness.lock().unwrap().is_live(index)
+    }
+
+    /// Drop the wakers of the retired children, and release the memory
+    /// they took up.
+    ///
+    /// Retired children at the end are removed altogether, which shortens
+    /// [`len`][WakerVec::len], and the next child to be
+    /// [`push`][WakerVec::push]ed reuses the first free index. Retired
+    /// children in between keep their index, but their waker is dropped.
+    /// Clones of a dropped waker which are still held elsewhere keep its
+    /// allocation alive until they're dropped too.
+    pub fn shrink_to_fit(&mut self) {
+        let mut readiness = self.readiness.lock().unwrap();
+        for (index, waker) in self.wakers.iter_mut().enumerate() {
+            if !readiness.is_live(index) {
+                *waker = None;
+            }
+        }
+        let len = self
+            .wakers
+            .iter()
+            .rposition(Option::is_some)
+            .map_or(0, |i| i + 1);
+        self.wakers.truncate(len);
+        self.wakers.shrink_to_fit();
+        readiness.truncate(len);
     }
 
     /// Access the `Readiness`.
-    pub(crate) fn readiness(&self) -> &Mutex<ReadinessVec> {
+    pub fn readiness(&self) -> &Mutex<ReadinessVec> {
         self.readiness.as_ref()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn shrink_drops_retired_wakers() {
+        let mut wakers = WakerVec::new(4);
+        // Each waker holds on to the readiness, so its reference count is
+        // one for the `WakerVec` itself plus one per waker.
+        assert_eq!(Arc::strong_count(&wakers.readiness), 5);
+
+        wakers.retire(1);
+        wakers.retire(3);
+        assert!(!wakers.is_live(1));
+        assert_eq!(Arc::strong_count(&wakers.readiness), 5);
+
+        wakers.shrink_to_fit();
+        assert_eq!(Arc::strong_count(&wakers.readiness), 3);
+        assert!(wakers.get(1).is_none());
+        // The retired child at the end is gone, the one in between keeps
+        // its index.
+        assert_eq!(wakers.len(), 3);
+        assert_eq!(wakers.capacity(), 3);
+
+        wakers.push();
+        assert_eq!(wakers.len(), 4);
+        assert!(wakers.is_live(3));
+    }
+
+    #[test]
+    fn retired_wakeups_are_ignored() {
+        let mut wakers = WakerVec::new(2);
+        let stale = wakers.get(1).unwrap().clone();
+        {
+            let mut readiness = wakers.readiness().lock().unwrap();
+            readiness.clear_ready(0);
+            readiness.clear_ready(1);
+        }
+        wakers.retire(1);
+        wakers.shrink_to_fit();
+        assert_eq!(wakers.len(), 1);
+
+        // The clone outlives the retired child, but waking it does nothing.
+        stale.wake();
+        assert!(!wakers.readiness().lock().unwrap().any_ready());
+
+        wakers.readiness().lock().unwrap().set_all_ready();
+        assert!(wakers.readiness().lock().unwrap().clear_ready(0));
+        assert!(!wakers.readiness().lock().unwrap().any_ready());
+    }
+}
@@ -0,0 +1,98 @@
+#![cfg(feature = "derive")]
+
+use futures_concurrency::future::Join;
+use futures_lite::future::block_on;
+use std::future::{ready, Future, Ready};
+
+#[derive(Join)]
+struct Named<A, B>
+where
+    B: Future<Output = u8>,
+{
+    a: A,
+    b: B,
+    c: Ready<&'static str>,
+}
+
+#[derive(Join)]
+struct Tuple<'a>(Ready<&'a str>, Ready<u8>);
+
+#[derive(Join)]
+struct Unit;
+
+#[derive(Join)]
+struct Sixteen(
+    Ready<u8>,
+    Ready<u8>,
+    Ready<u8>,
+    Ready<u8>,
+    Ready<u8>,
+    Ready<u8>,
+    Ready<u8>,
+    Ready<u8>,
+    Ready<u8>,
+    Ready<u8>,
+    Ready<u8>,
+    Ready<u8>,
+    Ready<u8>,
+    Ready<u8>,
+    Ready<u8>,
+    Ready<u8>,
+);
+
+#[test]
+fn named_fields() {
+    block_on(async {
+        let named = Named {
+            a: async { vec![1, 2] },
+            b: ready(3),
+            c: ready("c"),
+        };
+        let NamedOutput { a, b, c } = named.join_fields().await;
+        assert_eq!(a, vec![1, 2]);
+        assert_eq!(b, 3);
+        assert_eq!(c, "c");
+    })
+}
+
+#[test]
+fn tuple_fields() {
+    block_on(async {
+        let s = String::from("borrowed");
+        let TupleOutput(a, b) = Tuple(ready(&s), ready(1)).join_fields().await;
+        assert_eq!((a, b), ("borrowed", 1));
+    })
+}
+
+#[test]
+fn unit() {
+    block_on(async {
+        let UnitOutput = Unit.join_fields().await;
+    })
+}
+
+#[test]
+fn sixteen_fields() {
+    block_on(async {
+        let s = Sixteen(
+            ready(0),
+            ready(1),
+            ready(2),
+            ready(3),
+            ready(4),
+            ready(5),
+            ready(6),
+            ready(7),
+            ready(8),
+            ready(9),
+            ready(10),
+            ready(11),
+            ready(12),
+            ready(13),
+            ready(14),
+            ready(15),
+        );
+        let SixteenOutput(a, .., p) = s.join_fields().await;
+        assert_eq!((a, p), (0, 15));
+    })
+}
@@ -0,0 +1,84 @@
+//! The prelude can be glob-imported next to the preludes of `futures` and
+//! `futures-lite` without making method calls ambiguous. These tests fail to
+//! compile if it can't.
+
+mod with_futures {
+    use futures::executor::block_on;
+    use futures::prelude::*;
+    use futures::stream;
+    use futures_concurrency::prelude::*;
+
+    #[test]
+    fn methods_resolve() {
+        block_on(async {
+            let a = future::ready(1);
+            let b = future::ready(2);
+            assert_eq!((a, b).join().await, (1, 2));
+            assert_eq!(vec![future::ready(3)].race().await, 3);
+
+            let s = vec![stream::iter(vec![1]), stream::iter(vec![2])].merge();
+            assert_eq!(s.count().await, 2);
+            let s = stream::iter(vec![1]).merge(stream::iter(vec![2]));
+            assert_eq!(s.count().await, 2);
+
+            let s = [stream::iter(vec![1]), stream::iter(vec![2])].zip();
+            assert_eq!(s.collect::<Vec<_>>().await, vec![[1, 2]]);
+            // `zip` on a stream is still `StreamExt::zip`.
+            let s = stream::iter(vec![1]).zip(stream::iter(vec!['a']));
+            assert_eq!(s.collect::<Vec<_>>().await, vec![(1, 'a')]);
+        })
+    }
+}
+
+mod with_futures_lite {
+    use futures_concurrency::prelude::*;
+    use futures_lite::future::block_on;
+    use futures_lite::prelude::*;
+    use futures_lite::{future, stream};
+
+    #[test]
+    fn methods_resolve() {
+        block_on(async {
+            let a = future::ready(1);
+            let b = future::ready(2);
+            assert_eq!((a, b).join().await, (1, 2));
+            assert_eq!(vec![future::ready(3)].race().await, 3);
+
+            let s = vec![stream::once(1), stream::once(2)].merge();
+            assert_eq!(s.count().await, 2);
+            let s = stream::once(1).merge(stream::once(2));
+            assert_eq!(s.count().await, 2);
+
+            let s = [stream::once(1), stream::once(2)].chain();
+            assert_eq!(s.collect::<Vec<_>>().await, vec![1, 2]);
+            // `chain` on a stream is still `StreamExt::chain`.
+            let s = stream::once(1).chain(stream::once(2));
+            assert_eq!(s.collect::<Vec<_>>().await, vec![1, 2]);
+        })
+    }
+}
+
+// `merge` on a stream comes from `MergeExt`, and `chain` from `StreamExt`,
+// since `StreamCombineExt` isn't part of the prelude.
+mod with_stream_ext {
+    use futures::executor::block_on;
+    use futures::stream;
+    use futures::StreamExt;
+    use futures_concurrency::prelude::*;
+
+    #[test]
+    fn merge_and_chain_resolve() {
+        block_on(async {
+            let a = stream::iter(vec![1, 2]);
+            let b = stream::iter(vec![3]);
+            let mut items: Vec<_> = a.merge(b).collect().await;
+            items.sort_unstable();
+            assert_eq!(items, vec![1, 2, 3]);
+
+            let a = stream::iter(vec![1, 2]);
+            let b = stream::iter(vec![3]);
+            let items: Vec<_> = a.chain(b).collect().await;
+            assert_eq!(items, vec![1, 2, 3]);
+        })
+    }
+}
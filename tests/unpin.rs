@@ -0,0 +1,63 @@
+//! Combinators over `Unpin` futures and streams are `Unpin` themselves.
+//!
+//! The combinators are `#[pin_project]` structs, which only implement `Unpin`
+//! when all of their `#[pin]` fields do. These tests make sure that guarantee
+//! doesn't regress: they fail to compile if it does.
+
+use futures_concurrency::prelude::*;
+use futures_lite::stream;
+use std::future::ready;
+
+fn assert_unpin<T: Unpin>(_: &T) {}
+
+#[test]
+fn join() {
+    assert_unpin(&vec![ready(1), ready(2)].join());
+    assert_unpin(&[ready(1), ready(2)].join());
+    assert_unpin(&(ready(1), ready("a")).join());
+}
+
+#[test]
+fn try_join() {
+    let ok = || ready(Ok::<u8, ()>(1));
+    assert_unpin(&vec![ok(), ok()].try_join());
+    assert_unpin(&[ok(), ok()].try_join());
+}
+
+#[test]
+fn race() {
+    assert_unpin(&vec![ready(1), ready(2)].race());
+    assert_unpin(&[ready(1), ready(2)].race());
+    assert_unpin(&(ready(1), ready(2)).race());
+}
+
+#[test]
+fn race_ok() {
+    let ok = || ready(Ok::<u8, ()>(1));
+    assert_unpin(&vec![ok(), ok()].race_ok());
+    assert_unpin(&[ok(), ok()].race_ok());
+    assert_unpin(&(ok(), ok()).race_ok());
+}
+
+#[test]
+fn merge() {
+    let s = || stream::iter(vec![1, 2]);
+    assert_unpin(&vec![s(), s()].merge());
+    assert_unpin(&[s(), s()].merge());
+    assert_unpin(&(s(), s()).merge());
+    assert_unpin(&futures_concurrency::stream::MergeExt::merge(s(), s()));
+}
+
+#[test]
+fn chain() {
+    let s = || stream::iter(vec![1, 2]);
+    assert_unpin(&vec![s(), s()].chain());
+    assert_unpin(&[s(), s()].chain());
+}
+
+#[test]
+fn zip() {
+    let s = || stream::iter(vec![1, 2]);
+    assert_unpin(&vec![s(), s()].zip());
+    assert_unpin(&[s(), s()].zip());
+}